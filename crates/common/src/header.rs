@@ -1,7 +1,12 @@
 use fake::Dummy;
 
 use crate::prelude::*;
-use crate::{BlockCommitmentSignature, ReceiptCommitment, StateDiffCommitment};
+use crate::{
+    BlockBodyCommitment,
+    BlockCommitmentSignature,
+    ReceiptCommitment,
+    StateDiffCommitment,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Dummy)]
 pub struct BlockHeader {
@@ -28,6 +33,10 @@ pub struct BlockHeader {
     pub receipt_commitment: ReceiptCommitment,
     pub state_diff_commitment: StateDiffCommitment,
     pub state_diff_length: u64,
+    /// Commitment over the full block body, including events. Not yet part of
+    /// any Starknet version, so this is zero for all existing blocks.
+    #[dummy(default)]
+    pub block_body_commitment: BlockBodyCommitment,
 }
 
 #[derive(
@@ -181,6 +190,11 @@ impl BlockHeaderBuilder {
         self
     }
 
+    pub fn block_body_commitment(mut self, block_body_commitment: BlockBodyCommitment) -> Self {
+        self.0.block_body_commitment = block_body_commitment;
+        self
+    }
+
     pub fn finalize_with_hash(mut self, hash: BlockHash) -> BlockHeader {
         self.0.hash = hash;
         self.0
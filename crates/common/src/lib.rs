@@ -237,7 +237,7 @@ impl TransactionVersion {
 }
 
 /// A way of identifying a specific block.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
 #[cfg_attr(any(test, feature = "full-serde"), derive(Serialize))]
 #[serde(deny_unknown_fields)]
 pub enum BlockId {
@@ -249,6 +249,10 @@ pub enum BlockId {
     Latest,
     #[serde(rename = "pending")]
     Pending,
+    /// The latest block which has been accepted on L1. `finalized` is accepted
+    /// as an alias, following the Ethereum naming.
+    #[serde(rename = "safe", alias = "finalized")]
+    L1Accepted,
 }
 
 impl BlockId {
@@ -511,6 +515,7 @@ impl Display for StarknetVersion {
 macros::felt_newtypes!(
     [
         AccountDeploymentDataElem,
+        BlockBodyCommitment,
         BlockHash,
         ByteCodeOffset,
         BlockCommitmentSignatureElem,
@@ -693,6 +698,23 @@ mod tests {
         }
     }
 
+    mod block_timestamp {
+        use super::super::BlockTimestamp;
+
+        #[test]
+        fn values_beyond_i64_are_rejected() {
+            assert_eq!(
+                BlockTimestamp::new(i64::MAX as u64),
+                Some(BlockTimestamp::new_or_panic(i64::MAX as u64))
+            );
+            assert_eq!(BlockTimestamp::new(i64::MAX as u64 + 1), None);
+            assert_eq!(BlockTimestamp::new(u64::MAX), None);
+
+            serde_json::from_str::<BlockTimestamp>(&u64::MAX.to_string()).unwrap_err();
+            serde_json::from_str::<BlockTimestamp>("-1").unwrap_err();
+        }
+    }
+
     mod block_id_serde {
         use super::super::BlockId;
 
@@ -708,6 +730,18 @@ mod tests {
             assert_eq!(result, BlockId::Pending);
         }
 
+        #[test]
+        fn safe() {
+            let result = serde_json::from_str::<BlockId>(r#""safe""#).unwrap();
+            assert_eq!(result, BlockId::L1Accepted);
+        }
+
+        #[test]
+        fn finalized() {
+            let result = serde_json::from_str::<BlockId>(r#""finalized""#).unwrap();
+            assert_eq!(result, BlockId::L1Accepted);
+        }
+
         #[test]
         fn number() {
             use crate::BlockNumber;
@@ -28,6 +28,7 @@ pub mod metrics {
         CounterFn,
         Gauge,
         Histogram,
+        HistogramFn,
         Key,
         KeyName,
         Label,
@@ -74,16 +75,21 @@ pub mod metrics {
     pub struct FakeRecorder(FakeRecorderHandle);
 
     /// Handle to the [`FakeRecorder`], which allows to get the current value of
-    /// counters.
+    /// counters and the number of values recorded by histograms.
     #[derive(Clone, Debug, Default)]
     pub struct FakeRecorderHandle {
         counters: Arc<RwLock<HashMap<Key, Arc<FakeCounterFn>>>>,
+        histograms: Arc<RwLock<HashMap<Key, Arc<FakeHistogramFn>>>>,
         methods: Option<&'static [&'static str]>,
     }
 
     #[derive(Debug, Default)]
     struct FakeCounterFn(AtomicU64);
 
+    /// Only counts the recorded values.
+    #[derive(Debug, Default)]
+    struct FakeHistogramFn(AtomicU64);
+
     impl Recorder for FakeRecorder {
         fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
         fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
@@ -120,9 +126,20 @@ pub mod metrics {
         fn register_gauge(&self, _: &Key) -> Gauge {
             unimplemented!()
         }
-        fn register_histogram(&self, _: &Key) -> Histogram {
-            // Ignored in tests for now
-            Histogram::noop()
+        /// Registers a histogram if the method is on the `self::methods` list
+        /// and returns it.
+        ///
+        /// # Warning
+        ///
+        /// Returns `Histogram::noop()` in other cases.
+        fn register_histogram(&self, key: &Key) -> Histogram {
+            if self.is_key_used(key) {
+                let mut write_guard = self.0.histograms.write().unwrap();
+                let histogram = write_guard.entry(key.clone()).or_default();
+                Histogram::from_arc(histogram.clone())
+            } else {
+                Histogram::noop()
+            }
         }
     }
 
@@ -134,6 +151,7 @@ pub mod metrics {
         pub fn new_for(methods: &'static [&'static str]) -> Self {
             Self(FakeRecorderHandle {
                 counters: Arc::default(),
+                histograms: Arc::default(),
                 methods: Some(methods),
             })
         }
@@ -200,6 +218,29 @@ pub mod metrics {
                 .0
                 .load(Ordering::Relaxed)
         }
+
+        /// Returns the number of values recorded by a histogram.
+        ///
+        /// Panics if `histogram_name` with `labels` was not registered via
+        /// [`metrics::register_histogram`]
+        pub fn get_histogram_count_by_label<const N: usize>(
+            &self,
+            histogram_name: &'static str,
+            labels: [(&'static str, &'static str); N],
+        ) -> u64 {
+            let read_guard = self.histograms.read().unwrap();
+            read_guard
+                .get(&Key::from_parts(
+                    histogram_name,
+                    labels
+                        .iter()
+                        .map(|&(key, val)| Label::new(key, val))
+                        .collect::<Vec<_>>(),
+                ))
+                .expect("Unregistered histogram name")
+                .0
+                .load(Ordering::Relaxed)
+        }
     }
 
     impl CounterFn for FakeCounterFn {
@@ -210,4 +251,10 @@ pub mod metrics {
             unimplemented!()
         }
     }
+
+    impl HistogramFn for FakeHistogramFn {
+        fn record(&self, _: f64) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
 }
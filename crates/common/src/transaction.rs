@@ -66,7 +66,7 @@ impl Default for TransactionVariant {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TransactionKind {
     Declare,
     Deploy,
@@ -55,6 +55,10 @@ pub struct EthereumStateUpdate {
     pub state_root: StateCommitment,
     pub block_number: BlockNumber,
     pub block_hash: BlockHash,
+    /// The Ethereum block containing the state update, if known.
+    pub l1_block_number: Option<L1BlockNumber>,
+    /// The Ethereum transaction containing the state update, if known.
+    pub l1_transaction_hash: Option<L1TransactionHash>,
 }
 
 /// Ethereum API trait
@@ -175,6 +179,9 @@ impl EthereumApi for EthereumClient {
                     let eth_block = L1BlockNumber::new_or_panic(
                         state_update.block_number.expect("missing eth block number")
                     );
+                    let eth_tx_hash = state_update
+                        .transaction_hash
+                        .map(|hash| L1TransactionHash::from(hash.0));
                     let state_update: Log<StarknetCoreContract::LogStateUpdate> = state_update.log_decode()?;
                     let block_number = get_block_number(state_update.inner.blockNumber);
                     // Add or remove to/from pending state updates accordingly
@@ -183,6 +190,8 @@ impl EthereumApi for EthereumClient {
                             block_number,
                             block_hash: get_block_hash(state_update.inner.blockHash),
                             state_root: get_state_root(state_update.inner.globalRoot),
+                            l1_block_number: Some(eth_block),
+                            l1_transaction_hash: eth_tx_hash,
                         };
                         self.pending_state_updates.insert(eth_block, state_update);
                     } else {
@@ -298,6 +307,10 @@ impl EthereumApi for EthereumClient {
             state_root: get_state_root(state_root._0),
             block_hash: get_block_hash(block_hash._0),
             block_number: get_block_number(block_number._0),
+            // The state is read from the contract, not from the transaction which
+            // updated it.
+            l1_block_number: None,
+            l1_transaction_hash: None,
         })
     }
 
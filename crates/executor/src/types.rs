@@ -137,6 +137,26 @@ impl TransactionTrace {
             _ => None,
         }
     }
+
+    /// The transfer of the fee to the sequencer, which L1 handlers do not pay.
+    pub fn fee_transfer_invocation(&self) -> Option<&FunctionInvocation> {
+        match self {
+            TransactionTrace::Declare(trace) => trace.fee_transfer_invocation.as_ref(),
+            TransactionTrace::DeployAccount(trace) => trace.fee_transfer_invocation.as_ref(),
+            TransactionTrace::Invoke(trace) => trace.fee_transfer_invocation.as_ref(),
+            TransactionTrace::L1Handler(_) => None,
+        }
+    }
+
+    /// The state changes made by the transaction.
+    pub fn state_diff(&self) -> &StateDiff {
+        match self {
+            TransactionTrace::Declare(trace) => &trace.state_diff,
+            TransactionTrace::DeployAccount(trace) => &trace.state_diff,
+            TransactionTrace::Invoke(trace) => &trace.state_diff,
+            TransactionTrace::L1Handler(trace) => &trace.state_diff,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
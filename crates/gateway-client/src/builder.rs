@@ -170,7 +170,8 @@ impl<'a> Request<'a, stage::Method> {
 }
 
 impl<'a> Request<'a, stage::Params> {
-    pub fn block<B: Into<BlockId>>(self, block: B) -> Self {
+    /// Fails for block ids which the feeder gateway cannot resolve.
+    pub fn block<B: Into<BlockId>>(self, block: B) -> Result<Self, SequencerError> {
         use std::borrow::Cow;
 
         let block: BlockId = block.into();
@@ -184,9 +185,10 @@ impl<'a> Request<'a, stage::Params> {
             // These have to use "blockNumber", "blockHash" does not accept tags.
             BlockId::Latest => ("blockNumber", Cow::from("latest"), BlockTag::Latest),
             BlockId::Pending => ("blockNumber", Cow::from("pending"), BlockTag::Pending),
+            BlockId::L1Accepted => return Err(SequencerError::UnsupportedBlockId("l1_accepted")),
         };
 
-        self.block_tag(tag).param(name, &value)
+        Ok(self.block_tag(tag).param(name, &value))
     }
 
     pub fn class_hash(self, class_hash: ClassHash) -> Self {
@@ -480,7 +482,7 @@ fn retry_condition(e: &SequencerError) -> bool {
 
             true
         }
-        SequencerError::StarknetError(_) => false,
+        SequencerError::StarknetError(_) | SequencerError::UnsupportedBlockId(_) => false,
         SequencerError::InvalidStarknetErrorVariant => {
             error!(reason=%e, "Request failed, retrying");
             true
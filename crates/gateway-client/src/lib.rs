@@ -325,7 +325,7 @@ impl GatewayApi for Client {
         let result: Dto = self
             .feeder_gateway_request()
             .get_state_update()
-            .block(BlockId::Pending)
+            .block(BlockId::Pending)?
             .param("includeBlock", "true")
             .retry(self.retry)
             .get()
@@ -348,7 +348,7 @@ impl GatewayApi for Client {
         let header: BlockHeader = self
             .feeder_gateway_request()
             .get_block()
-            .block(block)
+            .block(block)?
             .param("headerOnly", "true")
             .retry(self.retry)
             .get()
@@ -366,7 +366,7 @@ impl GatewayApi for Client {
         self.feeder_gateway_request()
             .get_class_by_hash()
             .class_hash(class_hash)
-            .block(BlockId::Pending)
+            .block(BlockId::Pending)?
             .retry(self.retry)
             .get_as_bytes()
             .await
@@ -381,7 +381,7 @@ impl GatewayApi for Client {
         self.feeder_gateway_request()
             .get_compiled_class_by_class_hash()
             .class_hash(class_hash)
-            .block(BlockId::Pending)
+            .block(BlockId::Pending)?
             .retry(self.retry)
             .get_as_bytes()
             .await
@@ -422,7 +422,7 @@ impl GatewayApi for Client {
         let result: Dto = self
             .feeder_gateway_request()
             .get_state_update()
-            .block(block)
+            .block(block)?
             .param("includeBlock", "true")
             .retry(self.retry)
             .get()
@@ -506,7 +506,7 @@ impl GatewayApi for Client {
     async fn block_traces(&self, block: BlockId) -> Result<BlockTrace, SequencerError> {
         self.feeder_gateway_request()
             .get_block_traces()
-            .block(block)
+            .block(block)?
             .retry(self.retry)
             .get()
             .await
@@ -529,7 +529,7 @@ impl GatewayApi for Client {
     async fn signature(&self, block: BlockId) -> Result<reply::BlockSignature, SequencerError> {
         self.feeder_gateway_request()
             .get_signature()
-            .block(block)
+            .block(block)?
             .retry(self.retry)
             .get()
             .await
@@ -1085,6 +1085,15 @@ mod tests {
                 SequencerError::StarknetError(e) => assert_eq!(e.code, KnownStarknetErrorCode::BlockNotFound.into())
             );
         }
+
+        #[test_log::test(tokio::test)]
+        async fn l1_accepted_is_unsupported() {
+            // No request is expected to reach the gateway.
+            let (_jh, url) = setup::<&str, &str, 0>([]);
+            let client = Client::with_base_url(url, GATEWAY_TIMEOUT).unwrap();
+            let error = client.block_header(BlockId::L1Accepted).await.unwrap_err();
+            assert_matches!(error, SequencerError::UnsupportedBlockId(_));
+        }
     }
 
     mod pending_block {
@@ -14,6 +14,10 @@ pub enum SequencerError {
     /// not informative enough or bloated
     #[error("error decoding response body: invalid error variant")]
     InvalidStarknetErrorVariant,
+    /// The request referenced a block by an id the feeder gateway cannot
+    /// resolve.
+    #[error("the feeder gateway does not support the {0} block id")]
+    UnsupportedBlockId(&'static str),
 }
 
 /// Used for deserializing specific Starknet sequencer error data.
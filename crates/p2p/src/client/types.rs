@@ -118,6 +118,7 @@ impl TryFromDto<p2p_proto::header::SignedBlockHeader> for SignedBlockHeader {
                 l1_da_mode: TryFromDto::try_from_dto(dto.l1_data_availability_mode)?,
                 state_diff_commitment: StateDiffCommitment(dto.state_diff_commitment.root.0),
                 state_diff_length: dto.state_diff_commitment.state_diff_length,
+                block_body_commitment: Default::default(),
                 class_commitment: ClassCommitment::ZERO,
                 storage_commitment: StorageCommitment::ZERO,
             },
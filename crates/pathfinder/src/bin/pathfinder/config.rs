@@ -11,7 +11,8 @@ use ipnet::IpNet;
 #[cfg(feature = "p2p")]
 use p2p::libp2p::Multiaddr;
 use pathfinder_common::consts::VERGEN_GIT_DESCRIBE;
-use pathfinder_common::AllowedOrigins;
+use pathfinder_common::{AllowedOrigins, ClassHash};
+use pathfinder_crypto::Felt;
 use pathfinder_executor::VersionedConstants;
 use pathfinder_storage::JournalMode;
 use reqwest::Url;
@@ -259,12 +260,38 @@ This should only be enabled for debugging purposes as it adds substantial proces
         long = "storage.event-bloom-filter-cache-size",
         long_help = "The number of blocks whose event bloom filters are cached in memory. This \
                      cache speeds up event related RPC queries at the cost of using extra memory. \
-                     Each cached filter takes 2 KiB of memory.",
+                     Each cached filter takes 2 KiB of memory. Filters are cached in groups of \
+                     32768 blocks, so every 32768 blocks of cache size take 64 MiB of memory. The \
+                     default caches two groups, or 128 MiB. A value below 32768 disables the \
+                     cache.",
         env = "PATHFINDER_STORAGE_BLOOM_FILTER_CACHE_SIZE",
-        default_value = "524288"
+        default_value = "65536"
     )]
     event_bloom_filter_cache_size: std::num::NonZeroUsize,
 
+    #[arg(
+        long = "storage.sharded-storage-paths",
+        long_help = "Paths of additional databases, each holding a separate range of blocks, \
+                     which are also queried by `starknet_getEvents`. The block ranges must not \
+                     overlap with each other or with the main database.",
+        value_name = "PATHS",
+        value_delimiter = ',',
+        env = "PATHFINDER_STORAGE_SHARDED_STORAGE_PATHS"
+    )]
+    sharded_storage_paths: Vec<PathBuf>,
+
+    #[arg(
+        long = "storage.recompress-class-definitions",
+        long_help = "Re-compress class definitions which older versions stored gzip compressed or \
+                     uncompressed using zstd on startup. Such definitions can be read as they \
+                     are, this only reduces their stored size. Every stored definition is \
+                     scanned, which can delay startup by a long time on large databases.",
+        default_value = "false",
+        env = "PATHFINDER_STORAGE_RECOMPRESS_CLASS_DEFINITIONS",
+        value_name = "BOOL"
+    )]
+    recompress_class_definitions: bool,
+
     #[arg(
         long = "rpc.get-events-max-blocks-to-scan",
         long_help = "The number of blocks to scan for events when querying for events. This limit \
@@ -284,6 +311,135 @@ This should only be enabled for debugging purposes as it adds substantial proces
     )]
     get_events_max_event_filters_to_load: std::num::NonZeroUsize,
 
+    #[arg(
+        long = "rpc.large-block-threshold",
+        long_help = "Requests for blocks containing more transactions than this are logged as a \
+                     warning.",
+        env = "PATHFINDER_RPC_LARGE_BLOCK_THRESHOLD",
+        default_value = "10000"
+    )]
+    large_block_threshold: usize,
+
+    #[arg(
+        long = "rpc.class-fallback-url",
+        long_help = "URL of an IPFS gateway or Arweave node used to retrieve class definitions \
+                     which are missing from the local database. Classes are requested as \
+                     `<URL>/<class hash>` and stored locally once retrieved. Requests time out \
+                     after 30 seconds and definitions larger than 50 MiB are rejected.",
+        value_name = "URL",
+        value_hint = clap::ValueHint::Url,
+        env = "PATHFINDER_RPC_CLASS_FALLBACK_URL"
+    )]
+    class_fallback_url: Option<Url>,
+
+    #[arg(
+        long = "rpc.wait-for-block-timeout",
+        value_name = "Seconds",
+        long_help = "How long `pathfinder_waitForBlock` holds a request open waiting for a block \
+                     which does not exist yet. Must be shorter than the 120 second RPC request \
+                     timeout.",
+        env = "PATHFINDER_RPC_WAIT_FOR_BLOCK_TIMEOUT",
+        default_value = "30",
+        value_parser = clap::value_parser!(u64).range(1..120)
+    )]
+    wait_for_block_timeout: u64,
+
+    #[arg(
+        long = "rpc.validate-class-hash",
+        long_help = "Recompute the hash of class definitions returned by `starknet_getClass` and \
+                     fail the request if it does not match the requested class hash. This detects \
+                     corrupted class definitions in the database at the cost of hashing every \
+                     returned class.",
+        default_value = "false",
+        env = "PATHFINDER_RPC_VALIDATE_CLASS_HASH",
+        value_name = "BOOL"
+    )]
+    validate_class_hash: bool,
+
+    #[arg(
+        long = "rpc.prefetch-next-event-page",
+        long_help = "After serving a page of `starknet_getEvents` results with a continuation \
+                     token, fetch the next page in the background so it can be served from memory \
+                     when requested.",
+        default_value = "false",
+        env = "PATHFINDER_RPC_PREFETCH_NEXT_EVENT_PAGE",
+        value_name = "BOOL"
+    )]
+    prefetch_next_event_page: bool,
+
+    #[arg(
+        long = "rpc.subscription-send-timeout",
+        value_name = "Seconds",
+        long_help = "How long a websocket subscription waits for a client which is not reading its \
+                     messages before the subscription is closed.",
+        env = "PATHFINDER_RPC_SUBSCRIPTION_SEND_TIMEOUT",
+        default_value = "30",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    subscription_send_timeout: u64,
+
+    #[arg(
+        long = "rpc.websocket-keepalive-interval",
+        value_name = "Seconds",
+        long_help = "Interval at which WebSocket clients are sent a ping, preventing idle \
+                     subscriptions from being dropped by NAT devices or load balancers. Clients \
+                     which do not respond with a pong before the next ping is due are \
+                     disconnected. Disabled if not set.",
+        env = "PATHFINDER_RPC_WEBSOCKET_KEEPALIVE_INTERVAL"
+    )]
+    websocket_keepalive_interval: Option<std::num::NonZeroU64>,
+
+    #[arg(
+        long = "rpc.subscription-catchup-concurrency",
+        long_help = "Number of block batches fetched concurrently while a WebSocket subscription \
+                     catches up from a historical block. Setting this to 1 effectively disables \
+                     concurrency.",
+        env = "PATHFINDER_RPC_SUBSCRIPTION_CATCHUP_CONCURRENCY",
+        default_value = "1"
+    )]
+    subscription_catchup_concurrency: NonZeroUsize,
+
+    #[arg(
+        long = "rpc.max-subscriptions-per-connection",
+        long_help = "Maximum number of active subscriptions of a single WebSocket connection. \
+                     Further subscription requests are rejected until one of the active \
+                     subscriptions ends.",
+        env = "PATHFINDER_RPC_MAX_SUBSCRIPTIONS_PER_CONNECTION",
+        default_value = "100"
+    )]
+    max_subscriptions_per_connection: NonZeroUsize,
+
+    #[arg(
+        long = "rpc.block-header-cache-size",
+        long_help = "Number of block headers cached in memory to reduce database reads of \
+                     frequently requested blocks.",
+        env = "PATHFINDER_RPC_BLOCK_HEADER_CACHE_SIZE",
+        default_value = "1024"
+    )]
+    block_header_cache_size: NonZeroUsize,
+
+    #[arg(
+        long = "rpc.websocket-max-message-size",
+        value_name = "Bytes",
+        long_help = "Maximum size of a message received over a WebSocket connection. Connections \
+                     of clients sending larger messages are closed.",
+        env = "PATHFINDER_RPC_WEBSOCKET_MAX_MESSAGE_SIZE",
+        default_value = "10485760"
+    )]
+    websocket_max_message_size_bytes: usize,
+
+    #[arg(
+        long = "rpc.approved-class-hashes",
+        long_help = "Class hashes approved by the node operator, reported as such by \
+                     `pathfinder_isClassApproved`. This is node local configuration and has no \
+                     effect on which classes can be declared.",
+        value_name = "CLASS HASH LIST",
+        value_delimiter = ',',
+        value_parser = parse_class_hash,
+        env = "PATHFINDER_RPC_APPROVED_CLASS_HASHES"
+    )]
+    approved_class_hashes: Vec<ClassHash>,
+
     #[arg(
         long = "storage.state-tries",
         long_help = "When set to `archive` all historical Merkle trie state is preserved. When set to an integer N, only the last N+1 states of the Merkle tries are kept in the database. \
@@ -345,6 +501,12 @@ pub enum StateTries {
     Archive,
 }
 
+fn parse_class_hash(s: &str) -> Result<ClassHash, String> {
+    Felt::from_hex_str(s)
+        .map(ClassHash)
+        .map_err(|e| format!("Invalid class hash: {e}"))
+}
+
 fn parse_state_tries(s: &str) -> Result<StateTries, String> {
     match s {
         "archive" => Ok(StateTries::Archive),
@@ -713,8 +875,22 @@ pub struct Config {
     pub gateway_api_key: Option<String>,
     pub gateway_timeout: Duration,
     pub event_bloom_filter_cache_size: NonZeroUsize,
+    pub sharded_storage_paths: Vec<PathBuf>,
+    pub recompress_class_definitions: bool,
     pub get_events_max_blocks_to_scan: NonZeroUsize,
     pub get_events_max_event_filters_to_load: NonZeroUsize,
+    pub large_block_threshold: usize,
+    pub class_fallback_url: Option<Url>,
+    pub wait_for_block_timeout: Duration,
+    pub validate_class_hash: bool,
+    pub prefetch_next_event_page: bool,
+    pub subscription_send_timeout: Duration,
+    pub websocket_keepalive_interval: Option<Duration>,
+    pub subscription_catchup_concurrency: NonZeroUsize,
+    pub max_subscriptions_per_connection: NonZeroUsize,
+    pub block_header_cache_size: NonZeroUsize,
+    pub websocket_max_message_size_bytes: usize,
+    pub approved_class_hashes: Vec<ClassHash>,
     pub state_tries: Option<StateTries>,
     pub custom_versioned_constants: Option<VersionedConstants>,
     pub feeder_gateway_fetch_concurrency: NonZeroUsize,
@@ -941,6 +1117,8 @@ fn parse_l1_checkpoint_or_exit(
             state_root: dto.state_root,
             block_number: dto.block_number,
             block_hash: dto.block_hash,
+            l1_block_number: None,
+            l1_transaction_hash: None,
         }
     })
 }
@@ -1003,8 +1181,24 @@ impl Config {
             is_rpc_enabled: cli.is_rpc_enabled,
             gateway_api_key: cli.gateway_api_key,
             event_bloom_filter_cache_size: cli.event_bloom_filter_cache_size,
+            sharded_storage_paths: cli.sharded_storage_paths,
+            recompress_class_definitions: cli.recompress_class_definitions,
             get_events_max_blocks_to_scan: cli.get_events_max_blocks_to_scan,
             get_events_max_event_filters_to_load: cli.get_events_max_event_filters_to_load,
+            large_block_threshold: cli.large_block_threshold,
+            class_fallback_url: cli.class_fallback_url,
+            wait_for_block_timeout: Duration::from_secs(cli.wait_for_block_timeout),
+            validate_class_hash: cli.validate_class_hash,
+            prefetch_next_event_page: cli.prefetch_next_event_page,
+            subscription_send_timeout: Duration::from_secs(cli.subscription_send_timeout),
+            websocket_keepalive_interval: cli
+                .websocket_keepalive_interval
+                .map(|secs| Duration::from_secs(secs.get())),
+            subscription_catchup_concurrency: cli.subscription_catchup_concurrency,
+            max_subscriptions_per_connection: cli.max_subscriptions_per_connection,
+            block_header_cache_size: cli.block_header_cache_size,
+            websocket_max_message_size_bytes: cli.websocket_max_message_size_bytes,
+            approved_class_hashes: cli.approved_class_hashes,
             gateway_timeout: Duration::from_secs(cli.gateway_timeout.get()),
             feeder_gateway_fetch_concurrency: cli.feeder_gateway_fetch_concurrency,
             state_tries: cli.state_tries,
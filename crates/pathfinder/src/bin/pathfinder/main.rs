@@ -164,14 +164,17 @@ Hint: This is usually caused by exceeding the file descriptor limit of your syst
         .get()
         .try_into()
         .expect("usize should cast to u32");
-    let rpc_storage = std::cmp::max(10, max_rpc_connections / 8);
-    let rpc_storage = NonZeroU32::new(rpc_storage).expect("A non-zero minimum is set");
-    let rpc_storage = storage_manager.create_read_only_pool(rpc_storage).context(
-        r"Creating database connection pool for RPC
+    let rpc_storage_pool_size = std::cmp::max(10, max_rpc_connections / 8);
+    let rpc_storage_pool_size =
+        NonZeroU32::new(rpc_storage_pool_size).expect("A non-zero minimum is set");
+    let rpc_storage = storage_manager
+        .create_read_only_pool(rpc_storage_pool_size)
+        .context(
+            r"Creating database connection pool for RPC
 
 Hint: This is usually caused by exceeding the file descriptor limit of your system.
       Try increasing the file limit to using `ulimit` or similar tooling.",
-    )?;
+        )?;
 
     let execution_storage_pool_size = config.execution_concurrency.unwrap_or_else(|| {
         std::num::NonZeroU32::new(available_parallelism.get() as u32)
@@ -195,6 +198,19 @@ Hint: This is usually caused by exceeding the file descriptor limit of your syst
       Try increasing the file limit to using `ulimit` or similar tooling.",
         )?;
 
+    let event_shards = config
+        .sharded_storage_paths
+        .iter()
+        .map(|path| {
+            pathfinder_storage::StorageBuilder::file(path.clone())
+                .journal_mode(config.sqlite_wal)
+                .bloom_filter_cache_size(config.event_bloom_filter_cache_size.get())
+                .migrate()
+                .and_then(|manager| manager.create_read_only_pool(rpc_storage_pool_size))
+                .with_context(|| format!("Opening sharded database at {}", path.display()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
     info!(location=?pathfinder_context.database, "Database migrated.");
     verify_database(
         &sync_storage,
@@ -212,6 +228,18 @@ Hint: This is usually caused by exceeding the file descriptor limit of your syst
         .prune_tries()
         .context("Pruning tries on startup")?;
 
+    if config.recompress_class_definitions {
+        let mut db = sync_storage
+            .connection()
+            .context("Creating database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+        let count = tx
+            .recompress_class_definitions()
+            .context("Re-compressing class definitions")?;
+        tx.commit().context("Committing database transaction")?;
+        info!(%count, "Class definitions re-compressed");
+    }
+
     let (tx_pending, rx_pending) = tokio::sync::watch::channel(Default::default());
 
     let rpc_config = pathfinder_rpc::context::RpcConfig {
@@ -219,6 +247,18 @@ Hint: This is usually caused by exceeding the file descriptor limit of your syst
         get_events_max_blocks_to_scan: config.get_events_max_blocks_to_scan,
         get_events_max_event_filters_to_load: config.get_events_max_event_filters_to_load,
         custom_versioned_constants: config.custom_versioned_constants.take(),
+        large_block_threshold: config.large_block_threshold,
+        class_fallback_url: config.class_fallback_url.take(),
+        wait_for_block_timeout: config.wait_for_block_timeout,
+        validate_class_hash: config.validate_class_hash,
+        prefetch_next_event_page: config.prefetch_next_event_page,
+        subscription_send_timeout: config.subscription_send_timeout,
+        websocket_keepalive_interval: config.websocket_keepalive_interval,
+        subscription_catchup_concurrency: config.subscription_catchup_concurrency,
+        max_subscriptions_per_connection: config.max_subscriptions_per_connection,
+        block_header_cache_size: config.block_header_cache_size,
+        websocket_max_message_size_bytes: config.websocket_max_message_size_bytes,
+        approved_class_hashes: config.approved_class_hashes,
     };
 
     let notifications = Notifications::default();
@@ -234,7 +274,8 @@ Hint: This is usually caused by exceeding the file descriptor limit of your syst
         notifications.clone(),
         ethereum.client.clone(),
         rpc_config,
-    );
+    )
+    .with_event_shards(event_shards);
 
     let context = if config.websocket.enabled {
         context.with_websockets(WebsocketContext::new(
@@ -544,6 +585,7 @@ fn start_sync(
             gateway_public_key,
             config.p2p.l1_checkpoint_override,
             verify_tree_hashes,
+            notifications,
         )
     }
 }
@@ -618,6 +660,7 @@ fn start_feeder_gateway_sync(
 }
 
 #[cfg(feature = "p2p")]
+#[allow(clippy::too_many_arguments)]
 fn start_p2p_sync(
     storage: Storage,
     pathfinder_context: PathfinderContext,
@@ -626,6 +669,7 @@ fn start_p2p_sync(
     gateway_public_key: pathfinder_common::PublicKey,
     l1_checkpoint_override: Option<pathfinder_ethereum::EthereumStateUpdate>,
     verify_tree_hashes: bool,
+    notifications: Notifications,
 ) -> tokio::task::JoinHandle<anyhow::Result<()>> {
     use pathfinder_block_hashes::BlockHashDb;
 
@@ -640,6 +684,7 @@ fn start_p2p_sync(
         l1_checkpoint_override,
         verify_tree_hashes,
         block_hash_db: Some(BlockHashDb::new(pathfinder_context.network)),
+        notifications,
     };
     tokio::spawn(sync.run())
 }
@@ -923,6 +923,7 @@ async fn l2_update(
             receipt_commitment,
             state_diff_commitment,
             state_diff_length: state_update.state_diff_length(),
+            block_body_commitment: Default::default(),
         };
 
         transaction
@@ -981,6 +982,8 @@ async fn l2_update(
             .commit()
             .context("Commit database transaction")?;
 
+        notifications.latest_block.send_replace(Some(header.number));
+
         if let Some(sender) = websocket_txs {
             if let Err(e) = sender.new_head.send_if_receiving(header.clone().into()) {
                 tracing::error!(error=?e, "Failed to send header over websocket broadcaster.");
@@ -1104,6 +1107,8 @@ async fn l2_reorg(
             .commit()
             .context("Commit database transaction")?;
 
+        notifications.latest_block.send_replace(reorg_tail.parent());
+
         notifications
             .reorgs
             .send(
@@ -27,6 +27,7 @@ use pathfinder_common::{
     StarknetVersion,
 };
 use pathfinder_ethereum::EthereumStateUpdate;
+use pathfinder_rpc::Notifications;
 use pathfinder_storage::Transaction;
 use primitive_types::H160;
 use starknet_gateway_client::{Client as GatewayClient, GatewayApi};
@@ -60,6 +61,10 @@ pub struct Sync<P, G> {
     pub l1_checkpoint_override: Option<EthereumStateUpdate>,
     pub verify_tree_hashes: bool,
     pub block_hash_db: Option<BlockHashDb>,
+    /// Stored blocks are announced through
+    /// [latest_block](Notifications::latest_block) like in feeder gateway
+    /// sync.
+    pub notifications: Notifications,
 }
 
 impl<P, G> Sync<P, G>
@@ -136,6 +141,7 @@ where
                 public_key: self.public_key,
                 verify_tree_hashes: self.verify_tree_hashes,
                 block_hash_db: self.block_hash_db.clone(),
+                notifications: self.notifications.clone(),
             }
             .run(checkpoint)
             .await;
@@ -169,6 +175,9 @@ where
                 continue;
             }
 
+            let (next, _) = continue_from;
+            self.notifications.latest_block.send_replace(next.parent());
+
             break Ok(continue_from);
         }
     }
@@ -197,6 +206,7 @@ where
                 public_key: self.public_key,
                 verify_tree_hashes: self.verify_tree_hashes,
                 block_hash_db: self.block_hash_db.clone(),
+                notifications: self.notifications.clone(),
             }
             .run(&mut next, &mut parent_hash, self.fgw_client.clone())
             .await;
@@ -453,6 +463,7 @@ mod tests {
         let expect_fully_synced_blocks = error_setup.expected_last_synced.is_full();
 
         let error_trigger = ErrorTrigger::new(error_setup.fatal_at);
+        let notifications = Notifications::default();
 
         let sync = Sync {
             storage: storage.clone(),
@@ -473,9 +484,12 @@ mod tests {
                 state_root: last_checkpoint_header.state_commitment,
                 block_number: last_checkpoint_header.number,
                 block_hash: last_checkpoint_header.hash,
+                l1_block_number: None,
+                l1_transaction_hash: None,
             }),
             verify_tree_hashes: true,
             block_hash_db: None,
+            notifications: notifications.clone(),
         };
 
         tokio::select! {
@@ -489,6 +503,18 @@ mod tests {
 
         assert!(error_trigger.all_errors_triggered());
 
+        if expect_fully_synced_blocks {
+            // Blocks stored by track sync are announced to RPC.
+            let mut latest_block = notifications.latest_block.subscribe();
+            tokio::time::timeout(
+                Duration::from_secs(1),
+                latest_block.wait_for(|latest| *latest == Some(expected_last_synced_block)),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+
         let mut db = storage.connection().unwrap();
         let db = db.transaction().unwrap();
         for mut expected in blocks
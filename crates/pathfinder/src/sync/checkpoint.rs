@@ -33,6 +33,7 @@ use pathfinder_common::{
     TransactionIndex,
 };
 use pathfinder_ethereum::EthereumStateUpdate;
+use pathfinder_rpc::{Notifications, Reorg};
 use pathfinder_storage::Storage;
 use primitive_types::H160;
 use serde_json::de;
@@ -59,6 +60,9 @@ pub struct Sync<P, G> {
     pub public_key: PublicKey,
     pub verify_tree_hashes: bool,
     pub block_hash_db: Option<pathfinder_block_hashes::BlockHashDb>,
+    /// Blocks which are rolled back to the L1 anchor are announced as a
+    /// [reorg](Notifications::reorgs).
+    pub notifications: Notifications,
 }
 
 impl<P, G> Sync<P, G>
@@ -114,7 +118,7 @@ where
 
         // Ensure our local state is consistent with the L1 checkpoint.
         CheckpointAnalysis::analyse(&local_state, &checkpoint)
-            .handle(self.storage.clone())
+            .handle(self.storage.clone(), self.notifications.clone())
             .await
             .context("Analysing local storage against L1 checkpoint")?;
 
@@ -532,7 +536,7 @@ impl CheckpointAnalysis {
     /// insecure local data intact. Always rolling back to the L1 anchor
     /// would result in a poor user experience if restarting frequently as each
     /// restart would purge new data.
-    async fn handle(self, storage: Storage) -> anyhow::Result<()> {
+    async fn handle(self, storage: Storage, notifications: Notifications) -> anyhow::Result<()> {
         match self {
             CheckpointAnalysis::HashMismatchWithAnchor {
                 block,
@@ -564,7 +568,7 @@ impl CheckpointAnalysis {
                     "Rolling back local chain to latest anchor point. Local data is potentially invalid as the Ethereum checkpoint is newer the local chain."
                 );
 
-                rollback_to_anchor(storage, local, anchor)
+                rollback_to_anchor(storage, notifications, local, anchor)
                     .await
                     .context("Rolling back chain state to L1 anchor")?;
             }
@@ -579,7 +583,7 @@ impl CheckpointAnalysis {
                     "Rolling back local chain to latest anchor point. Local data is invalid as it did not match the Ethereum checkpoint's hash."
                 );
 
-                rollback_to_anchor(storage, block, anchor)
+                rollback_to_anchor(storage, notifications, block, anchor)
                     .await
                     .context("Rolling back chain state to L1 anchor")?;
             }
@@ -636,8 +640,12 @@ impl LocalState {
 
 /// Rolls back local chain-state until the given anchor point, making it the tip
 /// of the local chain. If this is ['None'] then all data will be rolled back.
+///
+/// The removed blocks are announced as a reorg so that RPC caches and
+/// subscriptions drop them.
 async fn rollback_to_anchor(
     storage: Storage,
+    notifications: Notifications,
     local: BlockNumber,
     anchor: Option<BlockNumber>,
 ) -> anyhow::Result<()> {
@@ -652,6 +660,13 @@ async fn rollback_to_anchor(
             .context("Creating database connection")?;
         let transaction = db.transaction().context("Create database transaction")?;
 
+        let first_block_hash = transaction
+            .block_hash(last_block_to_remove.into())
+            .context("Querying first block hash")?;
+        let last_block_hash = transaction
+            .block_hash(local.into())
+            .context("Querying last block hash")?;
+
         // TODO: roll back Merkle tree state once we're updating that
 
         while head >= last_block_to_remove {
@@ -671,6 +686,32 @@ async fn rollback_to_anchor(
             .reconstruct_running_event_filter()
             .context("Reconstructing running event filter after purge")?;
 
+        transaction
+            .commit()
+            .context("Committing database transaction")?;
+
+        if let (Some(first_block_hash), Some(last_block_hash)) = (first_block_hash, last_block_hash)
+        {
+            notifications
+                .latest_block
+                .send_replace(last_block_to_remove.parent());
+
+            notifications
+                .reorgs
+                .send(
+                    Reorg {
+                        first_block_number: last_block_to_remove,
+                        first_block_hash,
+                        last_block_number: local,
+                        last_block_hash,
+                    }
+                    .into(),
+                )
+                // Ignore errors in case nobody is listening. New listeners may subscribe in the
+                // future.
+                .ok();
+        }
+
         Ok(())
     })
     .await
@@ -776,6 +817,7 @@ mod tests {
                         event_count: dto.event_count,
                         state_diff_commitment: dto.state_diff_commitment,
                         state_diff_length: dto.state_diff_length,
+                        block_body_commitment: Default::default(),
                         receipt_commitment: dto.receipt_commitment,
                         starknet_version,
                         eth_l1_gas_price: dto.eth_l1_gas_price,
@@ -1841,4 +1883,56 @@ mod tests {
             );
         }
     }
+
+    mod rollback_to_anchor {
+        use pathfinder_common::{BlockHash, BlockHeader};
+        use pathfinder_crypto::Felt;
+        use pathfinder_storage::StorageBuilder;
+
+        use super::*;
+
+        #[tokio::test]
+        async fn removed_blocks_are_announced_as_reorg() {
+            let storage = StorageBuilder::in_memory().unwrap();
+            let mut db = storage.connection().unwrap();
+            let db = db.transaction().unwrap();
+            for i in 0..5 {
+                db.insert_block_header(&BlockHeader {
+                    hash: BlockHash(Felt::from_u64(i)),
+                    number: BlockNumber::new_or_panic(i),
+                    ..Default::default()
+                })
+                .unwrap();
+            }
+            db.commit().unwrap();
+            let notifications = Notifications::default();
+            let mut reorgs = notifications.reorgs.subscribe();
+
+            rollback_to_anchor(
+                storage.clone(),
+                notifications.clone(),
+                BlockNumber::new_or_panic(4),
+                Some(BlockNumber::new_or_panic(1)),
+            )
+            .await
+            .unwrap();
+
+            let reorg = reorgs.try_recv().unwrap();
+            assert_eq!(reorg.first_block_number, BlockNumber::new_or_panic(2));
+            assert_eq!(reorg.first_block_hash, BlockHash(Felt::from_u64(2)));
+            assert_eq!(reorg.last_block_number, BlockNumber::new_or_panic(4));
+            assert_eq!(reorg.last_block_hash, BlockHash(Felt::from_u64(4)));
+            assert_eq!(
+                *notifications.latest_block.borrow(),
+                Some(BlockNumber::new_or_panic(1))
+            );
+
+            let mut db = storage.connection().unwrap();
+            let db = db.transaction().unwrap();
+            assert_eq!(
+                db.block_id(pathfinder_storage::BlockId::Latest).unwrap(),
+                Some((BlockNumber::new_or_panic(1), BlockHash(Felt::from_u64(1))))
+            );
+        }
+    }
 }
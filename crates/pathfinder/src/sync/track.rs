@@ -41,6 +41,7 @@ use pathfinder_common::{
     TransactionHash,
 };
 use pathfinder_merkle_tree::starknet_state::update_starknet_state;
+use pathfinder_rpc::Notifications;
 use pathfinder_storage::Storage;
 use starknet_gateway_client::GatewayApi;
 use tokio_stream::wrappers::ReceiverStream;
@@ -60,6 +61,7 @@ pub struct Sync<L, P> {
     pub public_key: PublicKey,
     pub block_hash_db: Option<pathfinder_block_hashes::BlockHashDb>,
     pub verify_tree_hashes: bool,
+    pub notifications: Notifications,
 }
 
 impl<L, P> Sync<L, P> {
@@ -169,6 +171,7 @@ impl<L, P> Sync<L, P> {
                 storage_connection,
                 self.storage.clone(),
                 self.verify_tree_hashes,
+                self.notifications,
             ),
             10,
         )
@@ -739,6 +742,7 @@ struct StoreBlock {
     storage: Storage,
     // Verify trie node hashes when loading tries from DB.
     verify_tree_hashes: bool,
+    notifications: Notifications,
 }
 
 impl StoreBlock {
@@ -746,11 +750,13 @@ impl StoreBlock {
         connection: pathfinder_storage::Connection,
         storage: pathfinder_storage::Storage,
         verify_tree_hashes: bool,
+        notifications: Notifications,
     ) -> Self {
         Self {
             connection,
             storage,
             verify_tree_hashes,
+            notifications,
         }
     }
 }
@@ -801,6 +807,7 @@ impl ProcessStage for StoreBlock {
             receipt_commitment: header.receipt_commitment,
             state_diff_commitment: header.state_diff_commitment,
             state_diff_length: header.state_diff_length,
+            block_body_commitment: Default::default(),
         };
 
         db.insert_block_header(&header)
@@ -875,14 +882,14 @@ impl ProcessStage for StoreBlock {
             },
         )?;
 
-        let result = db
-            .commit()
-            .context("Committing transaction")
-            .map_err(Into::into)
-            .map(|_| (block_number, header.hash));
+        db.commit().context("Committing transaction")?;
+
+        self.notifications
+            .latest_block
+            .send_replace(Some(block_number));
 
         tracing::debug!(number=%block_number, "Block stored");
 
-        result
+        Ok((block_number, header.hash))
     }
 }
@@ -0,0 +1,122 @@
+//! Measures how quickly `starknet_subscribeNewHeads` streams historical blocks
+//! to a subscriber starting at genesis, for different catch-up concurrencies.
+
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use futures::{SinkExt, StreamExt};
+use pathfinder_common::{BlockHash, BlockHeader, BlockNumber, ChainId};
+use pathfinder_crypto::Felt;
+use pathfinder_ethereum::EthereumClient;
+use pathfinder_rpc::context::{RpcConfig, RpcContext};
+use pathfinder_rpc::{Notifications, RpcServer, RpcVersion, SyncState};
+use pathfinder_storage::{Storage, StorageBuilder, TriePruneMode};
+use primitive_types::H160;
+use starknet_gateway_client::Client;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Number of blocks the subscriber has to catch up with.
+const BLOCKS: u64 = 10_000;
+
+fn storage(pool_size: u32) -> Storage {
+    let storage = StorageBuilder::in_memory_with_trie_pruning_and_pool_size(
+        TriePruneMode::Archive,
+        NonZeroU32::new(pool_size).unwrap(),
+    )
+    .unwrap();
+    let mut connection = storage.connection().unwrap();
+    let tx = connection.transaction().unwrap();
+    for i in 0..BLOCKS {
+        let header = BlockHeader {
+            hash: BlockHash(Felt::from_u64(i)),
+            number: BlockNumber::new_or_panic(i),
+            ..Default::default()
+        };
+        tx.insert_block_header(&header).unwrap();
+    }
+    tx.commit().unwrap();
+    storage
+}
+
+fn context(storage: Storage, concurrency: usize) -> RpcContext {
+    let (_, pending_data) = tokio::sync::watch::channel(Default::default());
+    let config = RpcConfig {
+        batch_concurrency_limit: NonZeroUsize::new(1).unwrap(),
+        get_events_max_blocks_to_scan: NonZeroUsize::new(1).unwrap(),
+        get_events_max_event_filters_to_load: NonZeroUsize::new(1).unwrap(),
+        custom_versioned_constants: None,
+        large_block_threshold: 10_000,
+        class_fallback_url: None,
+        wait_for_block_timeout: Duration::from_secs(30),
+        validate_class_hash: false,
+        prefetch_next_event_page: false,
+        subscription_send_timeout: Duration::from_secs(30),
+        websocket_keepalive_interval: None,
+        subscription_catchup_concurrency: NonZeroUsize::new(concurrency).unwrap(),
+        max_subscriptions_per_connection: NonZeroUsize::new(100).unwrap(),
+        block_header_cache_size: NonZeroUsize::new(1024).unwrap(),
+        websocket_max_message_size_bytes: 10 * 1024 * 1024,
+        approved_class_hashes: Vec::new(),
+    };
+    RpcContext::new(
+        storage.clone(),
+        storage,
+        Arc::new(SyncState::default()),
+        ChainId::MAINNET,
+        H160::from(pathfinder_ethereum::core_addr::MAINNET),
+        Client::mainnet(Duration::from_secs(10)),
+        pending_data,
+        Notifications::default(),
+        EthereumClient::new("wss://eth-sepolia.g.alchemy.com/v2/just-for-benches").unwrap(),
+        config,
+    )
+}
+
+/// Subscribes from genesis and waits until all historical blocks have been
+/// received.
+async fn catch_up(url: &str) {
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "starknet_subscribeNewHeads",
+        "params": {"block_id": {"block_number": 0}}
+    });
+    ws.send(Message::Text(request.to_string())).await.unwrap();
+
+    // The subscription response followed by one notification per block.
+    for _ in 0..=BLOCKS {
+        ws.next().await.unwrap().unwrap();
+    }
+    ws.close(None).await.unwrap();
+}
+
+fn subscription_catch_up(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let mut group = c.benchmark_group("subscription_catch_up");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(BLOCKS));
+
+    for concurrency in [1, 4, 8] {
+        let storage = storage(concurrency as u32);
+        let context = context(storage, concurrency);
+        let (_, addr) = runtime
+            .block_on(
+                RpcServer::new("127.0.0.1:0".parse().unwrap(), context, RpcVersion::V08).spawn(),
+            )
+            .unwrap();
+        let url = format!("ws://{addr}/rpc/v0_8");
+
+        group.bench_function(format!("concurrency_{concurrency}"), |b| {
+            b.iter(|| runtime.block_on(catch_up(&url)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, subscription_catch_up);
+criterion_main!(benches);
@@ -1,14 +1,16 @@
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
-use pathfinder_common::ChainId;
+use pathfinder_common::{ChainId, ClassHash};
 use pathfinder_ethereum::EthereumClient;
 use pathfinder_executor::{TraceCache, VersionedConstants};
 use pathfinder_storage::Storage;
 use primitive_types::H160;
 
+use crate::header_cache::BlockHeaderCache;
 pub use crate::jsonrpc::websocket::WebsocketContext;
 use crate::jsonrpc::Notifications;
+use crate::method::get_events::EventPageCache;
 use crate::pending::{PendingData, PendingWatcher};
 use crate::SyncState;
 
@@ -21,13 +23,54 @@ pub struct RpcConfig {
     pub get_events_max_blocks_to_scan: NonZeroUsize,
     pub get_events_max_event_filters_to_load: NonZeroUsize,
     pub custom_versioned_constants: Option<VersionedConstants>,
+    /// Blocks with more transactions than this are logged as a warning when
+    /// requested.
+    pub large_block_threshold: usize,
+    /// IPFS gateway or Arweave node used to retrieve class definitions missing
+    /// from the local database.
+    pub class_fallback_url: Option<reqwest::Url>,
+    /// How long `pathfinder_waitForBlock` waits for a block to be produced.
+    pub wait_for_block_timeout: std::time::Duration,
+    /// Recompute the hash of class definitions served by `starknet_getClass`
+    /// to detect storage corruption.
+    pub validate_class_hash: bool,
+    /// Pre-fetch the next page of `starknet_getEvents` results in the
+    /// background after serving a page with a continuation token.
+    pub prefetch_next_event_page: bool,
+    /// How long a subscription waits for the client to make room for a new
+    /// message before the subscription is closed.
+    pub subscription_send_timeout: std::time::Duration,
+    /// Interval at which WebSocket clients are pinged. Connections of clients
+    /// which do not respond with a pong before the next ping is due are
+    /// closed. Disabled if `None`.
+    pub websocket_keepalive_interval: Option<std::time::Duration>,
+    /// Number of block batches fetched concurrently while a subscription
+    /// catches up with historical blocks.
+    pub subscription_catchup_concurrency: NonZeroUsize,
+    /// Maximum number of active subscriptions of a single WebSocket
+    /// connection.
+    pub max_subscriptions_per_connection: NonZeroUsize,
+    /// Number of block headers kept in [RpcContext::header_cache].
+    pub block_header_cache_size: NonZeroUsize,
+    /// Maximum size of a message received over a WebSocket connection.
+    /// Connections sending larger messages are closed.
+    pub websocket_max_message_size_bytes: usize,
+    /// Class hashes approved by the node operator, as reported by
+    /// `pathfinder_isClassApproved`.
+    pub approved_class_hashes: Vec<ClassHash>,
 }
 
 #[derive(Clone)]
 pub struct RpcContext {
     pub cache: TraceCache,
+    pub event_page_cache: EventPageCache,
+    /// Block headers recently read from [storage](Self::storage).
+    pub header_cache: BlockHeaderCache,
     pub storage: Storage,
     pub execution_storage: Storage,
+    /// Additional databases holding separate block ranges, queried by
+    /// `starknet_getEvents` in addition to [storage](Self::storage).
+    pub event_shards: Vec<Storage>,
     pub pending_data: PendingWatcher,
     pub sync_status: Arc<SyncState>,
     pub chain_id: ChainId,
@@ -54,10 +97,14 @@ impl RpcContext {
         config: RpcConfig,
     ) -> Self {
         let pending_data = PendingWatcher::new(pending_data);
+        let header_cache = BlockHeaderCache::new(config.block_header_cache_size, &notifications);
         Self {
             cache: Default::default(),
+            event_page_cache: EventPageCache::new(&notifications),
+            header_cache,
             storage,
             execution_storage,
+            event_shards: Vec::new(),
             sync_status,
             chain_id,
             core_contract_address,
@@ -122,6 +169,18 @@ impl RpcContext {
             get_events_max_blocks_to_scan: NonZeroUsize::new(1000).unwrap(),
             get_events_max_event_filters_to_load: NonZeroUsize::new(1000).unwrap(),
             custom_versioned_constants: None,
+            large_block_threshold: 10_000,
+            class_fallback_url: None,
+            wait_for_block_timeout: std::time::Duration::from_secs(30),
+            validate_class_hash: false,
+            prefetch_next_event_page: false,
+            subscription_send_timeout: std::time::Duration::from_secs(30),
+            websocket_keepalive_interval: None,
+            subscription_catchup_concurrency: NonZeroUsize::new(1).unwrap(),
+            max_subscriptions_per_connection: NonZeroUsize::new(100).unwrap(),
+            block_header_cache_size: NonZeroUsize::new(1024).unwrap(),
+            websocket_max_message_size_bytes: 10 * 1024 * 1024,
+            approved_class_hashes: Vec::new(),
         };
 
         let ethereum =
@@ -142,7 +201,11 @@ impl RpcContext {
     }
 
     pub fn with_storage(self, storage: Storage) -> Self {
+        // Headers cached so far were read from the replaced storage.
+        let header_cache =
+            BlockHeaderCache::new(self.config.block_header_cache_size, &self.notifications);
         Self {
+            header_cache,
             storage: storage.clone(),
             execution_storage: storage,
             ..self
@@ -170,6 +233,13 @@ impl RpcContext {
         context.with_pending_data(rx)
     }
 
+    pub fn with_event_shards(self, event_shards: Vec<Storage>) -> Self {
+        Self {
+            event_shards,
+            ..self
+        }
+    }
+
     pub fn with_websockets(self, websockets: WebsocketContext) -> Self {
         Self {
             websocket: Some(websockets),
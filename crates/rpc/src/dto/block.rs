@@ -13,20 +13,30 @@ pub struct PendingBlockHeader<'a>(pub &'a starknet_gateway_types::reply::Pending
 impl crate::dto::DeserializeForVersion for pathfinder_common::BlockId {
     fn deserialize(value: super::Value) -> Result<Self, serde_json::Error> {
         if value.is_string() {
+            let version = value.version;
             let value: String = value.deserialize_serde()?;
             match value.as_str() {
                 "latest" => Ok(Self::Latest),
                 "pending" => Ok(Self::Pending),
+                // `finalized` is the Ethereum name for the same block.
+                "safe" | "finalized" => Ok(Self::L1Accepted),
+                // Not part of the Starknet specification, so only accepted by the pathfinder
+                // API.
+                "genesis" if version == RpcVersion::PathfinderV01 => {
+                    Ok(Self::Number(pathfinder_common::BlockNumber::GENESIS))
+                }
                 _ => Err(serde_json::Error::custom("Invalid block id")),
             }
         } else {
             value.deserialize_map(|value| {
                 if value.contains_key("block_number") {
+                    let number: u64 = value.deserialize_serde("block_number")?;
+                    // Storage is limited to `i64::MAX`, so larger block numbers cannot exist.
+                    // Saturating them results in BLOCK_NOT_FOUND instead of a conversion
+                    // overflow.
                     Ok(Self::Number(
-                        pathfinder_common::BlockNumber::new(
-                            value.deserialize_serde("block_number")?,
-                        )
-                        .ok_or_else(|| serde_json::Error::custom("Invalid block number"))?,
+                        pathfinder_common::BlockNumber::new(number)
+                            .unwrap_or(pathfinder_common::BlockNumber::MAX),
                     ))
                 } else if value.contains_key("block_hash") {
                     Ok(Self::Hash(pathfinder_common::BlockHash(
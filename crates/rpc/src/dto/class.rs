@@ -68,7 +68,9 @@ impl SerializeForVersion for DeprecatedContractClass<'_> {
             "entry_points_by_type",
             &EntryPointsByType(&self.0.entry_points_by_type),
         )?;
-        serializer.serialize_optional("abi", self.0.abi.as_ref().map(|x| ContractAbi(x)))?;
+        // Classes declared before the ABI was required may not have one.
+        let abi = self.0.abi.as_deref().unwrap_or_default();
+        serializer.serialize_field("abi", &ContractAbi(abi))?;
 
         serializer.end()
     }
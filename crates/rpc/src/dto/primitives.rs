@@ -4,7 +4,7 @@ use serde::de::Error;
 
 use super::serialize::SerializeForVersion;
 use super::{DeserializeForVersion, Value};
-use crate::dto::serialize::{self, Serializer};
+use crate::dto::serialize::{self, NumberFormat, Serializer};
 
 pub struct SyncStatus<'a>(pub &'a crate::types::syncing::Status);
 
@@ -176,10 +176,24 @@ impl SerializeForVersion for SyncStatus<'_> {
     }
 }
 
+/// Serializes a big-endian unsigned integer of at most 32 bytes in the
+/// [NumberFormat] of the serializer.
+fn serialize_be_bytes(
+    serializer: Serializer,
+    bytes: &[u8],
+) -> Result<serialize::Ok, serialize::Error> {
+    match serializer.format {
+        NumberFormat::Hex => serializer.serialize_str(&hex_str::bytes_to_hex_str_stripped(bytes)),
+        NumberFormat::Decimal => {
+            let value = primitive_types::U256::from_big_endian(bytes);
+            serializer.serialize_str(&value.to_string())
+        }
+    }
+}
+
 impl SerializeForVersion for Felt<'_> {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
-        let hex_str = hex_str::bytes_to_hex_str_stripped(self.0.as_be_bytes());
-        serializer.serialize_str(&hex_str)
+        serialize_be_bytes(serializer, self.0.as_be_bytes())
     }
 }
 
@@ -200,8 +214,7 @@ impl SerializeForVersion for BlockHash<'_> {
 
 impl SerializeForVersion for ChainId<'_> {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
-        let hex_str = hex_str::bytes_to_hex_str_stripped(self.0 .0.as_be_bytes());
-        serializer.serialize_str(&hex_str)
+        serialize_be_bytes(serializer, self.0 .0.as_be_bytes())
     }
 }
 
@@ -213,13 +226,13 @@ impl SerializeForVersion for BlockNumber {
 
 impl SerializeForVersion for U64Hex {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
-        serializer.serialize_str(&hex_str::bytes_to_hex_str_stripped(&self.0.to_be_bytes()))
+        serialize_be_bytes(serializer, &self.0.to_be_bytes())
     }
 }
 
 impl SerializeForVersion for U128Hex {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
-        serializer.serialize_str(&hex_str::bytes_to_hex_str_stripped(&self.0.to_be_bytes()))
+        serialize_be_bytes(serializer, &self.0.to_be_bytes())
     }
 }
 
@@ -235,15 +248,13 @@ impl DeserializeForVersion for U128Hex {
 
 impl SerializeForVersion for H256Hex {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
-        serializer.serialize_str(&hex_str::bytes_to_hex_str_stripped(self.0.as_bytes()))
+        serialize_be_bytes(serializer, self.0.as_bytes())
     }
 }
 
 impl SerializeForVersion for U256Hex {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
-        serializer.serialize_str(&hex_str::bytes_to_hex_str_stripped(&<[u8; 32]>::from(
-            self.0,
-        )))
+        serialize_be_bytes(serializer, &<[u8; 32]>::from(self.0))
     }
 }
 
@@ -265,8 +276,14 @@ impl SerializeForVersion for Address<'_> {
 
 impl SerializeForVersion for EthAddress<'_> {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
-        let hex_str = hex_str::bytes_to_hex_str_full(self.0 .0.as_bytes());
-        serializer.serialize_str(&hex_str)
+        match serializer.format {
+            // Addresses keep their leading zeros in hex.
+            NumberFormat::Hex => {
+                let hex_str = hex_str::bytes_to_hex_str_full(self.0 .0.as_bytes());
+                serializer.serialize_str(&hex_str)
+            }
+            NumberFormat::Decimal => serialize_be_bytes(serializer, self.0 .0.as_bytes()),
+        }
     }
 }
 
@@ -289,6 +306,17 @@ mod tests {
         assert_eq!(encoded, expected);
     }
 
+    #[test]
+    fn felt_decimal() {
+        let uut = Felt(&felt!("0x1234"));
+        let expected = json!("4660");
+        let encoded = uut
+            .serialize(Serializer::default().with_format(NumberFormat::Decimal))
+            .unwrap();
+
+        assert_eq!(encoded, expected);
+    }
+
     #[test]
     fn block_hash() {
         let hash = block_hash!("0x1234");
@@ -386,6 +414,43 @@ mod tests {
         assert_eq!(encoded, expected);
     }
 
+    #[test]
+    fn decimal_format() {
+        let chain_id = pathfinder_common::ChainId(felt!("0x1234"));
+        let eth_address = pathfinder_common::EthereumAddress(H160::from_low_u64_be(0x1234));
+        let encoded = [
+            U64Hex(0x1234).serialize(decimal()).unwrap(),
+            U128Hex(0x1234).serialize(decimal()).unwrap(),
+            H256Hex(primitive_types::H256::from_low_u64_be(0x1234))
+                .serialize(decimal())
+                .unwrap(),
+            U256Hex(primitive_types::U256::from(0x1234u64))
+                .serialize(decimal())
+                .unwrap(),
+            ChainId(&chain_id).serialize(decimal()).unwrap(),
+            EthAddress(&eth_address).serialize(decimal()).unwrap(),
+        ];
+
+        for encoded in encoded {
+            assert_eq!(encoded, json!("4660"));
+        }
+    }
+
+    fn decimal() -> Serializer {
+        Serializer::default().with_format(NumberFormat::Decimal)
+    }
+
+    #[test]
+    fn decimal_format_of_large_values() {
+        let encoded = U256Hex(primitive_types::U256::MAX)
+            .serialize(decimal())
+            .unwrap();
+        assert_eq!(encoded, json!(primitive_types::U256::MAX.to_string()));
+
+        let encoded = U128Hex(u128::MAX).serialize(decimal()).unwrap();
+        assert_eq!(encoded, json!(u128::MAX.to_string()));
+    }
+
     mod to_hex {
         use super::super::hex_str::*;
 
@@ -1,10 +1,17 @@
 use pathfinder_common::event::Event;
 use pathfinder_common::receipt::Receipt;
 use pathfinder_common::transaction::{Transaction, TransactionKind, TransactionVariant};
-use pathfinder_common::{BlockHash, BlockNumber, TransactionHash, TransactionVersion};
+use pathfinder_common::{
+    BlockHash,
+    BlockNumber,
+    GasPrice,
+    L1DataAvailabilityMode,
+    TransactionHash,
+    TransactionVersion,
+};
 use serde::ser::Error;
 
-use super::{serialize, H256Hex};
+use super::{serialize, H256Hex, U128Hex};
 use crate::dto::serialize::{SerializeForVersion, Serializer};
 use crate::{dto, RpcVersion};
 
@@ -52,6 +59,9 @@ pub struct TxnReceiptWithBlockInfo<'a> {
     pub transaction: &'a Transaction,
     pub events: &'a [Event],
     pub finality: TxnFinalityStatus,
+    /// Non-standard, only set if requested for blocks using blob data
+    /// availability, see [blob_data_gas_price].
+    pub data_gas_price: Option<GasPrice>,
 }
 
 pub struct TxnReceipt<'a> {
@@ -59,6 +69,9 @@ pub struct TxnReceipt<'a> {
     pub transaction: &'a Transaction,
     pub events: &'a [Event],
     pub finality: TxnFinalityStatus,
+    /// Non-standard, only set if requested for blocks using blob data
+    /// availability, see [blob_data_gas_price].
+    pub data_gas_price: Option<GasPrice>,
 }
 
 pub struct InvokeTxnReceipt<'a>(pub &'a TxnReceipt<'a>);
@@ -78,8 +91,34 @@ pub struct FeePayment<'a> {
 }
 pub struct MsgToL1<'a>(pub &'a pathfinder_common::receipt::L2ToL1Message);
 pub struct ExecutionResources<'a>(pub &'a pathfinder_common::receipt::ExecutionResources);
+/// [ExecutionResources] including the non-standard data gas consumed and its
+/// price, if requested for blocks using blob data availability.
+struct ReceiptExecutionResources<'a> {
+    resources: &'a pathfinder_common::receipt::ExecutionResources,
+    data_gas_price: Option<GasPrice>,
+}
 pub struct ComputationResources<'a>(pub &'a pathfinder_common::receipt::ExecutionResources);
 
+/// Returns the price of the L1 data gas consumed by a transaction, in the unit
+/// of its fee, if its block published the state diff as blobs. Blocks using
+/// calldata do not pay for data gas separately, so `None` is returned.
+pub fn blob_data_gas_price(
+    l1_da_mode: L1DataAvailabilityMode,
+    price_in_wei: GasPrice,
+    price_in_fri: GasPrice,
+    transaction_version: TransactionVersion,
+) -> Option<GasPrice> {
+    match l1_da_mode {
+        L1DataAvailabilityMode::Calldata => None,
+        L1DataAvailabilityMode::Blob => match transaction_version {
+            TransactionVersion::ZERO | TransactionVersion::ONE | TransactionVersion::TWO => {
+                Some(price_in_wei)
+            }
+            _ => Some(price_in_fri),
+        },
+    }
+}
+
 impl SerializeForVersion for TxnStatus {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
         match self {
@@ -146,6 +185,7 @@ impl SerializeForVersion for TxnReceiptWithBlockInfo<'_> {
             transaction,
             events,
             finality,
+            data_gas_price,
         } = self;
 
         let mut serializer = serializer.serialize_struct()?;
@@ -155,6 +195,7 @@ impl SerializeForVersion for TxnReceiptWithBlockInfo<'_> {
             transaction,
             events,
             finality: *finality,
+            data_gas_price: *data_gas_price,
         })?;
 
         serializer.serialize_optional("block_hash", block_hash.map(dto::BlockHash))?;
@@ -322,7 +363,10 @@ impl SerializeForVersion for CommonReceiptProperties<'_> {
         )?;
         serializer.serialize_field(
             "execution_resources",
-            &ExecutionResources(&self.0.receipt.execution_resources),
+            &ReceiptExecutionResources {
+                resources: &self.0.receipt.execution_resources,
+                data_gas_price: self.0.data_gas_price,
+            },
         )?;
         serializer.flatten(&TxnExecutionStatusWithRevertReason(
             &self.0.receipt.execution_status,
@@ -388,6 +432,23 @@ impl SerializeForVersion for ExecutionResources<'_> {
     }
 }
 
+impl SerializeForVersion for ReceiptExecutionResources<'_> {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.flatten(&ExecutionResources(self.resources))?;
+        if let Some(data_gas_price) = self.data_gas_price {
+            serializer.serialize_field(
+                "data_gas_consumed",
+                &self.resources.data_availability.l1_data_gas,
+            )?;
+            serializer.serialize_field("data_gas_price", &U128Hex(data_gas_price.0))?;
+        }
+
+        serializer.end()
+    }
+}
+
 impl SerializeForVersion for PriceUnit<'_> {
     fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
         match self.0 {
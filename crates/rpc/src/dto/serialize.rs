@@ -4,10 +4,23 @@ use crate::RpcVersion;
 #[cfg_attr(test, derive(Default))]
 pub struct Serializer {
     pub version: RpcVersion,
+    pub format: NumberFormat,
+}
+
+/// The representation of [Felt](crate::dto::Felt) values.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberFormat {
+    /// Hex strings as required by the specification, e.g. `"0x2a"`.
+    #[default]
+    Hex,
+    /// Decimal strings, e.g. `"42"`.
+    Decimal,
 }
 
 pub struct SerializeStruct {
     pub version: RpcVersion,
+    format: NumberFormat,
     fields: serde_json::Map<String, Ok>,
 }
 
@@ -32,7 +45,15 @@ where
 
 impl Serializer {
     pub fn new(version: RpcVersion) -> Self {
-        Self { version }
+        Self {
+            version,
+            format: NumberFormat::Hex,
+        }
+    }
+
+    /// Serializes [Felt](crate::dto::Felt) values using the given format.
+    pub fn with_format(self, format: NumberFormat) -> Self {
+        Self { format, ..self }
     }
 
     pub fn serialize(self, value: &dyn SerializeForVersion) -> Result<Ok, Error> {
@@ -57,6 +78,7 @@ impl Serializer {
     pub fn serialize_struct(self) -> Result<SerializeStruct, Error> {
         Ok(SerializeStruct {
             version: self.version,
+            format: self.format,
             fields: Default::default(),
         })
     }
@@ -79,12 +101,16 @@ impl Serializer {
 }
 
 impl SerializeStruct {
+    fn serializer(&self) -> Serializer {
+        Serializer::new(self.version).with_format(self.format)
+    }
+
     pub fn serialize_field(
         &mut self,
         key: &'static str,
         value: &dyn SerializeForVersion,
     ) -> Result<(), Error> {
-        let value = value.serialize(Serializer::new(self.version))?;
+        let value = value.serialize(self.serializer())?;
         self.fields.insert(key.to_owned(), value);
         Ok(())
     }
@@ -95,7 +121,7 @@ impl SerializeStruct {
         len: usize,
         values: &mut dyn Iterator<Item = impl SerializeForVersion>,
     ) -> Result<(), Error> {
-        let seq = Serializer::new(self.version).serialize_iter(len, values)?;
+        let seq = self.serializer().serialize_iter(len, values)?;
         self.serialize_field(key, &seq)
     }
 
@@ -113,7 +139,7 @@ impl SerializeStruct {
     }
 
     pub fn flatten(&mut self, value: &dyn SerializeForVersion) -> Result<(), Error> {
-        let value = value.serialize(Serializer::new(self.version))?;
+        let value = value.serialize(self.serializer())?;
 
         if let serde_json::Value::Object(value) = value {
             for (k, v) in value {
@@ -171,6 +197,15 @@ mod tests {
             assert_eq!(uut.version, RpcVersion::PathfinderV01);
         }
 
+        #[test]
+        fn format_carries_over() {
+            let uut = Serializer::default()
+                .with_format(NumberFormat::Decimal)
+                .serialize_struct()
+                .unwrap();
+            assert_eq!(uut.serializer().format, NumberFormat::Decimal);
+        }
+
         #[test]
         fn optional() {
             let mut uut = Serializer::default().serialize_struct().unwrap();
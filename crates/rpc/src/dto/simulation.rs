@@ -33,7 +33,8 @@ impl crate::dto::serialize::SerializeForVersion for TransactionTrace<'_> {
                     )?;
                 }
                 if self.include_state_diff {
-                    serializer.serialize_field("state_diff", &StateDiff(&trace.state_diff))?;
+                    serializer
+                        .serialize_field("state_diff", &TransactionStateDiff(&trace.state_diff))?;
                 }
                 serializer.serialize_field(
                     "execution_resources",
@@ -65,7 +66,8 @@ impl crate::dto::serialize::SerializeForVersion for TransactionTrace<'_> {
                     )?;
                 }
                 if self.include_state_diff {
-                    serializer.serialize_field("state_diff", &StateDiff(&trace.state_diff))?;
+                    serializer
+                        .serialize_field("state_diff", &TransactionStateDiff(&trace.state_diff))?;
                 }
                 serializer.serialize_field(
                     "execution_resources",
@@ -91,7 +93,8 @@ impl crate::dto::serialize::SerializeForVersion for TransactionTrace<'_> {
                     )?;
                 }
                 if self.include_state_diff {
-                    serializer.serialize_field("state_diff", &StateDiff(&trace.state_diff))?;
+                    serializer
+                        .serialize_field("state_diff", &TransactionStateDiff(&trace.state_diff))?;
                 }
                 serializer.serialize_field(
                     "execution_resources",
@@ -107,7 +110,8 @@ impl crate::dto::serialize::SerializeForVersion for TransactionTrace<'_> {
                     })?),
                 )?;
                 if self.include_state_diff {
-                    serializer.serialize_field("state_diff", &StateDiff(&trace.state_diff))?;
+                    serializer
+                        .serialize_field("state_diff", &TransactionStateDiff(&trace.state_diff))?;
                 }
                 serializer.serialize_field(
                     "execution_resources",
@@ -120,7 +124,7 @@ impl crate::dto::serialize::SerializeForVersion for TransactionTrace<'_> {
 }
 
 #[derive(Debug)]
-struct FunctionInvocation<'a>(&'a pathfinder_executor::types::FunctionInvocation);
+pub struct FunctionInvocation<'a>(pub &'a pathfinder_executor::types::FunctionInvocation);
 
 impl crate::dto::serialize::SerializeForVersion for FunctionInvocation<'_> {
     fn serialize(
@@ -309,9 +313,88 @@ impl crate::dto::serialize::SerializeForVersion for InnerCallExecutionResources<
     }
 }
 
-struct StateDiff<'a>(&'a pathfinder_executor::types::StateDiff);
+/// The execution resources of each call made by a transaction, including
+/// nested calls, in execution order.
+pub struct InnerCallResources<'a>(pub &'a pathfinder_executor::types::TransactionTrace);
 
-impl crate::dto::serialize::SerializeForVersion for StateDiff<'_> {
+impl crate::dto::serialize::SerializeForVersion for InnerCallResources<'_> {
+    fn serialize(
+        &self,
+        serializer: super::serialize::Serializer,
+    ) -> Result<super::serialize::Ok, super::serialize::Error> {
+        use pathfinder_executor::types::{ExecuteInvocation, TransactionTrace};
+
+        fn flatten<'a>(
+            invocation: &'a pathfinder_executor::types::FunctionInvocation,
+            calls: &mut Vec<CallResources<'a>>,
+        ) {
+            calls.push(CallResources(invocation));
+            for inner in &invocation.internal_calls {
+                flatten(inner, calls);
+            }
+        }
+
+        let top_level = match self.0 {
+            TransactionTrace::Declare(trace) => vec![
+                trace.validate_invocation.as_ref(),
+                trace.fee_transfer_invocation.as_ref(),
+            ],
+            TransactionTrace::DeployAccount(trace) => vec![
+                trace.constructor_invocation.as_ref(),
+                trace.validate_invocation.as_ref(),
+                trace.fee_transfer_invocation.as_ref(),
+            ],
+            TransactionTrace::Invoke(trace) => vec![
+                trace.validate_invocation.as_ref(),
+                match &trace.execute_invocation {
+                    ExecuteInvocation::FunctionInvocation(invocation) => invocation.as_ref(),
+                    ExecuteInvocation::RevertedReason(_) => None,
+                },
+                trace.fee_transfer_invocation.as_ref(),
+            ],
+            TransactionTrace::L1Handler(trace) => vec![trace.function_invocation.as_ref()],
+        };
+
+        let mut calls = Vec::new();
+        for invocation in top_level.into_iter().flatten() {
+            flatten(invocation, &mut calls);
+        }
+
+        serializer.serialize_iter(calls.len(), &mut calls.into_iter())
+    }
+}
+
+struct CallResources<'a>(&'a pathfinder_executor::types::FunctionInvocation);
+
+impl crate::dto::serialize::SerializeForVersion for CallResources<'_> {
+    fn serialize(
+        &self,
+        serializer: super::serialize::Serializer,
+    ) -> Result<super::serialize::Ok, super::serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+        serializer.serialize_field(
+            "contract_address",
+            &crate::dto::Felt(&self.0.contract_address.0),
+        )?;
+        serializer.serialize_field("entry_point_selector", &crate::dto::Felt(&self.0.selector))?;
+        match serializer.version {
+            RpcVersion::V08 => serializer.serialize_field(
+                "execution_resources",
+                &InnerCallExecutionResources(&self.0.execution_resources),
+            )?,
+            _ => serializer.serialize_field(
+                "execution_resources",
+                &ComputationResources(&self.0.computation_resources),
+            )?,
+        }
+        serializer.end()
+    }
+}
+
+/// The state changes made by a single transaction.
+pub struct TransactionStateDiff<'a>(pub &'a pathfinder_executor::types::StateDiff);
+
+impl crate::dto::serialize::SerializeForVersion for TransactionStateDiff<'_> {
     fn serialize(
         &self,
         serializer: super::serialize::Serializer,
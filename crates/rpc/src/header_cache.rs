@@ -0,0 +1,213 @@
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use cached::{Cached, SizedCache};
+use pathfinder_common::{BlockHeader, BlockNumber};
+use pathfinder_storage::{BlockId, Transaction};
+use tokio::sync::broadcast;
+
+use crate::jsonrpc::{Notifications, Reorg};
+
+/// A least recently used cache of block headers keyed by block number, which
+/// saves RPC methods a database read for frequently requested blocks.
+///
+/// Historical headers are immutable, so the only entries that can become stale
+/// are those of blocks at the tip which get reorged away. These are evicted
+/// when the corresponding [Reorg] notification is received.
+#[derive(Clone)]
+pub struct BlockHeaderCache(Arc<Mutex<Inner>>);
+
+struct Inner {
+    headers: SizedCache<BlockNumber, Arc<BlockHeader>>,
+    capacity: NonZeroUsize,
+    /// The highest block number inserted into `headers`, which bounds the range
+    /// of entries evicted by a reorg.
+    highest: Option<BlockNumber>,
+    reorgs: broadcast::Receiver<Arc<Reorg>>,
+    /// Incremented every time entries are evicted because of a reorg.
+    generation: u64,
+}
+
+impl BlockHeaderCache {
+    pub fn new(capacity: NonZeroUsize, notifications: &Notifications) -> Self {
+        Self(Arc::new(Mutex::new(Inner {
+            headers: SizedCache::with_size(capacity.get()),
+            capacity,
+            highest: None,
+            reorgs: notifications.reorgs.subscribe(),
+            generation: 0,
+        })))
+    }
+
+    /// Same as [Transaction::block_header], except that headers of blocks
+    /// requested by number are served from the cache if possible.
+    ///
+    /// Headers read from the database are cached regardless of how they were
+    /// requested.
+    pub fn block_header(
+        &self,
+        tx: &Transaction<'_>,
+        block: BlockId,
+    ) -> anyhow::Result<Option<BlockHeader>> {
+        let generation = {
+            let mut inner = self.lock();
+            if let BlockId::Number(number) = block {
+                if let Some(header) = inner.headers.cache_get(&number) {
+                    return Ok(Some(header.as_ref().clone()));
+                }
+            }
+            inner.generation
+        };
+
+        let header = tx.block_header(block)?;
+
+        if let Some(header) = &header {
+            let mut inner = self.lock();
+            // The header may have been read before a reorg replaced the block, in which
+            // case it must not be cached.
+            if inner.generation == generation {
+                inner.insert(header.clone());
+            }
+        }
+
+        Ok(header)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        let mut inner = self.0.lock().unwrap();
+        inner.process_reorgs();
+        inner
+    }
+
+    #[cfg(test)]
+    fn misses(&self) -> Option<u64> {
+        self.0.lock().unwrap().headers.cache_misses()
+    }
+}
+
+impl Inner {
+    fn insert(&mut self, header: BlockHeader) {
+        self.highest = self.highest.max(Some(header.number));
+        self.headers.cache_set(header.number, Arc::new(header));
+    }
+
+    fn process_reorgs(&mut self) {
+        use broadcast::error::TryRecvError;
+
+        loop {
+            match self.reorgs.try_recv() {
+                Ok(reorg) => self.evict_from(reorg.first_block_number),
+                Err(TryRecvError::Lagged(_)) => self.evict_from(BlockNumber::GENESIS),
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Evicts the headers of `first` and all later blocks.
+    fn evict_from(&mut self, first: BlockNumber) {
+        self.generation += 1;
+
+        let Some(highest) = self.highest else {
+            return;
+        };
+        if highest < first {
+            return;
+        }
+
+        if highest.get() - first.get() >= self.capacity.get() as u64 {
+            self.headers.cache_clear();
+        } else {
+            let mut number = first;
+            while number <= highest {
+                self.headers.cache_remove(&number);
+                number += 1;
+            }
+        }
+        self.highest = first.parent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_storage::StorageBuilder;
+
+    use super::*;
+
+    fn header(number: u64) -> BlockHeader {
+        BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(number))
+            .finalize_with_hash(block_hash!("0x1"))
+    }
+
+    #[test]
+    fn sequential_reads_hit_the_database_once() {
+        let storage = StorageBuilder::in_memory().unwrap();
+        let mut db = storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        let expected = header(0);
+        tx.insert_block_header(&expected).unwrap();
+
+        let cache = BlockHeaderCache::new(NonZeroUsize::new(8).unwrap(), &Default::default());
+        let block = BlockId::Number(expected.number);
+
+        let first = cache.block_header(&tx, block).unwrap();
+        // Remove the block so that a second database read would not find it.
+        tx.purge_block(expected.number).unwrap();
+        let second = cache.block_header(&tx, block).unwrap();
+
+        assert_eq!(first, Some(expected.clone()));
+        assert_eq!(second, Some(expected));
+        assert_eq!(cache.misses(), Some(1));
+    }
+
+    #[test]
+    fn reorg_evicts_replaced_blocks_only() {
+        let storage = StorageBuilder::in_memory().unwrap();
+        let mut db = storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        let headers = (0..4).map(header).collect::<Vec<_>>();
+        for header in &headers {
+            tx.insert_block_header(header).unwrap();
+        }
+
+        let notifications = Notifications::default();
+        let cache = BlockHeaderCache::new(NonZeroUsize::new(8).unwrap(), &notifications);
+        for header in &headers {
+            cache
+                .block_header(&tx, BlockId::Number(header.number))
+                .unwrap();
+        }
+
+        // Blocks 2 and 3 are reorged away and block 2 is replaced.
+        tx.purge_block(BlockNumber::new_or_panic(3)).unwrap();
+        tx.purge_block(BlockNumber::new_or_panic(2)).unwrap();
+        let replacement = BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(2))
+            .finalize_with_hash(block_hash!("0x2"));
+        tx.insert_block_header(&replacement).unwrap();
+        notifications
+            .reorgs
+            .send(
+                Reorg {
+                    first_block_number: BlockNumber::new_or_panic(2),
+                    first_block_hash: headers[2].hash,
+                    last_block_number: BlockNumber::new_or_panic(3),
+                    last_block_hash: headers[3].hash,
+                }
+                .into(),
+            )
+            .unwrap();
+        // Blocks before the reorg must still be served from the cache.
+        tx.purge_block(BlockNumber::new_or_panic(1)).unwrap();
+
+        let read = |number| {
+            cache
+                .block_header(&tx, BlockId::Number(BlockNumber::new_or_panic(number)))
+                .unwrap()
+        };
+        assert_eq!(read(1), Some(headers[1].clone()));
+        assert_eq!(read(2), Some(replacement));
+        assert_eq!(read(3), None);
+    }
+}
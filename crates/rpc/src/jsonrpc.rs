@@ -19,9 +19,10 @@ pub use router::{
     RpcRouterBuilder,
     RpcSubscriptionFlow,
     SubscriptionMessage,
+    SubscriptionMessageSender,
 };
 use starknet_gateway_types::reply::Block;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum RequestId {
@@ -44,6 +45,9 @@ pub struct Notifications {
     pub block_headers: broadcast::Sender<Arc<pathfinder_common::BlockHeader>>,
     pub l2_blocks: broadcast::Sender<Arc<Block>>,
     pub reorgs: broadcast::Sender<Arc<Reorg>>,
+    /// The number of the latest block, [None] until the first block has been
+    /// synced.
+    pub latest_block: watch::Sender<Option<BlockNumber>>,
 }
 
 #[derive(Debug, Clone)]
@@ -59,10 +63,12 @@ impl Default for Notifications {
         let (block_headers, _) = broadcast::channel(1024);
         let (l2_blocks, _) = broadcast::channel(1024);
         let (reorgs, _) = broadcast::channel(1024);
+        let (latest_block, _) = watch::channel(None);
         Self {
             block_headers,
             l2_blocks,
             reorgs,
+            latest_block,
         }
     }
 }
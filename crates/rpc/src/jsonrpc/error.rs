@@ -17,6 +17,10 @@ pub enum RpcError {
         subscription_id: u32,
         reason: String,
     },
+    /// The connection already has the maximum number of active subscriptions.
+    TooManySubscriptions {
+        limit: usize,
+    },
 }
 
 impl PartialEq for RpcError {
@@ -39,6 +43,7 @@ impl RpcError {
             RpcError::InternalError(_) => -32603,
             RpcError::ApplicationError(err) => err.code(),
             RpcError::WebsocketSubscriptionClosed { .. } => -32099,
+            RpcError::TooManySubscriptions { .. } => -32003,
         }
     }
 
@@ -51,6 +56,7 @@ impl RpcError {
             RpcError::InternalError(_) => "Internal error".into(),
             RpcError::ApplicationError(e) => e.message(version).into(),
             RpcError::WebsocketSubscriptionClosed { .. } => "Websocket subscription closed".into(),
+            RpcError::TooManySubscriptions { .. } => "Too many subscriptions".into(),
         }
     }
 
@@ -63,6 +69,9 @@ impl RpcError {
                 "id": subscription_id,
                 "reason": reason,
             })),
+            RpcError::TooManySubscriptions { limit } => Some(json!({
+                "limit": limit,
+            })),
             RpcError::ApplicationError(e) => e.data(version),
             RpcError::InternalError(_) => None,
             RpcError::MethodNotFound => None,
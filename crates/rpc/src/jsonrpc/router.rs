@@ -9,8 +9,14 @@ use http::HeaderValue;
 use method::RpcMethodEndpoint;
 #[cfg(test)]
 pub use subscription::CATCH_UP_BATCH_SIZE;
-pub use subscription::{handle_json_rpc_socket, CatchUp, RpcSubscriptionFlow, SubscriptionMessage};
-use subscription::{split_ws, RpcSubscriptionEndpoint};
+pub use subscription::{
+    handle_json_rpc_socket,
+    CatchUp,
+    RpcSubscriptionFlow,
+    SubscriptionMessage,
+    SubscriptionMessageSender,
+};
+use subscription::{split_ws, MessageFormat, RpcSubscriptionEndpoint};
 
 use crate::context::RpcContext;
 use crate::dto::serialize;
@@ -127,8 +133,10 @@ impl RpcRouter {
 
         metrics::increment_counter!("rpc_method_calls_total", "method" => method_name, "version" => self.version.to_str());
 
+        let started = std::time::Instant::now();
         let method = method.invoke(self.context.clone(), request.params, self.version);
         let result = std::panic::AssertUnwindSafe(method).catch_unwind().await;
+        metrics::histogram!("rpc_method_call_duration_seconds", started.elapsed(), "method" => method_name, "version" => self.version.to_str());
 
         let output = match result {
             Ok(output) => output,
@@ -190,10 +198,15 @@ pub async fn rpc_handler(
     body: axum::body::Bytes,
 ) -> impl axum::response::IntoResponse {
     match ws {
-        Some(ws) => ws.on_upgrade(|ws| async move {
-            let (ws_tx, ws_rx) = split_ws(ws, state.version);
-            handle_json_rpc_socket(state, ws_tx, ws_rx);
-        }),
+        Some(ws) => ws
+            .protocols(MessageFormat::SUBPROTOCOLS)
+            .max_frame_size(state.context.config.websocket_max_message_size_bytes)
+            .max_message_size(state.context.config.websocket_max_message_size_bytes)
+            .on_upgrade(|ws| async move {
+                let format = MessageFormat::from_subprotocol(ws.protocol());
+                let (ws_tx, ws_rx) = split_ws(ws, state.version, format);
+                handle_json_rpc_socket(state, ws_tx, ws_rx);
+            }),
         None => {
             if method != http::Method::POST {
                 return StatusCode::METHOD_NOT_ALLOWED.into_response();
@@ -907,6 +920,118 @@ mod tests {
         assert_eq!(content_type, "application/json");
     }
 
+    mod message_format {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+        use tokio_tungstenite::tungstenite::Message;
+
+        use super::*;
+
+        fn router() -> RpcRouter {
+            fn success() -> &'static str {
+                "Success"
+            }
+
+            RpcRouter::builder(Default::default())
+                .register("success", success)
+                .build(RpcContext::for_tests())
+        }
+
+        /// Connects to a new server, requesting the given subprotocol.
+        async fn connect(
+            protocol: &str,
+        ) -> tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        > {
+            let url = spawn_server(router()).await.replace("http", "ws");
+            let mut request = url.into_client_request().unwrap();
+            request
+                .headers_mut()
+                .insert("Sec-WebSocket-Protocol", protocol.parse().unwrap());
+            let (ws, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+            assert_eq!(
+                response.headers().get("Sec-WebSocket-Protocol").unwrap(),
+                protocol
+            );
+            ws
+        }
+
+        fn to_cbor(value: ciborium::Value) -> Vec<u8> {
+            let mut bytes = Vec::new();
+            ciborium::into_writer(&value, &mut bytes).unwrap();
+            bytes
+        }
+
+        fn from_cbor(message: Message) -> Value {
+            let Message::Binary(bytes) = message else {
+                panic!("Expected a binary response, got {message:?}");
+            };
+            ciborium::from_reader(bytes.as_slice()).unwrap()
+        }
+
+        #[tokio::test]
+        async fn json() {
+            let mut ws = connect("json").await;
+
+            ws.send(Message::Text(
+                json!({"jsonrpc": "2.0", "method": "success", "id": 1}).to_string(),
+            ))
+            .await
+            .unwrap();
+
+            let Message::Text(response) = ws.next().await.unwrap().unwrap() else {
+                panic!("Expected a text response");
+            };
+            let response: Value = serde_json::from_str(&response).unwrap();
+            assert_eq!(
+                response,
+                json!({"jsonrpc": "2.0", "result": "Success", "id": 1})
+            );
+        }
+
+        #[tokio::test]
+        async fn cbor() {
+            let mut ws = connect("cbor").await;
+
+            let request =
+                ciborium::cbor!({"jsonrpc" => "2.0", "method" => "success", "id" => 1}).unwrap();
+            ws.send(Message::Binary(to_cbor(request))).await.unwrap();
+
+            let response = from_cbor(ws.next().await.unwrap().unwrap());
+            assert_eq!(
+                response,
+                json!({"jsonrpc": "2.0", "result": "Success", "id": 1})
+            );
+        }
+
+        #[tokio::test]
+        async fn cbor_response_to_json_request() {
+            let mut ws = connect("cbor").await;
+
+            ws.send(Message::Text(
+                json!({"jsonrpc": "2.0", "method": "success", "id": 1}).to_string(),
+            ))
+            .await
+            .unwrap();
+
+            let response = from_cbor(ws.next().await.unwrap().unwrap());
+            assert_eq!(
+                response,
+                json!({"jsonrpc": "2.0", "result": "Success", "id": 1})
+            );
+        }
+
+        #[tokio::test]
+        async fn invalid_cbor_is_a_parse_error() {
+            let mut ws = connect("cbor").await;
+
+            ws.send(Message::Binary(vec![0xff, 0xff])).await.unwrap();
+
+            let response = from_cbor(ws.next().await.unwrap().unwrap());
+            assert_eq!(response["error"]["code"], json!(-32700));
+            assert_eq!(response["id"], Value::Null);
+        }
+    }
+
     mod concurrent_futures {
         use std::cmp::max;
         use std::sync::Arc;
@@ -1071,4 +1196,29 @@ mod tests {
             max_simultaneous
         }
     }
+
+    #[tokio::test]
+    async fn websocket_message_too_big() {
+        use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+        use tokio_tungstenite::tungstenite::Message;
+
+        let mut context = RpcContext::for_tests();
+        context.config.websocket_max_message_size_bytes = 1024;
+        let router = RpcRouter::builder(Default::default()).build(context);
+
+        let url = spawn_server(router).await.replace("http", "ws");
+        let (ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let (mut ws_tx, mut ws_rx) = ws.split();
+
+        // The server closes the connection before reading the whole message, so
+        // sending it may fail.
+        tokio::spawn(async move {
+            let _ = ws_tx.send(Message::Text("a".repeat(2048))).await;
+        });
+
+        let Message::Close(Some(frame)) = ws_rx.next().await.unwrap().unwrap() else {
+            panic!("Expected a close frame");
+        };
+        assert_eq!(frame.code, CloseCode::Size);
+    }
 }
@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
-use axum::extract::ws::{Message, WebSocket};
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket};
 use dashmap::DashMap;
 use futures::{SinkExt, StreamExt};
 use pathfinder_common::{BlockId, BlockNumber};
 use serde_json::value::RawValue;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, OwnedSemaphorePermit, RwLock, Semaphore};
 use tracing::Instrument;
 
 use super::{run_concurrently, RpcRouter};
@@ -32,6 +32,8 @@ pub(super) struct InvokeParams {
     subscriptions: Arc<DashMap<SubscriptionId, tokio::task::JoinHandle<()>>>,
     ws_tx: mpsc::Sender<Result<Message, RpcResponse>>,
     lock: Arc<RwLock<()>>,
+    /// Held by the subscription task for as long as it runs.
+    permit: OwnedSemaphorePermit,
 }
 
 /// This trait is the main entry point for subscription endpoint
@@ -53,7 +55,8 @@ pub(super) struct InvokeParams {
 ///
 /// The flow is implemented as follows:
 /// - Catch up from the starting block to the latest block known to pathfinder,
-///   in batches. Call that block K.
+///   in batches. Several batches may be fetched concurrently, see
+///   [`RpcConfig`](crate::context::RpcConfig). Call that block K.
 /// - Subscribe to active updates. Fetch the first update, along with the block
 ///   number that it applies to.
 /// - Catch up from block K to the block just before the first active update.
@@ -92,11 +95,20 @@ pub trait RpcSubscriptionFlow: Send + Sync {
         Ok(Default::default())
     }
 
-    /// Subscribe to active updates.
+    /// Whether an active update from `subscribe` should be sent to the client.
+    ///
+    /// Active updates are checked for skipped blocks before this filter is
+    /// applied, so `subscribe` should send an update for every block and leave
+    /// dropping whole blocks to this method.
+    fn should_notify(_params: &Self::Params, _notification: &Self::Notification) -> bool {
+        true
+    }
+
+    /// Subscribe to active updates. Return once sending a message fails.
     async fn subscribe(
         state: RpcContext,
         params: Self::Params,
-        tx: mpsc::Sender<SubscriptionMessage<Self::Notification>>,
+        tx: SubscriptionMessageSender<Self::Notification>,
     ) -> Result<(), RpcError>;
 }
 
@@ -132,6 +144,48 @@ pub struct SubscriptionMessage<T> {
     pub subscription_name: &'static str,
 }
 
+/// Sends the active updates of [`RpcSubscriptionFlow::subscribe`].
+#[derive(Debug)]
+pub struct SubscriptionMessageSender<T> {
+    tx: mpsc::Sender<SubscriptionMessage<T>>,
+    /// Used to end the subscription if the client does not read its messages.
+    subscription: SubscriptionSender<T>,
+    /// See [`RpcConfig`](crate::context::RpcConfig).
+    timeout: std::time::Duration,
+}
+
+impl<T> SubscriptionMessageSender<T> {
+    /// Fails if the subscription is closing, or if the client did not make room
+    /// for the message within the timeout. A client which does not read its
+    /// messages would otherwise block the subscription indefinitely.
+    ///
+    /// On timeout the subscription is ended and the client is sent a
+    /// [`RpcError::WebsocketSubscriptionClosed`] error once it has read the
+    /// messages sent before.
+    pub async fn send(
+        &self,
+        msg: SubscriptionMessage<T>,
+    ) -> Result<(), mpsc::error::SendTimeoutError<SubscriptionMessage<T>>> {
+        let result = self.tx.send_timeout(msg, self.timeout).await;
+        if let Err(mpsc::error::SendTimeoutError::Timeout(_)) = result {
+            tracing::warn!(
+                subscription_id = self.subscription.subscription_id.0,
+                timeout = ?self.timeout,
+                "Client is not reading subscription messages, closing subscription"
+            );
+            self.subscription
+                .close_with_error(RpcError::WebsocketSubscriptionClosed {
+                    subscription_id: self.subscription.subscription_id.0,
+                    reason: format!(
+                        "Client did not read subscription messages within {:?}",
+                        self.timeout
+                    ),
+                });
+        }
+        result
+    }
+}
+
 #[axum::async_trait]
 impl<T> RpcSubscriptionEndpoint for T
 where
@@ -146,6 +200,7 @@ where
             subscriptions,
             ws_tx,
             lock,
+            permit,
         }: InvokeParams,
     ) -> Result<tokio::task::JoinHandle<()>, RpcError> {
         let params = T::Params::deserialize(crate::dto::Value::new(input, router.version))
@@ -171,7 +226,7 @@ where
                 // No need to catch up. The code below will subscribe to new blocks.
                 None
             }
-            first_block @ (BlockId::Number(_) | BlockId::Hash(_)) => {
+            first_block @ (BlockId::Number(_) | BlockId::Hash(_) | BlockId::L1Accepted) => {
                 // Load the first block number, return an error if it's invalid.
                 let first_block = pathfinder_storage::BlockId::try_from(first_block)
                     .map_err(|e| RpcError::InvalidParams(e.to_string()))?;
@@ -194,32 +249,44 @@ where
                 subscription_id,
                 subscriptions,
             };
+            // Frees up the subscription slot when the task ends or is aborted.
+            let _permit = permit;
             // This lock ensures that the streaming of subscriptions doesn't start before
             // the caller sends the success response for the subscription request.
             let _lock_guard = lock.read().await;
 
-            // Catch up to the latest block in batches of BATCH_SIZE.
+            // Catch up to the latest block in batches of CATCH_UP_BATCH_SIZE. Several
+            // batches are fetched concurrently, but are sent in order.
             if let Some(current_block) = current_block.as_mut() {
-                loop {
-                    // -1 because the end is inclusive, otherwise we get batches of
-                    // `CATCH_UP_BATCH_SIZE + 1` which probably doesn't really
-                    // matter, but it's misleading.
-                    let end = *current_block + CATCH_UP_BATCH_SIZE - 1;
-                    let catch_up =
-                        match T::catch_up(&router.context, &params, *current_block, end).await {
-                            Ok(messages) => messages,
-                            Err(e) => {
-                                tx.send_err(e)
-                                    .await
-                                    // Could error if the subscription is closing.
-                                    .ok();
-                                return;
-                            }
-                        };
+                let start = *current_block;
+                let context = &router.context;
+                let params = &params;
+                let mut batches = futures::stream::iter(0..)
+                    .map(|i| {
+                        let from = start + i * CATCH_UP_BATCH_SIZE;
+                        // -1 because the end is inclusive, otherwise we get batches of
+                        // `CATCH_UP_BATCH_SIZE + 1` which probably doesn't really
+                        // matter, but it's misleading.
+                        let end = from + CATCH_UP_BATCH_SIZE - 1;
+                        async move { (end, T::catch_up(context, params, from, end).await) }
+                    })
+                    .buffered(context.config.subscription_catchup_concurrency.get());
+                while let Some((end, catch_up)) = batches.next().await {
+                    let catch_up = match catch_up {
+                        Ok(messages) => messages,
+                        Err(e) => {
+                            tx.send_err(e)
+                                .await
+                                // Could error if the subscription is closing.
+                                .ok();
+                            return;
+                        }
+                    };
                     let last_block = match catch_up.last_block {
                         Some(last_block) => last_block,
                         None => {
                             // `None` means that there were no messages for the given block range.
+                            // Batches fetched for later ranges are discarded.
                             break;
                         }
                     };
@@ -248,6 +315,11 @@ where
                 let params = params.clone();
                 let context = router.context.clone();
                 let tx = tx.clone();
+                let tx1 = SubscriptionMessageSender {
+                    tx: tx1,
+                    subscription: tx.clone(),
+                    timeout: context.config.subscription_send_timeout,
+                };
                 async move {
                     if let Err(e) = T::subscribe(context, params, tx1).await {
                         tx.send_err(e).await.ok();
@@ -298,10 +370,11 @@ where
             }
 
             // Send the first subscription message and then forward the rest.
-            if tx
-                .send(first_msg.notification, first_msg.subscription_name)
-                .await
-                .is_err()
+            if T::should_notify(&params, &first_msg.notification)
+                && tx
+                    .send(first_msg.notification, first_msg.subscription_name)
+                    .await
+                    .is_err()
             {
                 // Subscription closing.
                 return;
@@ -313,10 +386,11 @@ where
                     // condition resulting from a reorg. This message should be ignored.
                     continue;
                 }
-                if tx
-                    .send(msg.notification, msg.subscription_name)
-                    .await
-                    .is_err()
+                if T::should_notify(&params, &msg.notification)
+                    && tx
+                        .send(msg.notification, msg.subscription_name)
+                        .await
+                        .is_err()
                 {
                     // Subscription closing.
                     break;
@@ -343,37 +417,124 @@ impl Drop for SubscriptionsGuard {
 type WsSender = mpsc::Sender<Result<Message, RpcResponse>>;
 type WsReceiver = mpsc::Receiver<Result<Message, axum::Error>>;
 
+/// The encoding of the messages exchanged over a websocket, negotiated using
+/// the `Sec-WebSocket-Protocol` header during the handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageFormat {
+    /// UTF-8 JSON text messages. This is the default if no subprotocol was
+    /// requested.
+    #[default]
+    Json,
+    /// [CBOR](https://cbor.io) encoded binary messages, which are smaller than
+    /// their JSON equivalent.
+    Cbor,
+}
+
+impl MessageFormat {
+    /// The subprotocols which can be requested by clients.
+    pub const SUBPROTOCOLS: [&'static str; 2] = ["json", "cbor"];
+
+    pub fn from_subprotocol(protocol: Option<&http::HeaderValue>) -> Self {
+        match protocol.map(http::HeaderValue::as_bytes) {
+            Some(b"cbor") => Self::Cbor,
+            _ => Self::Json,
+        }
+    }
+
+    /// Encodes a JSON message in this format.
+    fn encode(self, json: String) -> Message {
+        match self {
+            Self::Json => Message::Text(json),
+            Self::Cbor => {
+                // Responses are produced by us, so they are always valid JSON.
+                let value = serde_json::from_str(&json).expect("Outgoing message is valid JSON");
+                let mut bytes = Vec::new();
+                ciborium::into_writer(&json_to_cbor(value), &mut bytes)
+                    .expect("Writing to a Vec cannot fail");
+                Message::Binary(bytes)
+            }
+        }
+    }
+
+    /// Decodes a message in this format into a JSON message. Messages which
+    /// cannot be decoded are passed on as is, and result in a parse error.
+    fn decode(self, msg: Message) -> Message {
+        match (self, msg) {
+            (Self::Cbor, Message::Binary(bytes)) => {
+                match ciborium::from_reader::<serde_json::Value, _>(bytes.as_slice()) {
+                    Ok(value) => Message::Text(value.to_string()),
+                    Err(_) => Message::Binary(bytes),
+                }
+            }
+            (_, msg) => msg,
+        }
+    }
+}
+
+/// Converts a JSON value into a CBOR value.
+///
+/// This is done by hand because with `arbitrary_precision` enabled,
+/// [`serde_json::Number`] serializes as a map instead of as a number.
+fn json_to_cbor(value: serde_json::Value) -> ciborium::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => ciborium::Value::Null,
+        Value::Bool(b) => ciborium::Value::Bool(b),
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                ciborium::Value::Integer(n.into())
+            } else if let Some(n) = n.as_i64() {
+                ciborium::Value::Integer(n.into())
+            } else if let Some(n) = n.as_f64() {
+                ciborium::Value::Float(n)
+            } else {
+                ciborium::Value::Text(n.to_string())
+            }
+        }
+        Value::String(s) => ciborium::Value::Text(s),
+        Value::Array(values) => {
+            ciborium::Value::Array(values.into_iter().map(json_to_cbor).collect())
+        }
+        Value::Object(map) => ciborium::Value::Map(
+            map.into_iter()
+                .map(|(k, v)| (ciborium::Value::Text(k), json_to_cbor(v)))
+                .collect(),
+        ),
+    }
+}
+
 /// Split a websocket into an MPSC sender and receiver.
 /// These two are later passed to [`handle_json_rpc_socket`]. This separation
 /// serves to allow easier testing. The sender sends `Result<_, RpcResponse>`
 /// purely for convenience, and the [`RpcResponse`] will be encoded into a
 /// [`Message::Text`].
-pub fn split_ws(ws: WebSocket, version: RpcVersion) -> (WsSender, WsReceiver) {
+///
+/// Text messages are encoded and binary messages decoded according to the
+/// given [`MessageFormat`], so that [`handle_json_rpc_socket`] only ever deals
+/// with JSON.
+pub fn split_ws(
+    ws: WebSocket,
+    version: RpcVersion,
+    format: MessageFormat,
+) -> (WsSender, WsReceiver) {
     let (mut ws_sender, mut ws_receiver) = ws.split();
     // Send messages to the websocket using an MPSC channel.
     let (sender_tx, mut sender_rx) = mpsc::channel::<Result<Message, RpcResponse>>(1024);
     tokio::spawn(async move {
         while let Some(msg) = sender_rx.recv().await {
-            match msg {
-                Ok(msg) => {
-                    if ws_sender.send(msg).await.is_err() {
-                        break;
-                    }
-                }
-                Err(e) => {
-                    if ws_sender
-                        .send(Message::Text(
-                            serde_json::to_string(
-                                &e.serialize(serialize::Serializer::new(version)).unwrap(),
-                            )
-                            .unwrap(),
-                        ))
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
+            let msg = match msg {
+                Ok(Message::Text(json)) => format.encode(json),
+                Ok(msg) => msg,
+                Err(e) => format.encode(
+                    serde_json::to_string(
+                        &e.serialize(serialize::Serializer::new(version)).unwrap(),
+                    )
+                    .unwrap(),
+                ),
+            };
+            if ws_sender.send(msg).await.is_err() {
+                break;
             }
         }
     });
@@ -381,7 +542,11 @@ pub fn split_ws(ws: WebSocket, version: RpcVersion) -> (WsSender, WsReceiver) {
     let (receiver_tx, receiver_rx) = mpsc::channel::<Result<Message, axum::Error>>(1024);
     tokio::spawn(async move {
         while let Some(msg) = ws_receiver.next().await {
-            if receiver_tx.send(msg).await.is_err() {
+            if receiver_tx
+                .send(msg.map(|msg| format.decode(msg)))
+                .await
+                .is_err()
+            {
                 break;
             }
         }
@@ -389,6 +554,20 @@ pub fn split_ws(ws: WebSocket, version: RpcVersion) -> (WsSender, WsReceiver) {
     (sender_tx, receiver_rx)
 }
 
+/// Whether receiving a message failed because it exceeded the configured
+/// maximum message size.
+///
+/// The size is checked against the frame header, so the payload of such a
+/// message is never buffered.
+fn is_message_too_big(error: axum::Error) -> bool {
+    use tokio_tungstenite::tungstenite::error::{CapacityError, Error};
+
+    matches!(
+        error.into_inner().downcast_ref::<Error>(),
+        Some(Error::Capacity(CapacityError::MessageTooLong { .. }))
+    )
+}
+
 pub fn handle_json_rpc_socket(
     state: RpcRouter,
     ws_tx: mpsc::Sender<Result<Message, RpcResponse>>,
@@ -396,10 +575,37 @@ pub fn handle_json_rpc_socket(
 ) {
     let subscriptions: Arc<DashMap<SubscriptionId, tokio::task::JoinHandle<()>>> =
         Default::default();
+    let subscription_slots = Arc::new(Semaphore::new(
+        state.context.config.max_subscriptions_per_connection.get(),
+    ));
+    let mut keepalive = Keepalive::new(state.context.config.websocket_keepalive_interval);
     // Read and handle messages from the websocket.
     tokio::spawn(async move {
         loop {
-            let request = match ws_rx.recv().await {
+            let message = tokio::select! {
+                // Messages which arrived in the meantime are handled before the
+                // next ping, otherwise a pong received while a request was being
+                // processed could be missed.
+                biased;
+                message = ws_rx.recv() => message,
+                responsive = keepalive.tick() => {
+                    let message = if responsive {
+                        Message::Ping(Vec::new())
+                    } else {
+                        tracing::debug!("No pong received, closing websocket");
+                        Message::Close(Some(CloseFrame {
+                            code: close_code::AWAY,
+                            reason: "Keepalive timed out".into(),
+                        }))
+                    };
+                    if ws_tx.send(Ok(message)).await.is_err() || !responsive {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            let request = match message {
                 Some(Ok(Message::Text(msg))) => msg,
                 Some(Ok(Message::Binary(bytes))) => match String::from_utf8(bytes) {
                     Ok(msg) => msg,
@@ -415,8 +621,12 @@ pub fn handle_json_rpc_socket(
                         continue;
                     }
                 },
-                Some(Ok(Message::Pong(_) | Message::Ping(_))) => {
-                    // Ping and pong messages are handled automatically by axum.
+                Some(Ok(Message::Pong(_))) => {
+                    keepalive.pong_received();
+                    continue;
+                }
+                Some(Ok(Message::Ping(_))) => {
+                    // Ping messages are answered automatically by axum.
                     continue;
                 }
                 Some(Ok(Message::Close(_))) | None => {
@@ -425,6 +635,14 @@ pub fn handle_json_rpc_socket(
                 }
                 Some(Err(e)) => {
                     tracing::trace!(error = ?e, "Error receiving websocket message");
+                    if is_message_too_big(e) {
+                        let _ = ws_tx
+                            .send(Ok(Message::Close(Some(CloseFrame {
+                                code: close_code::SIZE,
+                                reason: "Message too big".into(),
+                            }))))
+                            .await;
+                    }
                     return;
                 }
             };
@@ -460,6 +678,7 @@ pub fn handle_json_rpc_socket(
                     &state,
                     raw_value,
                     subscriptions.clone(),
+                    subscription_slots.clone(),
                     ws_tx.clone(),
                     lock.clone(),
                 )
@@ -527,11 +746,19 @@ pub fn handle_json_rpc_socket(
                             let state = &state;
                             let ws_tx = ws_tx.clone();
                             let subscriptions = subscriptions.clone();
+                            let subscription_slots = subscription_slots.clone();
                             let lock = lock.clone();
                             async move {
-                                match handle_request(state, request, subscriptions, ws_tx, lock)
-                                    .instrument(tracing::debug_span!("ws batch", idx))
-                                    .await
+                                match handle_request(
+                                    state,
+                                    request,
+                                    subscriptions,
+                                    subscription_slots,
+                                    ws_tx,
+                                    lock,
+                                )
+                                .instrument(tracing::debug_span!("ws batch", idx))
+                                .await
                                 {
                                     Ok(Some(response)) | Err(response) => Some(response),
                                     Ok(None) => None,
@@ -571,6 +798,50 @@ pub fn handle_json_rpc_socket(
     });
 }
 
+/// Pings the client periodically so that idle connections are not dropped by
+/// NAT devices or load balancers, and detects clients which stopped
+/// responding.
+struct Keepalive {
+    /// `None` if keepalive is disabled.
+    interval: Option<tokio::time::Interval>,
+    awaiting_pong: bool,
+}
+
+impl Keepalive {
+    fn new(period: Option<std::time::Duration>) -> Self {
+        let interval = period.map(|period| {
+            // The first tick of `tokio::time::interval` completes immediately, there is
+            // no point in pinging a client which has just connected.
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            interval
+        });
+
+        Self {
+            interval,
+            awaiting_pong: false,
+        }
+    }
+
+    /// Completes once the next ping is due. Returns `false` if the client did
+    /// not respond to the previous ping in time. Never completes if keepalive
+    /// is disabled.
+    async fn tick(&mut self) -> bool {
+        match &mut self.interval {
+            Some(interval) => {
+                interval.tick().await;
+                !std::mem::replace(&mut self.awaiting_pong, true)
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    fn pong_received(&mut self) {
+        self.awaiting_pong = false;
+    }
+}
+
 /// Handle a single request. Returns `Result` for convenience, so that the `?`
 /// operator could be used in the body of the function. Returns `Ok(None)` if
 /// the request was a notification (i.e. no response is needed).
@@ -578,6 +849,7 @@ async fn handle_request(
     state: &RpcRouter,
     raw_request: &RawValue,
     subscriptions: Arc<DashMap<SubscriptionId, tokio::task::JoinHandle<()>>>,
+    subscription_slots: Arc<Semaphore>,
     ws_tx: mpsc::Sender<Result<Message, RpcResponse>>,
     lock: Arc<RwLock<()>>,
 ) -> Result<Option<RpcResponse>, RpcResponse> {
@@ -622,6 +894,9 @@ async fn handle_request(
                 version: state.version,
             })?;
         handle.abort();
+        // Wait for the task to be dropped so that its subscription slot is free by the
+        // time the client receives the response.
+        let _ = handle.await;
         metrics::increment_counter!("rpc_method_calls_total", "method" => "starknet_unsubscribe", "version" => state.version.to_str());
         return Ok(Some(RpcResponse {
             output: Ok(true.into()),
@@ -639,6 +914,19 @@ async fn handle_request(
     let params = serde_json::to_value(rpc_request.params)
         .map_err(|e| RpcResponse::invalid_params(req_id.clone(), e.to_string(), state.version))?;
 
+    // Reserve a slot before starting the subscription. The slot is released once
+    // the subscription task ends, including when it is aborted by
+    // `starknet_unsubscribe`.
+    let permit = subscription_slots
+        .try_acquire_owned()
+        .map_err(|_| RpcResponse {
+            output: Err(RpcError::TooManySubscriptions {
+                limit: state.context.config.max_subscriptions_per_connection.get(),
+            }),
+            id: req_id.clone(),
+            version: state.version,
+        })?;
+
     // Start the subscription.
     let router = state.clone();
     let subscription_id = SubscriptionId::next();
@@ -651,6 +939,7 @@ async fn handle_request(
             subscriptions: subscriptions.clone(),
             ws_tx: ws_tx.clone(),
             lock,
+            permit,
         })
         .await
     {
@@ -731,6 +1020,31 @@ impl<T: crate::dto::serialize::SerializeForVersion> SubscriptionSender<T> {
             // Race condition due to the subscription ending.
             return Ok(());
         }
+        self.tx
+            .send(Ok(self.error_message(err)))
+            .await
+            .map_err(|_| mpsc::error::SendError(()))
+    }
+}
+
+impl<T> SubscriptionSender<T> {
+    /// Ends the subscription and queues `err` as its final message.
+    ///
+    /// The message is sent in the background, since the websocket may be
+    /// backed up. It is dropped if the connection closes first.
+    pub fn close_with_error(&self, err: RpcError) {
+        let Some((_, handle)) = self.subscriptions.remove(&self.subscription_id) else {
+            // Already closing.
+            return;
+        };
+        handle.abort();
+
+        let tx = self.tx.clone();
+        let message = self.error_message(err);
+        tokio::spawn(async move { tx.send(Ok(message)).await });
+    }
+
+    fn error_message(&self, err: RpcError) -> Message {
         let notification = RpcNotification {
             jsonrpc: "2.0",
             method: "pathfinder_subscriptionError",
@@ -741,11 +1055,7 @@ impl<T: crate::dto::serialize::SerializeForVersion> SubscriptionSender<T> {
         }
         .serialize(crate::dto::serialize::Serializer::new(self.version))
         .unwrap();
-        let data = serde_json::to_string(&notification).unwrap();
-        self.tx
-            .send(Ok(Message::Text(data)))
-            .await
-            .map_err(|_| mpsc::error::SendError(()))
+        Message::Text(serde_json::to_string(&notification).unwrap())
     }
 }
 
@@ -798,7 +1108,7 @@ mod tests {
     use std::time::Duration;
 
     use axum::async_trait;
-    use axum::extract::ws::Message;
+    use axum::extract::ws::{close_code, CloseFrame, Message};
     use pathfinder_common::{BlockHash, BlockHeader, BlockId, BlockNumber, ChainId};
     use pathfinder_crypto::Felt;
     use pathfinder_ethereum::EthereumClient;
@@ -813,9 +1123,11 @@ mod tests {
     use crate::jsonrpc::{
         handle_json_rpc_socket,
         CatchUp,
+        RpcResponse,
         RpcRouter,
         RpcSubscriptionFlow,
         SubscriptionMessage,
+        SubscriptionMessageSender,
     };
     use crate::pending::PendingWatcher;
     use crate::types::syncing::Syncing;
@@ -848,7 +1160,7 @@ mod tests {
             async fn subscribe(
                 _state: RpcContext,
                 _params: Self::Params,
-                _tx: tokio::sync::mpsc::Sender<SubscriptionMessage<Self::Notification>>,
+                _tx: SubscriptionMessageSender<Self::Notification>,
             ) -> Result<(), crate::jsonrpc::RpcError> {
                 Ok(())
             }
@@ -923,7 +1235,7 @@ mod tests {
             async fn subscribe(
                 _state: RpcContext,
                 _params: Self::Params,
-                _tx: tokio::sync::mpsc::Sender<SubscriptionMessage<Self::Notification>>,
+                _tx: SubscriptionMessageSender<Self::Notification>,
             ) -> Result<(), crate::jsonrpc::RpcError> {
                 Err(crate::jsonrpc::RpcError::InternalError(anyhow::anyhow!(
                     "error from catch_up"
@@ -975,6 +1287,204 @@ mod tests {
         )
     }
 
+    struct Idle;
+
+    #[async_trait]
+    impl RpcSubscriptionFlow for Idle {
+        type Params = Params;
+        type Notification = serde_json::Value;
+
+        fn starting_block(_params: &Self::Params) -> BlockId {
+            BlockId::Latest
+        }
+
+        async fn catch_up(
+            _state: &RpcContext,
+            _params: &Self::Params,
+            _from: BlockNumber,
+            _to: BlockNumber,
+        ) -> Result<CatchUp<Self::Notification>, crate::jsonrpc::RpcError> {
+            Ok(Default::default())
+        }
+
+        async fn subscribe(
+            _state: RpcContext,
+            _params: Self::Params,
+            _tx: SubscriptionMessageSender<Self::Notification>,
+        ) -> Result<(), crate::jsonrpc::RpcError> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_pong_received() {
+        let mut router = setup(5, Idle).await;
+        router.context.config.websocket_keepalive_interval = Some(Duration::from_secs(10));
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router, sender_tx, receiver_rx);
+
+        for _ in 0..3 {
+            let start = tokio::time::Instant::now();
+            let msg = sender_rx.recv().await.unwrap().unwrap();
+            assert_eq!(msg, Message::Ping(Vec::new()));
+            assert_eq!(start.elapsed(), Duration::from_secs(10));
+            receiver_tx
+                .send(Ok(Message::Pong(Vec::new())))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_timeout() {
+        let mut router = setup(5, Idle).await;
+        router.context.config.websocket_keepalive_interval = Some(Duration::from_secs(10));
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (_receiver_tx, receiver_rx) = mpsc::channel(1024);
+        let start = tokio::time::Instant::now();
+        handle_json_rpc_socket(router, sender_tx, receiver_rx);
+
+        let msg = sender_rx.recv().await.unwrap().unwrap();
+        assert_eq!(msg, Message::Ping(Vec::new()));
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+
+        // No pong is sent, so the connection is closed when the next ping is due.
+        let msg = sender_rx.recv().await.unwrap().unwrap();
+        assert_eq!(
+            msg,
+            Message::Close(Some(CloseFrame {
+                code: close_code::AWAY,
+                reason: "Keepalive timed out".into(),
+            }))
+        );
+        assert_eq!(start.elapsed(), Duration::from_secs(20));
+        assert!(sender_rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_max_subscriptions_per_connection() {
+        let router = setup(5, Idle).await;
+        let limit = router.context.config.max_subscriptions_per_connection.get();
+        assert_eq!(limit, 100);
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router, sender_tx, receiver_rx);
+
+        async fn request(
+            receiver_tx: &mpsc::Sender<Result<Message, axum::Error>>,
+            sender_rx: &mut mpsc::Receiver<Result<Message, RpcResponse>>,
+            id: usize,
+            method: &str,
+            params: serde_json::Value,
+        ) -> serde_json::Value {
+            let request = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": method,
+                "params": params,
+            });
+            receiver_tx
+                .send(Ok(Message::Text(request.to_string())))
+                .await
+                .unwrap();
+            match sender_rx.recv().await.unwrap().unwrap() {
+                Message::Text(json) => serde_json::from_str(&json).unwrap(),
+                _ => panic!("Expected text message"),
+            }
+        }
+
+        let mut subscription_ids = Vec::new();
+        for id in 0..limit {
+            let json = request(
+                &receiver_tx,
+                &mut sender_rx,
+                id,
+                "test",
+                serde_json::json!({}),
+            )
+            .await;
+            assert_eq!(json["id"], id);
+            subscription_ids.push(json["result"].as_u64().unwrap());
+        }
+
+        let json = request(
+            &receiver_tx,
+            &mut sender_rx,
+            limit,
+            "test",
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": limit,
+                "error": {
+                    "code": -32003,
+                    "message": "Too many subscriptions",
+                    "data": { "limit": limit },
+                }
+            })
+        );
+
+        // Unsubscribing frees up a slot for a new subscription.
+        let json = request(
+            &receiver_tx,
+            &mut sender_rx,
+            limit + 1,
+            "starknet_unsubscribe",
+            serde_json::json!({ "subscription_id": subscription_ids[0] }),
+        )
+        .await;
+        assert_eq!(json["result"], true);
+        let json = request(
+            &receiver_tx,
+            &mut sender_rx,
+            limit + 2,
+            "test",
+            serde_json::json!({}),
+        )
+        .await;
+        assert!(json["result"].is_u64(), "{json}");
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_unknown_id() {
+        let router = setup(5, Idle).await;
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router, sender_tx, receiver_rx);
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_unsubscribe",
+            "params": { "subscription_id": 12345 },
+        });
+        receiver_tx
+            .send(Ok(Message::Text(request.to_string())))
+            .await
+            .unwrap();
+
+        let Message::Text(json) = sender_rx.recv().await.unwrap().unwrap() else {
+            panic!("Expected text message");
+        };
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {
+                    "code": 66,
+                    "message": "Invalid subscription id",
+                }
+            })
+        );
+    }
+
     #[derive(Debug, Clone)]
     struct Params;
 
@@ -984,6 +1494,73 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_subscription_ends_if_client_does_not_read() {
+        struct Flood;
+
+        #[async_trait]
+        impl RpcSubscriptionFlow for Flood {
+            type Params = Params;
+            type Notification = serde_json::Value;
+
+            async fn subscribe(
+                _state: RpcContext,
+                _params: Self::Params,
+                tx: SubscriptionMessageSender<Self::Notification>,
+            ) -> Result<(), crate::jsonrpc::RpcError> {
+                for i in 0.. {
+                    let msg = SubscriptionMessage {
+                        notification: serde_json::json!(i),
+                        block_number: BlockNumber::new_or_panic(i),
+                        subscription_name: "test",
+                    };
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let mut router = setup(0, Flood).await;
+        router.context.config.subscription_send_timeout = Duration::from_millis(100);
+        // Not read from until the send has timed out, so the channel is full once the
+        // subscription response has been sent.
+        let (sender_tx, mut sender_rx) = mpsc::channel(1);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router, sender_tx, receiver_rx);
+        receiver_tx
+            .send(Ok(Message::Text(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "test",
+                    "params": {}
+                })
+                .to_string(),
+            )))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        // The client is told that the subscription was closed once it reads the
+        // messages sent before.
+        let error = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let Ok(Message::Text(text)) = sender_rx.recv().await.unwrap() else {
+                    continue;
+                };
+                let message: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if message["method"] == "pathfinder_subscriptionError" {
+                    break message["params"]["result"].clone();
+                }
+            }
+        })
+        .await
+        .expect("Client should be notified that the subscription was closed");
+        assert_eq!(error["code"], -32099);
+    }
+
     async fn setup(num_blocks: u64, endpoint: impl RpcSubscriptionEndpoint + 'static) -> RpcRouter {
         let storage = StorageBuilder::in_memory().unwrap();
         tokio::task::spawn_blocking({
@@ -1009,8 +1586,14 @@ mod tests {
         let notifications = Notifications::default();
         let ctx = RpcContext {
             cache: Default::default(),
+            event_page_cache: crate::method::get_events::EventPageCache::new(&notifications),
+            header_cache: crate::header_cache::BlockHeaderCache::new(
+                1024.try_into().unwrap(),
+                &notifications,
+            ),
             storage,
             execution_storage: StorageBuilder::in_memory().unwrap(),
+            event_shards: Vec::new(),
             pending_data: PendingWatcher::new(pending_data),
             sync_status: SyncState {
                 status: Syncing::False(false).into(),
@@ -1028,6 +1611,18 @@ mod tests {
                 get_events_max_blocks_to_scan: 1.try_into().unwrap(),
                 get_events_max_event_filters_to_load: 1.try_into().unwrap(),
                 custom_versioned_constants: None,
+                large_block_threshold: 10_000,
+                class_fallback_url: None,
+                wait_for_block_timeout: std::time::Duration::from_secs(30),
+                validate_class_hash: false,
+                prefetch_next_event_page: false,
+                subscription_send_timeout: std::time::Duration::from_secs(30),
+                websocket_keepalive_interval: None,
+                subscription_catchup_concurrency: 1.try_into().unwrap(),
+                max_subscriptions_per_connection: 100.try_into().unwrap(),
+                block_header_cache_size: 1024.try_into().unwrap(),
+                websocket_max_message_size_bytes: 10 * 1024 * 1024,
+                approved_class_hashes: Vec::new(),
             },
         };
         RpcRouter::builder(crate::RpcVersion::V08)
@@ -251,6 +251,7 @@ impl serde::Serialize for BlockHeader {
             receipt_commitment,
             state_diff_commitment: _,
             state_diff_length: _,
+            block_body_commitment: _,
         } = &self.0;
 
         let mut map = serializer.serialize_map(Some(15))?;
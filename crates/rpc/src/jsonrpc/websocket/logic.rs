@@ -64,7 +64,8 @@ pub async fn websocket_handler(
     State(router): State<RpcRouter>,
 ) -> impl IntoResponse {
     let mut upgrade_response = ws
-        .max_message_size(crate::REQUEST_MAX_SIZE)
+        .max_frame_size(router.context.config.websocket_max_message_size_bytes)
+        .max_message_size(router.context.config.websocket_max_message_size_bytes)
         .on_failed_upgrade(|error| tracing::debug!(%error, "Websocket upgrade failed"))
         .on_upgrade(|socket| handle_socket(socket, router));
 
@@ -4,6 +4,7 @@ mod dto;
 mod error;
 mod executor;
 mod felt;
+mod header_cache;
 mod jsonrpc;
 pub(crate) mod method;
 pub mod middleware;
@@ -192,7 +193,10 @@ impl RpcServer {
             .with_state(v08_routes.clone())
             .route("/rpc/pathfinder/v0.1", post(rpc_handler))
             .route("/rpc/pathfinder/v0_1", post(rpc_handler))
-            .with_state(pathfinder_routes.clone());
+            .with_state(pathfinder_routes.clone())
+            .layer(axum::middleware::from_fn(
+                middleware::cache_control::cache_control,
+            ));
 
         let router = if self.context.websocket.is_some() {
             router
@@ -857,6 +861,36 @@ mod tests {
         }
     }
 
+    #[rstest::rstest]
+    #[case::v06(v06::register_routes(), "0.6.0")]
+    #[case::v07(v07::register_routes(), "0.7.1")]
+    #[case::v08(v08::register_routes(), "0.8.0-rc1")]
+    #[tokio::test]
+    async fn spec_version(#[case] routes: jsonrpc::RpcRouterBuilder, #[case] expected: &str) {
+        use axum::extract::ws::Message;
+        use tokio::sync::mpsc;
+
+        let router = routes.build(RpcContext::for_tests());
+        let (sender_tx, mut sender_rx) = mpsc::channel(1);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1);
+        jsonrpc::handle_json_rpc_socket(router, sender_tx, receiver_rx);
+
+        let request = json!({"jsonrpc": "2.0", "id": 1, "method": "starknet_specVersion"});
+        receiver_tx
+            .send(Ok(Message::Text(request.to_string())))
+            .await
+            .unwrap();
+        let Message::Text(response) = sender_rx.recv().await.unwrap().unwrap() else {
+            panic!("Expected a text message");
+        };
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+        assert_eq!(
+            response,
+            json!({"jsonrpc": "2.0", "id": 1, "result": expected})
+        );
+    }
+
     #[tokio::test]
     async fn empty_get_on_root_is_ok() {
         // Monitoring bots often get query `/` with no body as a form
@@ -894,6 +928,54 @@ mod tests {
         assert!(!status.is_success());
     }
 
+    #[rstest::rstest]
+    // The hashes of the blocks in the test storage are `block_hash_bytes!(b"genesis")`,
+    // which is accepted on L1, and `block_hash_bytes!(b"block 1")`.
+    #[case::by_hash(
+        json!([{"block_hash": "0x67656e65736973"}]),
+        Some("public, max-age=86400, immutable")
+    )]
+    #[case::by_hash_accepted_on_l2(
+        json!([{"block_hash": "0x626c6f636b2031"}]),
+        Some("public, max-age=10")
+    )]
+    #[case::by_number(json!([{"block_number": 0}]), Some("public, max-age=10"))]
+    #[case::latest(json!(["latest"]), Some("no-store"))]
+    #[case::not_found(json!([{"block_number": 999}]), None)]
+    #[tokio::test]
+    async fn get_block_with_tx_hashes_cache_control(
+        #[case] params: serde_json::Value,
+        #[case] expected: Option<&str>,
+    ) {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let context = RpcContext::for_tests();
+        let (_jh, addr) = RpcServer::new(addr, context, RpcVersion::V07)
+            .spawn()
+            .await
+            .unwrap();
+
+        let url = format!("http://{addr}/rpc/v0_7");
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": "starknet_getBlockWithTxHashes",
+            "params": params,
+            "id": 0,
+        });
+
+        let response = reqwest::Client::new()
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        let cache_control = response
+            .headers()
+            .get("cache-control")
+            .map(|value| value.to_str().unwrap().to_owned());
+
+        assert_eq!(cache_control.as_deref(), expected);
+    }
+
     enum Api {
         HttpOnly,
         WebsocketOnly,
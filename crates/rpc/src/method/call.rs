@@ -126,8 +126,9 @@ pub async fn call(context: RpcContext, input: Input) -> Result<Output, CallError
             }
             other => {
                 let block_id = other.try_into().expect("Only pending cast should fail");
-                let header = db
-                    .block_header(block_id)
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_id)
                     .context("Querying block header")?
                     .ok_or(CallError::BlockNotFound)?;
 
@@ -38,8 +38,9 @@ pub async fn estimate_message_fee(
             }
             other => {
                 let block_id = other.try_into().expect("Only pending cast should fail");
-                let header = db
-                    .block_header(block_id)
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_id)
                     .context("Querying block header")?
                     .ok_or(EstimateMessageFeeError::BlockNotFound)?;
 
@@ -222,8 +223,8 @@ mod tests {
 
     enum Setup {
         Full,
-        _SkipBlock,
-        _SkipContract,
+        SkipBlock,
+        SkipContract,
     }
 
     async fn setup(mode: Setup) -> anyhow::Result<RpcContext> {
@@ -257,7 +258,7 @@ mod tests {
             let block1_number = BlockNumber::GENESIS + 1;
             let block1_hash = BlockHash(felt!("0xb01"));
 
-            if !matches!(mode, Setup::_SkipBlock) {
+            if !matches!(mode, Setup::SkipBlock) {
                 let header = BlockHeader::builder()
                     .number(BlockNumber::GENESIS)
                     .timestamp(BlockTimestamp::new_or_panic(0))
@@ -278,7 +279,7 @@ mod tests {
                 tx.insert_block_header(&header).unwrap();
             }
 
-            if !matches!(mode, Setup::_SkipBlock | Setup::_SkipContract) {
+            if !matches!(mode, Setup::SkipBlock | Setup::SkipContract) {
                 let contract_address = contract_address!(
                     "0x57dde83c18c0efe7123c36a52d704cf27d5c38cdf0b1e1edc3b0dae3ee4e374"
                 );
@@ -329,4 +330,71 @@ mod tests {
             .expect("result");
         assert_eq!(result, expected);
     }
+
+    #[tokio::test]
+    async fn test_block_not_found() {
+        let rpc = setup(Setup::SkipBlock).await.expect("RPC context");
+        let result = super::estimate_message_fee(rpc, input()).await;
+        assert_matches::assert_matches!(result, Err(super::EstimateMessageFeeError::BlockNotFound));
+    }
+
+    #[tokio::test]
+    async fn test_contract_not_found() {
+        let rpc = setup(Setup::SkipContract).await.expect("RPC context");
+        let result = super::estimate_message_fee(rpc, input()).await;
+        assert_matches::assert_matches!(
+            result,
+            Err(super::EstimateMessageFeeError::ContractNotFound)
+        );
+    }
+
+    /// `starknet_estimateMessageFee` must be routed to this method rather than
+    /// to `starknet_estimateFee` on v0.8.
+    #[tokio::test]
+    async fn v08_route() {
+        use axum::extract::ws::Message;
+        use tokio::sync::mpsc;
+
+        use crate::dto::serialize::{SerializeForVersion, Serializer};
+
+        let rpc = setup(Setup::Full).await.expect("RPC context");
+        let expected = super::estimate_message_fee(rpc.clone(), input())
+            .await
+            .expect("result")
+            .serialize(Serializer::new(crate::RpcVersion::V08))
+            .unwrap();
+
+        let router = crate::v08::register_routes().build(rpc);
+        let (sender_tx, mut sender_rx) = mpsc::channel(1);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1);
+        crate::jsonrpc::handle_json_rpc_socket(router, sender_tx, receiver_rx);
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_estimateMessageFee",
+            "params": {
+                "message": {
+                    "from_address": "0x0000000000000000000000000000000000000000",
+                    "to_address": "0x57dde83c18c0efe7123c36a52d704cf27d5c38cdf0b1e1edc3b0dae3ee4e374",
+                    "entry_point_selector": EntryPoint::hashed(b"my_l1_handler"),
+                    "payload": ["0xa"]
+                },
+                "block_id": {"block_number": 1}
+            }
+        });
+        receiver_tx
+            .send(Ok(Message::Text(request.to_string())))
+            .await
+            .unwrap();
+        let Message::Text(response) = sender_rx.recv().await.unwrap().unwrap() else {
+            panic!("Expected a text message");
+        };
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+        assert_eq!(
+            response,
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": expected})
+        );
+    }
 }
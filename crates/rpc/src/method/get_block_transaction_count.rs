@@ -97,6 +97,58 @@ mod tests {
         assert_eq!(result.0, expected);
     }
 
+    #[rstest::rstest]
+    #[case::empty(3, 0)]
+    #[case::single(4, 1)]
+    #[case::many(5, 7)]
+    #[tokio::test]
+    async fn counts_stored_transactions(#[case] block_number: u64, #[case] expected: u64) {
+        use pathfinder_common::receipt::Receipt;
+        use pathfinder_common::transaction::Transaction;
+        use pathfinder_common::{BlockHeader, BlockNumber};
+
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let mut parent_hash = block_hash_bytes!(b"latest");
+        for (number, count) in [(3, 0), (4, 1), (5, 7)] {
+            let hash = block_hash_bytes!(format!("count block {number}").as_bytes());
+            let header = BlockHeader::builder()
+                .number(BlockNumber::new_or_panic(number))
+                .parent_hash(parent_hash)
+                .finalize_with_hash(hash);
+            tx.insert_block_header(&header).unwrap();
+
+            let body = (0..count)
+                .map(|i| {
+                    let hash =
+                        transaction_hash_bytes!(format!("count txn {number} {i}").as_bytes());
+                    let transaction = Transaction {
+                        hash,
+                        variant: Default::default(),
+                    };
+                    let receipt = Receipt {
+                        transaction_hash: hash,
+                        ..Default::default()
+                    };
+                    (transaction, receipt)
+                })
+                .collect::<Vec<_>>();
+            tx.insert_transaction_data(header.number, &body, None)
+                .unwrap();
+            parent_hash = hash;
+        }
+        tx.commit().unwrap();
+
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::new_or_panic(block_number)),
+        };
+        let result = get_block_transaction_count(context, input).await.unwrap();
+
+        assert_eq!(result.0, expected);
+    }
+
     #[tokio::test]
     async fn block_not_found() {
         let input = Input {
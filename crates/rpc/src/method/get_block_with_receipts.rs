@@ -60,8 +60,9 @@ pub async fn get_block_with_receipts(context: RpcContext, input: Input) -> Resul
             other => other.try_into().expect("Only pending cast should fail"),
         };
 
-        let header = db
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&db, block_id)
             .context("Fetching block header")?
             .ok_or(Error::BlockNotFound)?;
 
@@ -177,6 +178,7 @@ impl crate::dto::serialize::SerializeForVersion for TransactionWithReceipt<'_> {
                 transaction: self.transaction,
                 events: self.events,
                 finality: self.finality,
+                data_gas_price: None,
             },
         )?;
         serializer.end()
@@ -201,9 +203,7 @@ mod tests {
         let output = get_block_with_receipts(context.clone(), input)
             .await
             .unwrap()
-            .serialize(Serializer {
-                version: RpcVersion::V07,
-            })
+            .serialize(Serializer::new(RpcVersion::V07))
             .unwrap();
 
         let expected = serde_json::json!({
@@ -353,9 +353,7 @@ mod tests {
         let output = get_block_with_receipts(context.clone(), input)
             .await
             .unwrap()
-            .serialize(Serializer {
-                version: RpcVersion::V07,
-            })
+            .serialize(Serializer::new(RpcVersion::V07))
             .unwrap();
 
         let expected = serde_json::json!({
@@ -557,4 +555,85 @@ mod tests {
         });
         assert_eq!(output, expected);
     }
+
+    #[rstest::rstest]
+    #[case::v07(RpcVersion::V07)]
+    #[case::v08(RpcVersion::V08)]
+    #[tokio::test]
+    async fn transaction_types(#[case] version: RpcVersion) {
+        use pathfinder_common::macro_prelude::*;
+        use pathfinder_common::receipt::Receipt;
+        use pathfinder_common::transaction::{
+            DeclareTransactionV2,
+            DeployAccountTransactionV1,
+            InvokeTransactionV1,
+            Transaction,
+            TransactionVariant,
+        };
+        use pathfinder_common::{BlockHeader, TransactionIndex};
+
+        use crate::method::get_block_with_tx_hashes::tests::context_with_block;
+
+        let transactions = [
+            TransactionVariant::InvokeV1(InvokeTransactionV1 {
+                sender_address: contract_address!("0x1"),
+                ..Default::default()
+            }),
+            TransactionVariant::DeclareV2(DeclareTransactionV2 {
+                class_hash: class_hash!("0x2"),
+                ..Default::default()
+            }),
+            TransactionVariant::DeployAccountV1(DeployAccountTransactionV1 {
+                contract_address: contract_address!("0x3"),
+                ..Default::default()
+            }),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, variant)| {
+            let hash = pathfinder_common::TransactionHash(pathfinder_crypto::Felt::from_u64(
+                0x100 + i as u64,
+            ));
+            let receipt = Receipt {
+                transaction_hash: hash,
+                transaction_index: TransactionIndex::new_or_panic(i as u64),
+                ..Default::default()
+            };
+            (Transaction { hash, variant }, receipt)
+        })
+        .collect::<Vec<_>>();
+
+        let header = BlockHeader::builder().finalize_with_hash(block_hash!("0xabcd"));
+        let context = context_with_block(&header, &transactions);
+        let input = Input {
+            block_id: BlockId::Latest,
+        };
+
+        let output = get_block_with_receipts(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(version))
+            .unwrap();
+
+        let output = output["transactions"].as_array().unwrap();
+        let types = output
+            .iter()
+            .map(|item| {
+                assert_eq!(item["transaction"]["type"], item["receipt"]["type"]);
+                item["receipt"]["type"].as_str().unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(types, ["INVOKE", "DECLARE", "DEPLOY_ACCOUNT"]);
+
+        let hashes = output
+            .iter()
+            .map(|item| item["receipt"]["transaction_hash"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, ["0x100", "0x101", "0x102"]);
+
+        assert_eq!(output[0]["transaction"]["sender_address"], "0x1");
+        assert_eq!(output[1]["transaction"]["class_hash"], "0x2");
+        // Deploy account receipts include the address of the deployed account.
+        assert_eq!(output[2]["receipt"]["contract_address"], "0x3");
+    }
 }
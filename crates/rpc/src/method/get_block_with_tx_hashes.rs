@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
 use anyhow::Context;
+use pathfinder_common::transaction::Transaction;
 use pathfinder_common::{BlockHeader, BlockId, TransactionHash};
 
 use crate::context::RpcContext;
+use crate::dto::serialize::NumberFormat;
 
 crate::error::generate_rpc_error_subset!(Error: BlockNotFound);
 
@@ -11,6 +13,26 @@ crate::error::generate_rpc_error_subset!(Error: BlockNotFound);
 #[serde(deny_unknown_fields)]
 pub struct Input {
     pub block_id: BlockId,
+    /// Non-standard flag which replaces the transaction hashes with the full
+    /// transactions, as returned by `starknet_getBlockWithTxs`.
+    #[serde(default)]
+    pub include_full_transactions: bool,
+    /// Non-standard format of the felts in the response, hex strings unless
+    /// `decimal` is requested.
+    #[serde(default)]
+    pub response_format: NumberFormat,
+    /// Non-standard flag which adds the `block_body_commitment` to the header
+    /// of non-pending blocks.
+    #[serde(default)]
+    pub include_block_body_commitment: bool,
+    /// Non-standard flag which adds the `transaction_commitment` to the header
+    /// of non-pending blocks.
+    #[serde(default)]
+    pub include_transaction_commitment: bool,
+    /// Non-standard flag which adds the `event_commitment` to the header of
+    /// non-pending blocks.
+    #[serde(default)]
+    pub include_event_commitment: bool,
 }
 
 impl crate::dto::DeserializeForVersion for Input {
@@ -18,6 +40,21 @@ impl crate::dto::DeserializeForVersion for Input {
         value.deserialize_map(|value| {
             Ok(Self {
                 block_id: value.deserialize("block_id")?,
+                include_full_transactions: value
+                    .deserialize_optional_serde("include_full_transactions")?
+                    .unwrap_or_default(),
+                response_format: value
+                    .deserialize_optional_serde("response_format")?
+                    .unwrap_or_default(),
+                include_block_body_commitment: value
+                    .deserialize_optional_serde("include_block_body_commitment")?
+                    .unwrap_or_default(),
+                include_transaction_commitment: value
+                    .deserialize_optional_serde("include_transaction_commitment")?
+                    .unwrap_or_default(),
+                include_event_commitment: value
+                    .deserialize_optional_serde("include_event_commitment")?
+                    .unwrap_or_default(),
             })
         })
     }
@@ -27,20 +64,41 @@ impl crate::dto::DeserializeForVersion for Input {
 pub enum Output {
     Pending {
         header: Arc<starknet_gateway_types::reply::PendingBlock>,
-        transactions: Vec<TransactionHash>,
+        transactions: Transactions,
+        format: NumberFormat,
     },
     Full {
         header: Box<BlockHeader>,
-        transactions: Vec<TransactionHash>,
+        transactions: Transactions,
         l1_accepted: bool,
+        format: NumberFormat,
+        include_block_body_commitment: bool,
+        include_transaction_commitment: bool,
+        include_event_commitment: bool,
     },
 }
 
+#[derive(Debug)]
+pub enum Transactions {
+    Hashes(Vec<TransactionHash>),
+    Full(Vec<Transaction>),
+}
+
+impl Transactions {
+    fn len(&self) -> usize {
+        match self {
+            Transactions::Hashes(hashes) => hashes.len(),
+            Transactions::Full(transactions) => transactions.len(),
+        }
+    }
+}
+
 /// Get block information with transaction hashes given the block id
 pub async fn get_block_with_tx_hashes(context: RpcContext, input: Input) -> Result<Output, Error> {
     let span = tracing::Span::current();
+    let large_block_threshold = context.config.large_block_threshold;
 
-    tokio::task::spawn_blocking(move || {
+    let output = tokio::task::spawn_blocking(move || {
         let _g = span.enter();
         let mut connection = context
             .storage
@@ -58,36 +116,74 @@ pub async fn get_block_with_tx_hashes(context: RpcContext, input: Input) -> Resu
                     .get(&transaction)
                     .context("Querying pending data")?;
 
-                let transactions = pending.block.transactions.iter().map(|t| t.hash).collect();
+                let transactions = if input.include_full_transactions {
+                    Transactions::Full(pending.block.transactions.clone())
+                } else {
+                    Transactions::Hashes(
+                        pending.block.transactions.iter().map(|t| t.hash).collect(),
+                    )
+                };
 
                 return Ok(Output::Pending {
                     header: pending.block,
                     transactions,
+                    format: input.response_format,
                 });
             }
             other => other.try_into().expect("Only pending cast should fail"),
         };
 
-        let header = transaction
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&transaction, block_id)
             .context("Reading block from database")?
             .ok_or(Error::BlockNotFound)?;
 
         let l1_accepted = transaction.block_is_l1_accepted(header.number.into())?;
 
-        let transactions = transaction
-            .transaction_hashes_for_block(header.number.into())
-            .context("Reading transaction hashes")?
-            .context("Transaction hashes missing")?;
+        let transactions = if input.include_full_transactions {
+            let transactions = transaction
+                .transactions_for_block(header.number.into())
+                .context("Reading transactions from database")?
+                .context("Transaction data missing")?;
+            Transactions::Full(transactions)
+        } else {
+            let transactions = transaction
+                .transaction_hashes_for_block(header.number.into())
+                .context("Reading transaction hashes")?
+                .context("Transaction hashes missing")?;
+            Transactions::Hashes(transactions)
+        };
 
         Ok(Output::Full {
             header: Box::new(header),
             transactions,
             l1_accepted,
+            format: input.response_format,
+            include_block_body_commitment: input.include_block_body_commitment,
+            include_transaction_commitment: input.include_transaction_commitment,
+            include_event_commitment: input.include_event_commitment,
         })
     })
     .await
-    .context("Joining blocking task")?
+    .context("Joining blocking task")??;
+
+    if let Output::Full {
+        header,
+        transactions,
+        ..
+    } = &output
+    {
+        if transactions.len() > large_block_threshold {
+            tracing::warn!(
+                block_number = %header.number,
+                transaction_count = transactions.len(),
+                "Requested block contains a large number of transactions"
+            );
+        }
+    }
+
+    Ok(output)
 }
 
 impl crate::dto::serialize::SerializeForVersion for Output {
@@ -99,28 +195,44 @@ impl crate::dto::serialize::SerializeForVersion for Output {
             Output::Pending {
                 header,
                 transactions,
+                format,
             } => {
-                let mut serializer = serializer.serialize_struct()?;
+                let mut serializer = serializer.with_format(*format).serialize_struct()?;
                 serializer.flatten(&crate::dto::PendingBlockHeader(header))?;
-                serializer.serialize_iter(
-                    "transactions",
-                    transactions.len(),
-                    &mut transactions.iter().map(crate::dto::TxnHash),
-                )?;
+                serializer.serialize_field("transactions", transactions)?;
                 serializer.end()
             }
             Output::Full {
                 header,
                 transactions,
                 l1_accepted,
+                format,
+                include_block_body_commitment,
+                include_transaction_commitment,
+                include_event_commitment,
             } => {
-                let mut serializer = serializer.serialize_struct()?;
+                let mut serializer = serializer.with_format(*format).serialize_struct()?;
                 serializer.flatten(&crate::dto::BlockHeader(header))?;
-                serializer.serialize_iter(
-                    "transactions",
-                    transactions.len(),
-                    &mut transactions.iter().map(crate::dto::TxnHash),
-                )?;
+                if *include_transaction_commitment {
+                    serializer.serialize_field(
+                        "transaction_commitment",
+                        &crate::dto::Felt(&header.transaction_commitment.0),
+                    )?;
+                }
+                if *include_event_commitment {
+                    serializer.serialize_field(
+                        "event_commitment",
+                        &crate::dto::Felt(&header.event_commitment.0),
+                    )?;
+                }
+                if *include_block_body_commitment {
+                    // Zero for all blocks until a Starknet version commits to the block body.
+                    serializer.serialize_field(
+                        "block_body_commitment",
+                        &crate::dto::Felt(&header.block_body_commitment.0),
+                    )?;
+                }
+                serializer.serialize_field("transactions", transactions)?;
                 serializer.serialize_field(
                     "status",
                     &if *l1_accepted {
@@ -134,3 +246,539 @@ impl crate::dto::serialize::SerializeForVersion for Output {
         }
     }
 }
+
+impl crate::dto::serialize::SerializeForVersion for Transactions {
+    fn serialize(
+        &self,
+        serializer: crate::dto::serialize::Serializer,
+    ) -> Result<crate::dto::serialize::Ok, crate::dto::serialize::Error> {
+        match self {
+            Transactions::Hashes(hashes) => {
+                serializer.serialize_iter(hashes.len(), &mut hashes.iter().map(crate::dto::TxnHash))
+            }
+            Transactions::Full(transactions) => serializer.serialize_iter(
+                transactions.len(),
+                &mut transactions.iter().map(crate::dto::TransactionWithHash),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::{BlockNumber, BlockTimestamp, SequencerAddress};
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    #[test]
+    fn safe_block_id_parsing() {
+        let input = Input::deserialize(crate::dto::Value::new(
+            json!({"block_id": "safe"}),
+            RpcVersion::V07,
+        ))
+        .unwrap();
+
+        assert_eq!(input.block_id, BlockId::L1Accepted);
+        assert!(!input.include_full_transactions);
+    }
+
+    #[test]
+    fn finalized_block_id_parsing() {
+        let input = Input::deserialize(crate::dto::Value::new(
+            json!({"block_id": "finalized"}),
+            RpcVersion::V07,
+        ))
+        .unwrap();
+
+        assert_eq!(input.block_id, BlockId::L1Accepted);
+    }
+
+    #[test]
+    fn genesis_block_id_parsing() {
+        let input = Input::deserialize(crate::dto::Value::new(
+            json!({"block_id": "genesis"}),
+            RpcVersion::PathfinderV01,
+        ))
+        .unwrap();
+
+        assert_eq!(input.block_id, BlockId::Number(BlockNumber::GENESIS));
+    }
+
+    #[rstest::rstest]
+    #[case::v06(RpcVersion::V06)]
+    #[case::v07(RpcVersion::V07)]
+    #[case::v08(RpcVersion::V08)]
+    fn genesis_block_id_is_not_part_of_the_spec(#[case] version: RpcVersion) {
+        Input::deserialize(crate::dto::Value::new(
+            json!({"block_id": "genesis"}),
+            version,
+        ))
+        .unwrap_err();
+    }
+
+    #[test]
+    fn include_full_transactions_parsing() {
+        let input = Input::deserialize(crate::dto::Value::new(
+            json!({"block_id": "latest", "include_full_transactions": true}),
+            RpcVersion::V07,
+        ))
+        .unwrap();
+
+        assert_eq!(input.block_id, BlockId::Latest);
+        assert!(input.include_full_transactions);
+    }
+
+    #[rstest::rstest]
+    #[case::absent(json!({"block_id": "latest"}), NumberFormat::Hex)]
+    #[case::hex(json!({"block_id": "latest", "response_format": "hex"}), NumberFormat::Hex)]
+    #[case::decimal(
+        json!({"block_id": "latest", "response_format": "decimal"}),
+        NumberFormat::Decimal
+    )]
+    fn response_format_parsing(#[case] input: serde_json::Value, #[case] expected: NumberFormat) {
+        let input = Input::deserialize(crate::dto::Value::new(input, RpcVersion::V07)).unwrap();
+
+        assert_eq!(input.response_format, expected);
+    }
+
+    #[test]
+    fn invalid_response_format_parsing() {
+        Input::deserialize(crate::dto::Value::new(
+            json!({"block_id": "latest", "response_format": "octal"}),
+            RpcVersion::V07,
+        ))
+        .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn response_format() {
+        use crate::dto::serialize::{SerializeForVersion, Serializer};
+
+        let context = RpcContext::for_tests();
+        let get_block = |response_format| {
+            let input = Input {
+                block_id: BlockId::Latest,
+                include_full_transactions: false,
+                response_format,
+                include_block_body_commitment: false,
+                include_transaction_commitment: false,
+                include_event_commitment: false,
+            };
+            let context = context.clone();
+            async move {
+                get_block_with_tx_hashes(context, input)
+                    .await
+                    .unwrap()
+                    .serialize(Serializer::new(RpcVersion::V07))
+                    .unwrap()
+            }
+        };
+
+        let hex = get_block(NumberFormat::Hex).await;
+        let decimal = get_block(NumberFormat::Decimal).await;
+
+        let latest = block_hash_bytes!(b"latest");
+        assert_eq!(hex["block_hash"], json!(latest.0.to_hex_str()));
+        assert_eq!(
+            decimal["block_hash"],
+            json!(primitive_types::U256::from_big_endian(latest.0.as_be_bytes()).to_string())
+        );
+        // Block numbers are integers in both formats.
+        assert_eq!(hex["block_number"], json!(2));
+        assert_eq!(decimal["block_number"], json!(2));
+
+        let transactions = hex["transactions"].as_array().unwrap();
+        assert!(!transactions.is_empty());
+        for (hex, decimal) in transactions
+            .iter()
+            .zip(decimal["transactions"].as_array().unwrap())
+        {
+            let hex = hex.as_str().unwrap();
+            let expected = primitive_types::U256::from_str_radix(&hex[2..], 16).unwrap();
+            assert_eq!(decimal, &json!(expected.to_string()));
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::i64_max(i64::MAX as u64)]
+    #[case::i64_max_plus_one(i64::MAX as u64 + 1)]
+    #[case::u64_max(u64::MAX)]
+    #[tokio::test]
+    async fn block_number_out_of_range(#[case] block_number: u64) {
+        let input = Input::deserialize(crate::dto::Value::new(
+            json!({"block_id": {"block_number": block_number}}),
+            RpcVersion::V07,
+        ))
+        .unwrap();
+        assert_eq!(input.block_id, BlockId::Number(BlockNumber::MAX));
+
+        let context = RpcContext::for_tests();
+        let error = get_block_with_tx_hashes(context, input).await.unwrap_err();
+
+        assert_matches::assert_matches!(error, Error::BlockNotFound);
+    }
+
+    #[tokio::test]
+    async fn safe() {
+        // Only the genesis block is marked as L1 accepted in the test storage.
+        let context = RpcContext::for_tests();
+        let input = Input {
+            block_id: BlockId::L1Accepted,
+            include_full_transactions: false,
+            response_format: Default::default(),
+            include_block_body_commitment: false,
+            include_transaction_commitment: false,
+            include_event_commitment: false,
+        };
+
+        let result = get_block_with_tx_hashes(context, input).await.unwrap();
+
+        assert_matches::assert_matches!(result, Output::Full { header, l1_accepted: true, .. } => {
+            assert_eq!(header.number, BlockNumber::GENESIS);
+            assert_eq!(header.hash, block_hash_bytes!(b"genesis"));
+        });
+    }
+
+    /// All the ways of referring to the genesis block must resolve to the same
+    /// block.
+    #[tokio::test]
+    async fn genesis_block_ids_are_equivalent() {
+        use crate::dto::serialize::{SerializeForVersion, Serializer};
+
+        let context = RpcContext::for_tests();
+        let get_block = |block_id: serde_json::Value, version: RpcVersion| {
+            let input = Input::deserialize(crate::dto::Value::new(
+                json!({ "block_id": block_id }),
+                version,
+            ))
+            .unwrap();
+            let context = context.clone();
+            async move {
+                get_block_with_tx_hashes(context, input)
+                    .await
+                    .unwrap()
+                    .serialize(Serializer::new(RpcVersion::V07))
+                    .unwrap()
+            }
+        };
+
+        let by_number = get_block(json!({ "block_number": 0 }), RpcVersion::V07).await;
+        // The tag is only accepted by the pathfinder API.
+        let by_tag = get_block(json!("genesis"), RpcVersion::PathfinderV01).await;
+        let by_known_hash = get_block(
+            json!({ "block_hash": block_hash_bytes!(b"genesis").0.to_hex_str() }),
+            RpcVersion::V07,
+        )
+        .await;
+        let by_fetched_hash = get_block(
+            json!({ "block_hash": by_number["block_hash"] }),
+            RpcVersion::V07,
+        )
+        .await;
+
+        assert_eq!(by_number["block_number"], json!(0));
+        assert_eq!(by_tag, by_number);
+        assert_eq!(by_known_hash, by_number);
+        assert_eq!(by_fetched_hash, by_number);
+    }
+
+    #[tokio::test]
+    async fn gap_in_block_numbers() {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        tx.purge_block(BlockNumber::new_or_panic(1)).unwrap();
+        tx.commit().unwrap();
+
+        let input = |number| Input {
+            block_id: BlockId::Number(BlockNumber::new_or_panic(number)),
+            include_full_transactions: false,
+            response_format: Default::default(),
+            include_block_body_commitment: false,
+            include_transaction_commitment: false,
+            include_event_commitment: false,
+        };
+
+        let error = get_block_with_tx_hashes(context.clone(), input(1))
+            .await
+            .unwrap_err();
+        assert_matches::assert_matches!(error, Error::BlockNotFound);
+
+        for (number, hash) in [
+            (0, block_hash_bytes!(b"genesis")),
+            (2, block_hash_bytes!(b"latest")),
+        ] {
+            let result = get_block_with_tx_hashes(context.clone(), input(number))
+                .await
+                .unwrap();
+            assert_matches::assert_matches!(result, Output::Full { header, .. } => {
+                assert_eq!(header.number, BlockNumber::new_or_panic(number));
+                assert_eq!(header.hash, hash);
+            });
+        }
+    }
+
+    /// Creates a context whose storage only contains a block with `header` and
+    /// `transactions`.
+    pub(crate) fn context_with_block(
+        header: &BlockHeader,
+        transactions: &[(Transaction, pathfinder_common::receipt::Receipt)],
+    ) -> RpcContext {
+        let storage = pathfinder_storage::StorageBuilder::in_memory().unwrap();
+        let mut db = storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        tx.insert_block_header(header).unwrap();
+        let events = vec![vec![]; transactions.len()];
+        tx.insert_transaction_data(header.number, transactions, Some(&events))
+            .unwrap();
+        tx.commit().unwrap();
+        drop(db);
+
+        RpcContext::for_tests().with_storage(storage)
+    }
+
+    /// Serializes the only block of a storage containing `header`, with all
+    /// non-standard header fields requested.
+    async fn serialized_block(header: BlockHeader, version: RpcVersion) -> serde_json::Value {
+        use crate::dto::serialize::{SerializeForVersion, Serializer};
+
+        let context = context_with_block(&header, &[]);
+        let input = Input {
+            block_id: BlockId::Latest,
+            include_full_transactions: false,
+            response_format: Default::default(),
+            include_block_body_commitment: true,
+            include_transaction_commitment: true,
+            include_event_commitment: true,
+        };
+
+        get_block_with_tx_hashes(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(version))
+            .unwrap()
+    }
+
+    #[rstest::rstest]
+    #[case::transaction_commitment(
+        BlockHeader::builder().transaction_commitment(transaction_commitment!("0x1234abcd")),
+        "transaction_commitment",
+        json!("0x1234abcd")
+    )]
+    #[case::event_commitment(
+        BlockHeader::builder().event_commitment(event_commitment!("0x5678ef")),
+        "event_commitment",
+        json!("0x5678ef")
+    )]
+    #[case::block_body_commitment(
+        BlockHeader::builder().block_body_commitment(block_body_commitment!("0x9abc")),
+        "block_body_commitment",
+        json!("0x9abc")
+    )]
+    // Blocks from before the field was added have a zero commitment.
+    #[case::unset_block_body_commitment(
+        BlockHeader::builder(),
+        "block_body_commitment",
+        json!("0x0")
+    )]
+    // Timestamps are stored as `i64`, so larger values cannot be constructed in the
+    // first place.
+    #[case::zero_timestamp(
+        BlockHeader::builder().timestamp(BlockTimestamp::new_or_panic(0)),
+        "timestamp",
+        json!(0)
+    )]
+    #[case::one_timestamp(
+        BlockHeader::builder().timestamp(BlockTimestamp::new_or_panic(1)),
+        "timestamp",
+        json!(1)
+    )]
+    #[case::max_timestamp(
+        BlockHeader::builder().timestamp(BlockTimestamp::new_or_panic(i64::MAX as u64)),
+        "timestamp",
+        json!(i64::MAX as u64)
+    )]
+    // Blocks from before sequencer addresses were assigned have a zero sequencer
+    // address, which must still be part of the response.
+    #[case::zero_sequencer_address(
+        BlockHeader::builder().sequencer_address(SequencerAddress::ZERO),
+        "sequencer_address",
+        json!("0x0")
+    )]
+    #[tokio::test]
+    async fn header_field(
+        #[case] header: pathfinder_common::BlockHeaderBuilder,
+        #[case] field: &str,
+        #[case] expected: serde_json::Value,
+        #[values(RpcVersion::V07, RpcVersion::V08)] version: RpcVersion,
+    ) {
+        let header = header.finalize_with_hash(block_hash!("0xabcd"));
+
+        let block = serialized_block(header, version).await;
+
+        assert_eq!(block[field], expected);
+    }
+
+    #[tokio::test]
+    async fn non_standard_header_fields_are_opt_in() {
+        use crate::dto::serialize::{SerializeForVersion, Serializer};
+
+        let header = BlockHeader::builder()
+            .transaction_commitment(transaction_commitment!("0x1234abcd"))
+            .event_commitment(event_commitment!("0x5678ef"))
+            .block_body_commitment(block_body_commitment!("0x9abc"))
+            .finalize_with_hash(block_hash!("0xabcd"));
+        let input = Input {
+            block_id: BlockId::Latest,
+            include_full_transactions: false,
+            response_format: Default::default(),
+            include_block_body_commitment: false,
+            include_transaction_commitment: false,
+            include_event_commitment: false,
+        };
+
+        let block = get_block_with_tx_hashes(context_with_block(&header, &[]), input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::V07))
+            .unwrap();
+
+        for field in [
+            "transaction_commitment",
+            "event_commitment",
+            "block_body_commitment",
+        ] {
+            assert_eq!(block.get(field), None, "{field}");
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::latest(BlockId::Latest)]
+    #[case::pending(BlockId::Pending)]
+    #[tokio::test]
+    async fn include_full_transactions(#[case] block_id: BlockId) {
+        use crate::dto::serialize::{SerializeForVersion, Serializer};
+
+        let context = RpcContext::for_tests_with_pending().await;
+
+        let hashes = get_block_with_tx_hashes(
+            context.clone(),
+            Input {
+                block_id,
+                include_full_transactions: false,
+                response_format: Default::default(),
+                include_block_body_commitment: false,
+                include_transaction_commitment: false,
+                include_event_commitment: false,
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(Serializer::new(RpcVersion::V07))
+        .unwrap();
+        let full = get_block_with_tx_hashes(
+            context.clone(),
+            Input {
+                block_id,
+                include_full_transactions: true,
+                response_format: Default::default(),
+                include_block_body_commitment: false,
+                include_transaction_commitment: false,
+                include_event_commitment: false,
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(Serializer::new(RpcVersion::V07))
+        .unwrap();
+        let expected = crate::method::get_block_with_txs(
+            context,
+            crate::method::get_block_with_txs::Input { block_id },
+        )
+        .await
+        .unwrap()
+        .serialize(Serializer::new(RpcVersion::V07))
+        .unwrap();
+
+        assert_eq!(full, expected);
+
+        let hashes = hashes["transactions"].as_array().unwrap();
+        assert!(!hashes.is_empty());
+        assert!(hashes.iter().all(|hash| hash.is_string()));
+
+        let transaction_hashes = full["transactions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tx| tx["transaction_hash"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(&transaction_hashes, hashes);
+    }
+
+    #[derive(Clone, Default)]
+    struct Logs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl tracing_subscriber::fmt::MakeWriter<'_> for Logs {
+        type Writer = Self;
+
+        fn make_writer(&self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::below_threshold(5, false)]
+    #[case::above_threshold(4, true)]
+    #[tokio::test]
+    async fn large_block_warning(#[case] threshold: usize, #[case] expect_warning: bool) {
+        let logs = Logs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // The latest block in the test storage contains 5 transactions.
+        let mut context = RpcContext::for_tests();
+        context.config.large_block_threshold = threshold;
+        let input = Input {
+            block_id: BlockId::Latest,
+            include_full_transactions: false,
+            response_format: Default::default(),
+            include_block_body_commitment: false,
+            include_transaction_commitment: false,
+            include_event_commitment: false,
+        };
+
+        get_block_with_tx_hashes(context, input).await.unwrap();
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let warning = logs
+            .lines()
+            .find(|line| line.contains("Requested block contains a large number of transactions"));
+        match warning {
+            Some(warning) => {
+                assert!(expect_warning, "Unexpected warning: {warning}");
+                assert!(warning.contains("WARN"), "{warning}");
+                assert!(warning.contains("block_number=2"), "{warning}");
+                assert!(warning.contains("transaction_count=5"), "{warning}");
+            }
+            None => assert!(!expect_warning, "Missing warning in logs: {logs}"),
+        }
+    }
+}
@@ -75,8 +75,9 @@ pub async fn get_block_with_txs(context: RpcContext, input: Input) -> Result<Out
             other => other.try_into().expect("Only pending cast should fail"),
         };
 
-        let header = transaction
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&transaction, block_id)
             .context("Reading block from database")?
             .ok_or(Error::BlockNotFound)?;
 
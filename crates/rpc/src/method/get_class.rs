@@ -1,5 +1,8 @@
 use anyhow::Context;
 use pathfinder_common::{BlockId, ClassHash};
+use pathfinder_crypto::Felt;
+use pathfinder_executor::IntoFelt;
+use starknet_gateway_types::class_hash::{compute_class_hash, ComputedClassHash};
 
 use crate::context::RpcContext;
 use crate::dto;
@@ -12,6 +15,12 @@ crate::error::generate_rpc_error_subset!(Error: BlockNotFound, ClassHashNotFound
 pub struct Input {
     block_id: pathfinder_common::BlockId,
     class_hash: pathfinder_common::ClassHash,
+    /// Non-standard flag which, when unset, replaces the `hints` of a Cairo 0
+    /// program with an empty object to reduce the response size.
+    include_hints: bool,
+    /// Non-standard flag which adds the constants defined by a Cairo 0 program
+    /// to the response.
+    include_constants: bool,
 }
 
 impl crate::dto::DeserializeForVersion for Input {
@@ -20,6 +29,12 @@ impl crate::dto::DeserializeForVersion for Input {
             Ok(Self {
                 block_id: value.deserialize("block_id")?,
                 class_hash: ClassHash(value.deserialize("class_hash")?),
+                include_hints: value
+                    .deserialize_optional_serde("include_hints")?
+                    .unwrap_or(true),
+                include_constants: value
+                    .deserialize_optional_serde("include_constants")?
+                    .unwrap_or_default(),
             })
         })
     }
@@ -27,23 +42,39 @@ impl crate::dto::DeserializeForVersion for Input {
 
 #[derive(Debug)]
 pub enum Output {
-    DeprecatedClass(CairoContractClass),
+    DeprecatedClass {
+        class: CairoContractClass,
+        /// Non-standard extension listing the constants defined by the
+        /// program, by their fully qualified name. Only set if
+        /// [`Input::include_constants`] was requested.
+        constants: Option<Vec<(String, Felt)>>,
+    },
     Class(SierraContractClass),
 }
 
-impl From<ContractClass> for Output {
-    fn from(value: ContractClass) -> Self {
-        match value {
-            ContractClass::Cairo(x) => Self::DeprecatedClass(x),
-            ContractClass::Sierra(x) => Self::Class(x),
-        }
-    }
-}
-
 /// Get a contract class.
+///
+/// Classes missing from the local database are retrieved from
+/// [RpcConfig::class_fallback_url](crate::context::RpcConfig::class_fallback_url)
+/// if it is configured.
+///
+/// Returns an internal error if
+/// [RpcConfig::validate_class_hash](crate::context::RpcConfig::validate_class_hash)
+/// is set and the stored definition does not hash to the requested class hash.
 pub async fn get_class(context: RpcContext, input: Input) -> Result<Output, Error> {
+    // No class can have a zero hash, so there is no point in querying storage or
+    // the fallback for it.
+    if input.class_hash == ClassHash::ZERO {
+        return Err(Error::ClassHashNotFound);
+    }
+
     let span = tracing::Span::current();
-    let jh = tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+    let fallback_url = context.config.class_fallback_url.clone();
+    let storage = context.storage.clone();
+    let class_hash = input.class_hash;
+    let include_hints = input.include_hints;
+    let include_constants = input.include_constants;
+    let jh = tokio::task::spawn_blocking(move || -> Result<Option<Output>, Error> {
         let _g = span.enter();
         let mut db = context
             .storage
@@ -51,50 +82,355 @@ pub async fn get_class(context: RpcContext, input: Input) -> Result<Output, Erro
             .context("Opening database connection")?;
         let tx = db.transaction().context("Creating database transaction")?;
 
-        let is_pending = if input.block_id.is_pending() {
+        let definition = ClassLookup::new(&context, &tx, input.block_id, input.class_hash)?
+            .ok_or(Error::BlockNotFound)?
+            .class_definition(&tx, input.class_hash)?;
+
+        let definition = match definition {
+            Some(definition) => definition,
+            None if context.config.class_fallback_url.is_some() => {
+                // Classes retrieved from the fallback are stored without a declaration
+                // block. Classes which are declared, but only after the requested block,
+                // must not be served.
+                match tx
+                    .class_definition_with_block_number(input.class_hash)
+                    .context("Fetching class definition")?
+                {
+                    Some((None, definition)) => definition,
+                    Some((Some(_), _)) => return Err(Error::ClassHashNotFound),
+                    None => return Ok(None),
+                }
+            }
+            None => return Err(Error::ClassHashNotFound),
+        };
+
+        // Corrupt storage can decompress to invalid UTF-8, which would otherwise
+        // only surface as an opaque JSON parsing error.
+        if let Err(error) = std::str::from_utf8(&definition) {
+            return Err(anyhow::anyhow!(
+                "Stored definition of class {} is not valid UTF-8, the first invalid byte is at \
+                 position {}",
+                input.class_hash,
+                error.valid_up_to()
+            )
+            .into());
+        }
+
+        if context.config.validate_class_hash {
+            let computed = compute_class_hash(&definition)
+                .context("Computing class hash")?
+                .hash();
+            if computed != input.class_hash {
+                return Err(anyhow::anyhow!(
+                    "Stored definition of class {} hashes to {computed}",
+                    input.class_hash
+                )
+                .into());
+            }
+        }
+
+        let class =
+            class_from_definition(&definition, input.include_hints, input.include_constants)?;
+
+        Ok(Some(class))
+    });
+
+    if let Some(class) = jh.await.context("Reading class from database")?? {
+        return Ok(class);
+    }
+
+    let Some(fallback_url) = fallback_url else {
+        return Err(Error::ClassHashNotFound);
+    };
+
+    match fetch_from_fallback(storage, &fallback_url, class_hash).await {
+        Ok(Some(definition)) => Ok(class_from_definition(
+            &definition,
+            include_hints,
+            include_constants,
+        )?),
+        Ok(None) => Err(Error::ClassHashNotFound),
+        Err(error) => {
+            tracing::warn!(
+                %class_hash,
+                error = %format!("{error:#}"),
+                "Fetching class from fallback failed"
+            );
+            Err(Error::ClassHashNotFound)
+        }
+    }
+}
+
+/// The block at which a class must have been declared for a request to see it.
+pub(crate) enum ClassLookup {
+    /// The class is declared in the pending block, so its declaration point is
+    /// not checked.
+    Pending,
+    /// The class must have been declared at or before this block.
+    At(pathfinder_storage::BlockId),
+}
+
+impl ClassLookup {
+    /// Returns [None] if the block does not exist.
+    pub(crate) fn new(
+        context: &RpcContext,
+        tx: &pathfinder_storage::Transaction<'_>,
+        block_id: BlockId,
+        class_hash: ClassHash,
+    ) -> anyhow::Result<Option<Self>> {
+        let is_pending = if block_id.is_pending() {
             context
                 .pending_data
-                .get(&tx)
+                .get(tx)
                 .context("Querying pending data")?
                 .state_update
-                .class_is_declared(input.class_hash)
+                .class_is_declared(class_hash)
         } else {
             false
         };
 
         // Map block id to the storage variant.
-        let block_id = match input.block_id {
+        let block_id = match block_id {
             BlockId::Pending => pathfinder_storage::BlockId::Latest,
             other => other.try_into().expect("Only pending cast should fail"),
         };
 
-        // Check that block exists
-        let block_exists = tx.block_exists(block_id)?;
-        if !block_exists {
-            return Err(Error::BlockNotFound);
+        if !tx.block_exists(block_id)? {
+            return Ok(None);
         }
 
-        // If the class is declared in the pending block, then we shouldn't check the
-        // class's declaration point.
-        let definition = if is_pending {
-            tx.class_definition(input.class_hash)
+        Ok(Some(if is_pending {
+            Self::Pending
         } else {
-            tx.class_definition_at(block_id, input.class_hash)
+            Self::At(block_id)
+        }))
+    }
+
+    pub(crate) fn class_definition(
+        &self,
+        tx: &pathfinder_storage::Transaction<'_>,
+        class_hash: ClassHash,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        match self {
+            Self::Pending => tx.class_definition(class_hash),
+            Self::At(block_id) => tx.class_definition_at(*block_id, class_hash),
         }
-        .context("Fetching class definition")?;
+        .context("Fetching class definition")
+    }
 
-        let Some(definition) = definition else {
-            return Err(Error::ClassHashNotFound);
-        };
+    pub(crate) fn class_metadata(
+        &self,
+        tx: &pathfinder_storage::Transaction<'_>,
+        class_hash: ClassHash,
+    ) -> anyhow::Result<Option<pathfinder_storage::ClassMetadata>> {
+        match self {
+            Self::Pending => tx.class_metadata(class_hash),
+            Self::At(block_id) => tx.class_metadata_at(*block_id, class_hash),
+        }
+        .context("Querying class metadata")
+    }
+}
 
-        let class = ContractClass::from_definition_bytes(&definition)
-            .context("Parsing class definition")?
-            .into();
+fn class_from_definition(
+    definition: &[u8],
+    include_hints: bool,
+    include_constants: bool,
+) -> anyhow::Result<Output> {
+    let class = if include_hints {
+        ContractClass::from_definition_bytes(definition)
+    } else {
+        ContractClass::from_definition_bytes(&without_hints(definition)?)
+    }
+    .context("Parsing class definition")?;
 
-        Ok(class)
-    });
+    let output = match class {
+        ContractClass::Cairo(class) => Output::DeprecatedClass {
+            class,
+            constants: include_constants
+                .then(|| program_constants(definition))
+                .transpose()?,
+        },
+        ContractClass::Sierra(class) => Output::Class(class),
+    };
+
+    Ok(output)
+}
+
+/// Reads the constants defined by a Cairo 0 program, which are stored as
+/// identifiers of type `const`, sorted by name.
+///
+/// Constants without a valid integer value are skipped, since they should not
+/// make the class itself unavailable.
+fn program_constants(definition: &[u8]) -> anyhow::Result<Vec<(String, Felt)>> {
+    #[derive(serde::Deserialize)]
+    struct Definition {
+        program: Program,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Program {
+        #[serde(default)]
+        identifiers: std::collections::BTreeMap<String, Identifier>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Identifier {
+        #[serde(rename = "type")]
+        kind: String,
+        // References have a string value, so this cannot be a number.
+        value: Option<serde_json::Value>,
+    }
+
+    let definition =
+        serde_json::from_slice::<Definition>(definition).context("Parsing class definition")?;
+
+    let constants = definition
+        .program
+        .identifiers
+        .into_iter()
+        .filter(|(_, identifier)| identifier.kind == "const")
+        .filter_map(|(name, identifier)| {
+            let value = identifier
+                .value
+                .as_ref()
+                .and_then(serde_json::Value::as_number)
+                .map(|value| felt_from_dec_str(&value.to_string()));
+            match value {
+                Some(Ok(value)) => Some((name, value)),
+                Some(Err(error)) => {
+                    tracing::debug!(%name, %error, "Skipping constant with invalid value");
+                    None
+                }
+                None => {
+                    tracing::debug!(%name, "Skipping constant without an integer value");
+                    None
+                }
+            }
+        })
+        .collect();
+
+    Ok(constants)
+}
+
+/// Negative values are taken modulo the field prime, the same way Cairo
+/// interprets them.
+fn felt_from_dec_str(value: &str) -> anyhow::Result<Felt> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+
+    let magnitude = starknet_types_core::felt::Felt::from_dec_str(digits)
+        .map_err(|_| anyhow::anyhow!("Invalid decimal integer {value}"))?
+        .into_felt();
 
-    jh.await.context("Reading class from database")?
+    Ok(if negative {
+        Felt::ZERO - magnitude
+    } else {
+        magnitude
+    })
+}
+
+/// Replaces the `hints` of a Cairo 0 program with an empty object. Sierra
+/// classes have no hints and are returned unchanged.
+fn without_hints(definition: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut json = serde_json::from_slice::<serde_json::Value>(definition)
+        .context("Parsing class definition")?;
+
+    if let Some(hints) = json
+        .get_mut("program")
+        .and_then(|program| program.get_mut("hints"))
+    {
+        *hints = serde_json::json!({});
+    }
+
+    serde_json::to_vec(&json).context("Serializing class definition")
+}
+
+/// Requests to the class fallback which take longer than this are abandoned.
+const FALLBACK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+/// Class definitions larger than this are not accepted from the fallback.
+const FALLBACK_MAX_DEFINITION_SIZE: usize = 50 * 1024 * 1024;
+
+/// Fetches a class definition from the fallback URL and stores it in the local
+/// database.
+///
+/// Returns [None] if the fallback does not have the class. Only Cairo 0
+/// classes are accepted, and only if the definition matches the class hash.
+async fn fetch_from_fallback(
+    storage: pathfinder_storage::Storage,
+    url: &reqwest::Url,
+    class_hash: ClassHash,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let mut url = url.clone();
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Fallback URL cannot be a base"))?
+        .pop_if_empty()
+        .push(&class_hash.0.to_hex_str());
+
+    let client = reqwest::Client::builder()
+        .timeout(FALLBACK_TIMEOUT)
+        .build()
+        .context("Creating HTTP client")?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .context("Requesting class definition")?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let response = response
+        .error_for_status()
+        .context("Requesting class definition")?;
+    let definition = read_body(response, FALLBACK_MAX_DEFINITION_SIZE)
+        .await
+        .context("Reading class definition")?;
+
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> anyhow::Result<Option<Vec<u8>>> {
+        let _g = span.enter();
+
+        match compute_class_hash(&definition).context("Computing class hash")? {
+            ComputedClassHash::Cairo(hash) if hash == class_hash => {}
+            ComputedClassHash::Cairo(hash) => {
+                anyhow::bail!("Class hash mismatch, fallback returned class {hash}")
+            }
+            ComputedClassHash::Sierra(_) => anyhow::bail!("Fallback returned a Sierra class"),
+        }
+
+        let mut db = storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+        tx.insert_cairo_class(class_hash, &definition)
+            .context("Inserting class definition")?;
+        tx.commit().context("Committing database transaction")?;
+
+        Ok(Some(definition))
+    })
+    .await
+    .context("Storing class definition")?
+}
+
+/// Reads the response body, failing as soon as it exceeds `max_size` bytes.
+async fn read_body(mut response: reqwest::Response, max_size: usize) -> anyhow::Result<Vec<u8>> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_size as u64)
+    {
+        anyhow::bail!("Response is larger than {max_size} bytes");
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max_size {
+            anyhow::bail!("Response is larger than {max_size} bytes");
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
 }
 
 impl SerializeForVersion for Output {
@@ -103,14 +439,33 @@ impl SerializeForVersion for Output {
         serializer: dto::serialize::Serializer,
     ) -> Result<dto::serialize::Ok, dto::serialize::Error> {
         match self {
-            Output::DeprecatedClass(cairo) => {
-                dto::DeprecatedContractClass(cairo).serialize(serializer)
+            Output::DeprecatedClass { class, constants } => {
+                let mut serializer = serializer.serialize_struct()?;
+                serializer.flatten(&dto::DeprecatedContractClass(class))?;
+                serializer.serialize_optional("constants", constants.as_deref().map(Constants))?;
+                serializer.end()
             }
             Output::Class(sierra) => dto::ContractClass(sierra).serialize(serializer),
         }
     }
 }
 
+struct Constants<'a>(&'a [(String, Felt)]);
+
+impl SerializeForVersion for Constants<'_> {
+    fn serialize(
+        &self,
+        serializer: dto::serialize::Serializer,
+    ) -> Result<dto::serialize::Ok, dto::serialize::Error> {
+        // Constant names are not known up front, so the object is built directly.
+        let mut constants = serde_json::Map::new();
+        for (name, value) in self.0 {
+            constants.insert(name.clone(), dto::Felt(value).serialize(serializer)?);
+        }
+        Ok(serde_json::Value::Object(constants))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -137,6 +492,8 @@ mod tests {
             let expected = Input {
                 block_id: block_hash!("0xabcde").into(),
                 class_hash: class_hash!("0x12345"),
+                include_hints: true,
+                include_constants: false,
             };
             assert_eq!(input, expected);
         }
@@ -152,11 +509,195 @@ mod tests {
             let expected = Input {
                 block_id: block_hash!("0xabcde").into(),
                 class_hash: class_hash!("0x12345"),
+                include_hints: true,
+                include_constants: false,
+            };
+            assert_eq!(input, expected);
+        }
+
+        #[test]
+        fn non_standard_flags() {
+            let named = json!({
+                "block_id": { "block_hash": "0xabcde" },
+                "class_hash": "0x12345",
+                "include_hints": false,
+                "include_constants": true
+            });
+
+            let input = Input::deserialize(crate::dto::Value::new(named, RpcVersion::V07)).unwrap();
+            let expected = Input {
+                block_id: block_hash!("0xabcde").into(),
+                class_hash: class_hash!("0x12345"),
+                include_hints: false,
+                include_constants: true,
             };
             assert_eq!(input, expected);
         }
     }
 
+    mod hints {
+        use std::io::Read;
+
+        use super::*;
+
+        /// Decodes the base64 encoded, gzip compressed program of a Cairo 0
+        /// class.
+        fn program(class: &serde_json::Value) -> serde_json::Value {
+            let compressed = base64::decode(class["program"].as_str().unwrap()).unwrap();
+            let mut program = Vec::new();
+            flate2::read::GzDecoder::new(compressed.as_slice())
+                .read_to_end(&mut program)
+                .unwrap();
+            serde_json::from_slice(&program).unwrap()
+        }
+
+        async fn get_class(class_hash: ClassHash, include_hints: bool) -> serde_json::Value {
+            super::get_class(
+                RpcContext::for_tests(),
+                Input {
+                    block_id: BlockId::Latest,
+                    class_hash,
+                    include_hints,
+                    include_constants: false,
+                },
+            )
+            .await
+            .unwrap()
+            .serialize(dto::serialize::Serializer::new(crate::RpcVersion::V07))
+            .unwrap()
+        }
+
+        #[tokio::test]
+        async fn included_by_default() {
+            let class = get_class(class_hash_bytes!(b"class 0 hash"), true).await;
+
+            let definition: serde_json::Value = serde_json::from_slice(
+                starknet_gateway_test_fixtures::class_definitions::CONTRACT_DEFINITION,
+            )
+            .unwrap();
+            let hints = &program(&class)["hints"];
+            assert!(!hints.as_object().unwrap().is_empty());
+            assert_eq!(hints, &definition["program"]["hints"]);
+        }
+
+        #[tokio::test]
+        async fn excluded() {
+            let class = get_class(class_hash_bytes!(b"class 0 hash"), false).await;
+
+            let program = program(&class);
+            assert_eq!(program["hints"], serde_json::json!({}));
+            assert!(program["data"]
+                .as_array()
+                .is_some_and(|data| !data.is_empty()));
+        }
+
+        #[tokio::test]
+        async fn sierra_class_is_unaffected() {
+            let class_hash = class_hash_bytes!(b"class 2 hash (sierra)");
+
+            let with_hints = get_class(class_hash, true).await;
+            let without_hints = get_class(class_hash, false).await;
+
+            assert_eq!(with_hints, without_hints);
+        }
+    }
+
+    mod constants {
+        use super::*;
+
+        async fn get_class(class_hash: ClassHash, include_constants: bool) -> serde_json::Value {
+            super::get_class(
+                RpcContext::for_tests(),
+                Input {
+                    block_id: BlockId::Latest,
+                    class_hash,
+                    include_hints: true,
+                    include_constants,
+                },
+            )
+            .await
+            .unwrap()
+            .serialize(dto::serialize::Serializer::new(crate::RpcVersion::V07))
+            .unwrap()
+        }
+
+        #[tokio::test]
+        async fn included_for_cairo_0_classes() {
+            let class = get_class(class_hash_bytes!(b"class 0 hash"), true).await;
+
+            let definition: serde_json::Value = serde_json::from_slice(
+                starknet_gateway_test_fixtures::class_definitions::CONTRACT_DEFINITION,
+            )
+            .unwrap();
+            let expected_count = definition["program"]["identifiers"]
+                .as_object()
+                .unwrap()
+                .values()
+                .filter(|identifier| identifier["type"] == "const")
+                .count();
+
+            let constants = class["constants"].as_object().unwrap();
+            assert_eq!(constants.len(), expected_count);
+            assert_eq!(
+                constants["starkware.starknet.common.storage.MAX_STORAGE_ITEM_SIZE"],
+                "0x100"
+            );
+            assert_eq!(
+                constants["starkware.starknet.common.syscalls.CALL_CONTRACT_SELECTOR"],
+                "0x43616c6c436f6e7472616374"
+            );
+            // Defined as `2**251 - 256`, which the compiler stores as a negative
+            // value because it is larger than half of the field prime.
+            assert_eq!(
+                constants["starkware.starknet.common.storage.ADDR_BOUND"],
+                "0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00"
+            );
+            // The standard fields are unaffected.
+            assert!(class["abi"].is_array());
+        }
+
+        #[tokio::test]
+        async fn not_included_by_default() {
+            let class = get_class(class_hash_bytes!(b"class 0 hash"), false).await;
+
+            assert!(class.get("constants").is_none());
+        }
+
+        #[tokio::test]
+        async fn not_included_for_sierra_classes() {
+            let class = get_class(class_hash_bytes!(b"class 2 hash (sierra)"), true).await;
+
+            assert!(class.get("constants").is_none());
+        }
+
+        #[test]
+        fn invalid_values_are_skipped() {
+            let definition = serde_json::json!({
+                "program": {
+                    "identifiers": {
+                        "valid": { "type": "const", "value": 1 },
+                        "fractional": { "type": "const", "value": 1.5 },
+                        "missing": { "type": "const" },
+                        "reference": { "type": "reference", "value": "x" },
+                    }
+                }
+            });
+
+            let constants = program_constants(definition.to_string().as_bytes()).unwrap();
+
+            assert_eq!(constants, vec![("valid".to_owned(), Felt::from_u64(1))]);
+        }
+
+        #[rstest::rstest]
+        #[case::zero("0", Felt::ZERO)]
+        #[case::positive("256", Felt::from_u64(256))]
+        #[case::negative("-1", Felt::ZERO - Felt::from_u64(1))]
+        #[test]
+        fn parsing_values(#[case] value: &str, #[case] expected: Felt) {
+            assert_eq!(felt_from_dec_str(value).unwrap(), expected);
+        }
+    }
+
     #[tokio::test]
     async fn pending() {
         let context = RpcContext::for_tests();
@@ -168,6 +709,8 @@ mod tests {
             Input {
                 block_id: BlockId::Pending,
                 class_hash: valid_v0,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -179,6 +722,8 @@ mod tests {
             Input {
                 block_id: BlockId::Pending,
                 class_hash: valid_v1,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -190,6 +735,8 @@ mod tests {
             Input {
                 block_id: BlockId::Pending,
                 class_hash: invalid,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -198,6 +745,186 @@ mod tests {
         assert_matches!(error, Error::ClassHashNotFound);
     }
 
+    #[tokio::test]
+    async fn zero_class_hash() {
+        let context = RpcContext::for_tests();
+
+        let error = super::get_class(
+            context,
+            Input {
+                block_id: BlockId::Latest,
+                class_hash: ClassHash::ZERO,
+                include_hints: true,
+                include_constants: false,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_matches!(error, Error::ClassHashNotFound);
+    }
+
+    /// The specification defines a Sierra class's ABI as a JSON encoded string,
+    /// whereas a deprecated Cairo class's ABI is a JSON array of entries.
+    #[rstest::rstest]
+    #[case::v06(crate::RpcVersion::V06)]
+    #[case::v07(crate::RpcVersion::V07)]
+    #[case::v08(crate::RpcVersion::V08)]
+    #[tokio::test]
+    async fn abi_format(#[case] version: crate::RpcVersion) {
+        let context = RpcContext::for_tests();
+
+        let sierra = super::get_class(
+            context.clone(),
+            Input {
+                block_id: BlockId::Latest,
+                class_hash: class_hash_bytes!(b"class 2 hash (sierra)"),
+                include_hints: true,
+                include_constants: false,
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(dto::serialize::Serializer::new(version))
+        .unwrap();
+        assert!(sierra["abi"].is_string(), "{}", sierra["abi"]);
+        let abi: serde_json::Value = serde_json::from_str(sierra["abi"].as_str().unwrap()).unwrap();
+        assert!(abi.is_array());
+
+        let cairo = super::get_class(
+            context,
+            Input {
+                block_id: BlockId::Latest,
+                class_hash: class_hash_bytes!(b"class 0 hash"),
+                include_hints: true,
+                include_constants: false,
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(dto::serialize::Serializer::new(version))
+        .unwrap();
+        assert!(cairo["abi"].is_array(), "{}", cairo["abi"]);
+    }
+
+    #[tokio::test]
+    async fn sierra_entry_points_include_function_idx() {
+        let context = RpcContext::for_tests();
+
+        let sierra = super::get_class(
+            context,
+            Input {
+                block_id: BlockId::Latest,
+                class_hash: class_hash_bytes!(b"class 2 hash (sierra)"),
+                include_hints: true,
+                include_constants: false,
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(dto::serialize::Serializer::new(crate::RpcVersion::V07))
+        .unwrap();
+
+        // Taken from the `sierra-0.11.json` class definition fixture.
+        let expected = serde_json::json!([
+            {
+                "function_idx": 0,
+                "selector": "0x22ff5f21f0b81b113e63f7db6da94fedef11b2119b4088b89664fb9a3cb658",
+            },
+            {
+                "function_idx": 1,
+                "selector": "0x1fc3f77ebc090777f567969ad9823cf6334ab888acb385ca72668ec5adbde80",
+            },
+            {
+                "function_idx": 2,
+                "selector": "0x3d778356014c91effae9863ee4a8c2663d8fa2e9f0c4145c1e01f5435ced0be",
+            },
+        ]);
+        assert_eq!(sierra["entry_points_by_type"]["EXTERNAL"], expected);
+        assert_eq!(
+            sierra["entry_points_by_type"]["CONSTRUCTOR"],
+            serde_json::json!([])
+        );
+        assert_eq!(
+            sierra["entry_points_by_type"]["L1_HANDLER"],
+            serde_json::json!([])
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::v1_0_0_rc0(starknet_gateway_test_fixtures::class_definitions::CAIRO_1_0_0_RC0_SIERRA)]
+    #[case::v1_1_0_rc0(starknet_gateway_test_fixtures::class_definitions::CAIRO_1_1_0_RC0_SIERRA)]
+    fn sierra_abi_includes_events(#[case] definition: &[u8]) {
+        let abi_events = |abi: &str| {
+            serde_json::from_str::<Vec<serde_json::Value>>(abi)
+                .unwrap()
+                .into_iter()
+                .filter(|entry| entry["type"] == "event")
+                .collect::<Vec<_>>()
+        };
+        let fixture: serde_json::Value = serde_json::from_slice(definition).unwrap();
+        let expected = abi_events(fixture["abi"].as_str().unwrap());
+        assert!(!expected.is_empty());
+
+        let output = class_from_definition(definition, true)
+            .unwrap()
+            .serialize(dto::serialize::Serializer::new(crate::RpcVersion::V07))
+            .unwrap();
+
+        assert_eq!(abi_events(output["abi"].as_str().unwrap()), expected);
+    }
+
+    #[rstest::rstest]
+    #[case::cairo_0_10(
+        starknet_gateway_test_fixtures::class_definitions::CAIRO_0_10_COMPILER_VERSION
+    )]
+    #[case::integration(starknet_gateway_test_fixtures::class_definitions::INTEGRATION_TEST)]
+    #[case::erc20(starknet_gateway_test_fixtures::class_definitions::ERC20_CONTRACT_DEFINITION)]
+    fn cairo_abi_entries_are_preserved(#[case] definition: &[u8]) {
+        let entries = |abi: &serde_json::Value| {
+            abi.as_array()
+                .unwrap()
+                .iter()
+                .map(|entry| (entry["type"].clone(), entry["name"].clone()))
+                .collect::<Vec<_>>()
+        };
+        let fixture: serde_json::Value = serde_json::from_slice(definition).unwrap();
+        let expected = entries(&fixture["abi"]);
+        assert!(!expected.is_empty());
+
+        let output = class_from_definition(definition, true)
+            .unwrap()
+            .serialize(dto::serialize::Serializer::new(crate::RpcVersion::V07))
+            .unwrap();
+
+        assert_eq!(entries(&output["abi"]), expected);
+    }
+
+    #[rstest::rstest]
+    #[case::missing(None)]
+    #[case::null(Some(serde_json::Value::Null))]
+    fn deprecated_class_without_abi(#[case] abi: Option<serde_json::Value>) {
+        let mut definition: serde_json::Value = serde_json::from_slice(
+            starknet_gateway_test_fixtures::class_definitions::CONTRACT_DEFINITION,
+        )
+        .unwrap();
+        let definition_obj = definition.as_object_mut().unwrap();
+        definition_obj.remove("abi");
+        if let Some(abi) = abi {
+            definition_obj.insert("abi".to_owned(), abi);
+        }
+        let definition = serde_json::to_vec(&definition).unwrap();
+
+        let class = ContractClass::from_definition_bytes(&definition).unwrap();
+        assert_matches!(&class, ContractClass::Cairo(cairo) if cairo.abi.is_none());
+
+        let output = class_from_definition(&definition, true)
+            .unwrap()
+            .serialize(dto::serialize::Serializer::new(crate::RpcVersion::V07))
+            .unwrap();
+        assert_eq!(output["abi"], serde_json::json!([]));
+    }
+
     #[tokio::test]
     async fn latest() {
         let context = RpcContext::for_tests();
@@ -209,6 +936,8 @@ mod tests {
             Input {
                 block_id: BlockId::Latest,
                 class_hash: valid_v0,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -221,6 +950,8 @@ mod tests {
             Input {
                 block_id: BlockId::Latest,
                 class_hash: valid_v1,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -232,6 +963,8 @@ mod tests {
             Input {
                 block_id: BlockId::Latest,
                 class_hash: invalid,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -246,6 +979,8 @@ mod tests {
             Input {
                 block_id: BlockId::Latest,
                 class_hash: undeclared,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -267,6 +1002,8 @@ mod tests {
             Input {
                 block_id: BlockId::Number(BlockNumber::new_or_panic(1)),
                 class_hash: valid_v0,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -280,6 +1017,8 @@ mod tests {
             Input {
                 block_id: BlockId::Number(BlockNumber::new_or_panic(2)),
                 class_hash: valid_v1,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -290,6 +1029,8 @@ mod tests {
             Input {
                 block_id: BlockId::Number(BlockNumber::GENESIS),
                 class_hash: valid_v1,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -302,6 +1043,8 @@ mod tests {
             Input {
                 block_id: BlockId::Number(BlockNumber::new_or_panic(2)),
                 class_hash: invalid,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -316,6 +1059,8 @@ mod tests {
             Input {
                 block_id: BlockId::Number(BlockNumber::new_or_panic(2)),
                 class_hash: undeclared,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -329,6 +1074,8 @@ mod tests {
             Input {
                 block_id: BlockId::Number(BlockNumber::MAX),
                 class_hash: valid,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -349,6 +1096,8 @@ mod tests {
             Input {
                 block_id: BlockId::Hash(block1_hash),
                 class_hash: valid_v0,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -363,6 +1112,8 @@ mod tests {
             Input {
                 block_id: BlockId::Hash(block2_hash),
                 class_hash: valid_v1,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -374,6 +1125,8 @@ mod tests {
             Input {
                 block_id: BlockId::Hash(block0_hash),
                 class_hash: valid_v1,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -387,6 +1140,8 @@ mod tests {
             Input {
                 block_id: BlockId::Hash(latest_hash),
                 class_hash: invalid,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -401,6 +1156,8 @@ mod tests {
             Input {
                 block_id: BlockId::Hash(latest_hash),
                 class_hash: undeclared,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
@@ -415,10 +1172,280 @@ mod tests {
             Input {
                 block_id: BlockId::Hash(invalid_block),
                 class_hash: valid,
+                include_hints: true,
+                include_constants: false,
             },
         )
         .await
         .unwrap_err();
         assert_matches!(error, Error::BlockNotFound);
     }
+
+    mod fallback {
+        use httpmock::prelude::*;
+        use starknet_gateway_test_fixtures::class_definitions::{
+            CONTRACT_DEFINITION,
+            DUMMY_ACCOUNT,
+            DUMMY_ACCOUNT_CLASS_HASH,
+        };
+
+        use super::*;
+
+        fn context_with_fallback(server: &MockServer) -> RpcContext {
+            let mut context = RpcContext::for_tests();
+            context.config.class_fallback_url = Some(server.base_url().parse().unwrap());
+            context
+        }
+
+        fn class_path(class_hash: ClassHash) -> String {
+            format!("/{}", class_hash.0.to_hex_str())
+        }
+
+        #[tokio::test]
+        async fn fetched_and_stored_locally() {
+            let server = MockServer::start_async().await;
+            let mock = server.mock(|when, then| {
+                when.method(GET).path(class_path(DUMMY_ACCOUNT_CLASS_HASH));
+                then.status(200).body(DUMMY_ACCOUNT);
+            });
+            let context = context_with_fallback(&server);
+
+            for _ in 0..2 {
+                let class = super::get_class(
+                    context.clone(),
+                    Input {
+                        block_id: BlockId::Latest,
+                        class_hash: DUMMY_ACCOUNT_CLASS_HASH,
+                        include_hints: true,
+                        include_constants: false,
+                    },
+                )
+                .await
+                .unwrap();
+                assert_matches!(class, Output::DeprecatedClass { .. });
+            }
+
+            // The second request is served from the local database.
+            mock.assert_hits(1);
+        }
+
+        #[tokio::test]
+        async fn not_found() {
+            let server = MockServer::start_async().await;
+            let mock = server.mock(|when, then| {
+                when.method(GET).path(class_path(DUMMY_ACCOUNT_CLASS_HASH));
+                then.status(404);
+            });
+            let context = context_with_fallback(&server);
+
+            let error = super::get_class(
+                context,
+                Input {
+                    block_id: BlockId::Latest,
+                    class_hash: DUMMY_ACCOUNT_CLASS_HASH,
+                    include_hints: true,
+                    include_constants: false,
+                },
+            )
+            .await
+            .unwrap_err();
+
+            assert_matches!(error, Error::ClassHashNotFound);
+            mock.assert_hits(1);
+        }
+
+        #[tokio::test]
+        async fn class_hash_mismatch_is_rejected() {
+            let server = MockServer::start_async().await;
+            let mock = server.mock(|when, then| {
+                when.method(GET).path(class_path(DUMMY_ACCOUNT_CLASS_HASH));
+                then.status(200).body(CONTRACT_DEFINITION);
+            });
+            let context = context_with_fallback(&server);
+
+            for _ in 0..2 {
+                let error = super::get_class(
+                    context.clone(),
+                    Input {
+                        block_id: BlockId::Latest,
+                        class_hash: DUMMY_ACCOUNT_CLASS_HASH,
+                        include_hints: true,
+                        include_constants: false,
+                    },
+                )
+                .await
+                .unwrap_err();
+                assert_matches!(error, Error::ClassHashNotFound);
+            }
+
+            // Nothing was stored, so the fallback is queried again.
+            mock.assert_hits(2);
+        }
+
+        #[tokio::test]
+        async fn not_used_for_classes_declared_after_the_block() {
+            let server = MockServer::start_async().await;
+            let mock = server.mock(|when, then| {
+                when.any_request();
+                then.status(200).body(CONTRACT_DEFINITION);
+            });
+            let context = context_with_fallback(&server);
+
+            // Declared in block 1.
+            let error = super::get_class(
+                context,
+                Input {
+                    block_id: BlockId::Number(pathfinder_common::BlockNumber::GENESIS),
+                    class_hash: class_hash_bytes!(b"class 1 hash"),
+                    include_hints: true,
+                    include_constants: false,
+                },
+            )
+            .await
+            .unwrap_err();
+
+            assert_matches!(error, Error::ClassHashNotFound);
+            mock.assert_hits(0);
+        }
+
+        #[tokio::test]
+        async fn body_size_is_limited() {
+            let server = MockServer::start_async().await;
+            server.mock(|when, then| {
+                when.method(GET);
+                then.status(200).body(DUMMY_ACCOUNT);
+            });
+            let response = || async { reqwest::get(server.base_url()).await.unwrap() };
+
+            let body = read_body(response().await, DUMMY_ACCOUNT.len())
+                .await
+                .unwrap();
+            assert_eq!(body, DUMMY_ACCOUNT);
+
+            read_body(response().await, DUMMY_ACCOUNT.len() - 1)
+                .await
+                .unwrap_err();
+        }
+    }
+
+    mod validate_class_hash {
+        use pathfinder_common::{BlockNumber, StateUpdate};
+        use starknet_gateway_test_fixtures::class_definitions::{
+            DUMMY_ACCOUNT,
+            DUMMY_ACCOUNT_CLASS_HASH,
+        };
+
+        use super::*;
+
+        fn context_with_validation() -> RpcContext {
+            let mut context = RpcContext::for_tests();
+            context.config.validate_class_hash = true;
+            context
+        }
+
+        #[tokio::test]
+        async fn matching_definition() {
+            let context = context_with_validation();
+            let mut db = context.storage.connection().unwrap();
+            let tx = db.transaction().unwrap();
+            tx.insert_cairo_class(DUMMY_ACCOUNT_CLASS_HASH, DUMMY_ACCOUNT)
+                .unwrap();
+            tx.insert_state_update(
+                BlockNumber::new_or_panic(2),
+                &StateUpdate::default().with_declared_cairo_class(DUMMY_ACCOUNT_CLASS_HASH),
+            )
+            .unwrap();
+            tx.commit().unwrap();
+
+            let class = super::get_class(
+                context,
+                Input {
+                    block_id: BlockId::Latest,
+                    class_hash: DUMMY_ACCOUNT_CLASS_HASH,
+                    include_hints: true,
+                    include_constants: false,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_matches!(class, Output::DeprecatedClass { .. });
+        }
+
+        #[tokio::test]
+        async fn corrupt_definition() {
+            // The test storage stores fixture definitions under made up hashes.
+            let input = || Input {
+                block_id: BlockId::Latest,
+                class_hash: class_hash_bytes!(b"class 0 hash"),
+                include_hints: true,
+                include_constants: false,
+            };
+
+            super::get_class(RpcContext::for_tests(), input())
+                .await
+                .unwrap();
+
+            let error = super::get_class(context_with_validation(), input())
+                .await
+                .unwrap_err();
+
+            assert_matches!(error, Error::Internal(_));
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_utf8_definition() {
+        use pathfinder_common::{BlockNumber, StateUpdate};
+
+        // Corrupts the first byte of the first ABI entry's name.
+        let mut definition =
+            starknet_gateway_test_fixtures::class_definitions::CONTRACT_DEFINITION.to_vec();
+        let abi = definition
+            .windows(b"\"abi\"".len())
+            .position(|window| window == b"\"abi\"")
+            .unwrap();
+        let name = abi
+            + definition[abi..]
+                .windows(b"\"name\": \"".len())
+                .position(|window| window == b"\"name\": \"")
+                .unwrap()
+            + b"\"name\": \"".len();
+        definition[name] = 0xff;
+
+        let context = RpcContext::for_tests();
+        let class_hash = class_hash!("0xbad");
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        tx.insert_cairo_class(class_hash, &definition).unwrap();
+        tx.insert_state_update(
+            BlockNumber::new_or_panic(2),
+            &StateUpdate::default().with_declared_cairo_class(class_hash),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let error = super::get_class(
+            context,
+            Input {
+                block_id: BlockId::Latest,
+                class_hash,
+                include_hints: true,
+                include_constants: false,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        let Error::Internal(error) = error else {
+            panic!("Expected an internal error, got {error:?}");
+        };
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Stored definition of class {class_hash} is not valid UTF-8, the first invalid \
+                 byte is at position {name}"
+            )
+        );
+    }
 }
@@ -114,6 +114,7 @@ pub async fn get_class_at(context: RpcContext, input: Input) -> Result<ContractC
 mod tests {
     use assert_matches::assert_matches;
     use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::ClassHash;
 
     use super::*;
 
@@ -197,6 +198,91 @@ mod tests {
         assert_matches!(error, Error::ContractNotFound);
     }
 
+    #[tokio::test]
+    async fn pending_declared_classes() {
+        use starknet_gateway_test_fixtures::class_definitions::{
+            CAIRO_0_11_SIERRA,
+            CONTRACT_DEFINITION,
+        };
+
+        let context = RpcContext::for_tests();
+
+        let cairo_hash = class_hash_bytes!(b"pending cairo class");
+        let sierra_hash = sierra_hash_bytes!(b"pending sierra class");
+        let cairo_contract = contract_address_bytes!(b"pending cairo contract");
+        let sierra_contract = contract_address_bytes!(b"pending sierra contract");
+
+        // Definitions of classes declared in pending are stored without a block.
+        let latest = {
+            let mut db = context.storage.connection().unwrap();
+            let tx = db.transaction().unwrap();
+            tx.insert_cairo_class(cairo_hash, CONTRACT_DEFINITION)
+                .unwrap();
+            tx.insert_sierra_class(
+                &sierra_hash,
+                CAIRO_0_11_SIERRA,
+                &casm_hash_bytes!(b"pending casm class"),
+                b"casm definition",
+            )
+            .unwrap();
+            let latest = tx
+                .block_header(pathfinder_storage::BlockId::Latest)
+                .unwrap()
+                .unwrap();
+            tx.commit().unwrap();
+            latest
+        };
+
+        let state_update = pathfinder_common::StateUpdate::default()
+            .with_declared_cairo_class(cairo_hash)
+            .with_declared_sierra_class(sierra_hash, casm_hash_bytes!(b"pending casm class"))
+            .with_deployed_contract(cairo_contract, cairo_hash)
+            .with_deployed_contract(sierra_contract, ClassHash(sierra_hash.0));
+        let pending_data = crate::pending::PendingData {
+            block: starknet_gateway_types::reply::PendingBlock {
+                parent_hash: latest.hash,
+                ..Default::default()
+            }
+            .into(),
+            state_update: state_update.into(),
+            number: latest.number + 1,
+        };
+        let (_tx, rx) = tokio::sync::watch::channel(pending_data);
+        let context = context.with_pending_data(rx);
+
+        for (contract_address, definition) in [
+            (cairo_contract, CONTRACT_DEFINITION),
+            (sierra_contract, CAIRO_0_11_SIERRA),
+        ] {
+            let class = super::get_class_at(
+                context.clone(),
+                Input {
+                    block_id: BlockId::Pending,
+                    contract_address,
+                },
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(
+                class,
+                ContractClass::from_definition_bytes(definition).unwrap()
+            );
+        }
+
+        // The contracts do not exist before the pending block.
+        let error = super::get_class_at(
+            context,
+            Input {
+                block_id: BlockId::Latest,
+                contract_address: cairo_contract,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_matches!(error, Error::ContractNotFound);
+    }
+
     #[tokio::test]
     async fn latest() {
         let context = RpcContext::for_tests();
@@ -135,6 +135,65 @@ mod tests {
         assert_matches::assert_matches!(result, Error::ClassHashNotFound(_));
     }
 
+    /// Classes declared by a `DECLARE` v3 transaction are stored with their
+    /// CASM, the same as for earlier declare versions.
+    #[tokio::test]
+    async fn test_get_casm_declared_by_v3_transaction() {
+        use pathfinder_common::receipt::Receipt;
+        use pathfinder_common::transaction::{
+            DeclareTransactionV3,
+            Transaction,
+            TransactionVariant,
+        };
+        use pathfinder_common::{block_hash, transaction_hash, BlockHeader, BlockNumber};
+
+        let rpc = setup().await.expect("RPC context");
+        let transaction_hash = transaction_hash!("0xdec1a3e");
+        {
+            let mut db = rpc.storage.connection().unwrap();
+            let tx = db.transaction().unwrap();
+
+            let header = BlockHeader::builder().finalize_with_hash(block_hash!("0x1"));
+            tx.insert_block_header(&header).unwrap();
+            let declare = Transaction {
+                hash: transaction_hash,
+                variant: TransactionVariant::DeclareV3(DeclareTransactionV3 {
+                    class_hash: input().class_hash,
+                    compiled_class_hash: casm_hash!(
+                        "0x0484c163658bcce5f9916f486171ac60143a92897533aa7ff7ac800b16c63311"
+                    ),
+                    ..Default::default()
+                }),
+            };
+            let receipt = Receipt {
+                transaction_hash,
+                ..Default::default()
+            };
+            tx.insert_transaction_data(BlockNumber::GENESIS, &[(declare, receipt)], None)
+                .unwrap();
+            tx.commit().unwrap();
+        }
+
+        let declared_class = {
+            let mut db = rpc.storage.connection().unwrap();
+            let tx = db.transaction().unwrap();
+            match tx.transaction(transaction_hash).unwrap().unwrap().variant {
+                TransactionVariant::DeclareV3(declare) => declare.class_hash,
+                other => panic!("Expected a DECLARE v3 transaction, got {other:?}"),
+            }
+        };
+
+        let result = get_compiled_casm(
+            rpc,
+            Input {
+                class_hash: declared_class,
+            },
+        )
+        .await
+        .expect("result");
+        assert_eq!(result.0, expected());
+    }
+
     async fn setup() -> anyhow::Result<RpcContext> {
         let dir = tempdir().expect("tempdir");
         let mut db_path = dir.path().to_path_buf();
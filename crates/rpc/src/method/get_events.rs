@@ -1,6 +1,11 @@
+use std::num::NonZeroUsize;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
+use cached::{Cached, SizedCache};
+use pathfinder_common::transaction::TransactionKind;
 use pathfinder_common::{
     BlockHash,
     BlockId,
@@ -10,17 +15,33 @@ use pathfinder_common::{
     EventKey,
     TransactionHash,
 };
-use pathfinder_storage::{EventFilterError, EVENT_KEY_FILTER_LIMIT};
+use pathfinder_storage::{
+    EventConstraints,
+    EventFilterError,
+    PageOfEvents,
+    Storage,
+    EVENT_KEY_FILTER_LIMIT,
+};
 use starknet_gateway_types::reply::PendingBlock;
+use tokio::sync::{broadcast, Semaphore};
 use tokio::task::JoinHandle;
 
 use crate::context::RpcContext;
 use crate::dto::serialize::{self, SerializeForVersion, Serializer};
 use crate::dto::{self};
+use crate::jsonrpc::{Notifications, Reorg};
 use crate::pending::PendingData;
 
 pub const EVENT_PAGE_SIZE_LIMIT: usize = 1024;
 
+/// Maximum number of pre-fetched pages held by the [EventPageCache].
+const EVENT_PAGE_CACHE_LIMIT: usize = 1024;
+/// Maximum number of pages pre-fetched for the [EventPageCache] at the same
+/// time.
+const EVENT_PAGE_PREFETCH_LIMIT: usize = 16;
+/// How long a pre-fetched page is served from the [EventPageCache].
+const EVENT_PAGE_CACHE_TTL: Duration = Duration::from_secs(30);
+
 #[derive(Debug)]
 pub enum GetEventsError {
     Internal(anyhow::Error),
@@ -54,7 +75,7 @@ impl From<GetEventsError> for crate::error::ApplicationError {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetEventsInput {
-    filter: EventFilter,
+    pub(crate) filter: EventFilter,
 }
 
 impl crate::dto::DeserializeForVersion for GetEventsInput {
@@ -68,7 +89,7 @@ impl crate::dto::DeserializeForVersion for GetEventsInput {
 }
 
 /// Contains event filter parameters passed to `starknet_getEvents`.
-#[derive(Default, Clone, Debug, PartialEq, Eq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EventFilter {
     pub from_block: Option<BlockId>,
     pub to_block: Option<BlockId>,
@@ -77,24 +98,49 @@ pub struct EventFilter {
     pub chunk_size: usize,
     /// Offset, measured in events, which points to the requested chunk
     pub continuation_token: Option<String>,
+    /// Non-standard filter on the type of the emitting transaction, only
+    /// available via `pathfinder_getEvents`.
+    pub transaction_types: Option<Vec<TransactionKind>>,
+    /// Non-standard filter on any of several emitting contracts, only available
+    /// via `pathfinder_getEvents`. Not combined with
+    /// [`address`](Self::address).
+    pub from_addresses: Option<Vec<ContractAddress>>,
+}
+
+impl EventFilter {
+    /// Deserializes the standard filter fields, leaving any other fields in
+    /// `value` for the caller to consume.
+    pub(crate) fn deserialize_fields(
+        value: &mut crate::dto::Map,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            from_block: value.deserialize_optional("from_block")?,
+            to_block: value.deserialize_optional("to_block")?,
+            address: value.deserialize_optional("address")?.map(ContractAddress),
+            keys: value
+                .deserialize_optional_array("keys", |value| {
+                    value.deserialize_array(|value| value.deserialize().map(EventKey))
+                })?
+                .unwrap_or_default(),
+            chunk_size: value.deserialize_serde("chunk_size")?,
+            continuation_token: value.deserialize_optional_serde("continuation_token")?,
+            transaction_types: None,
+            from_addresses: None,
+        })
+    }
+
+    /// The contracts the events must be emitted by, any contract if [`None`].
+    fn addresses(&self) -> Option<&[ContractAddress]> {
+        match &self.from_addresses {
+            Some(addresses) => Some(addresses),
+            None => self.address.as_ref().map(std::slice::from_ref),
+        }
+    }
 }
 
 impl crate::dto::DeserializeForVersion for EventFilter {
     fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
-        value.deserialize_map(|value| {
-            Ok(Self {
-                from_block: value.deserialize_optional("from_block")?,
-                to_block: value.deserialize_optional("to_block")?,
-                address: value.deserialize_optional("address")?.map(ContractAddress),
-                keys: value
-                    .deserialize_optional_array("keys", |value| {
-                        value.deserialize_array(|value| value.deserialize().map(EventKey))
-                    })?
-                    .unwrap_or_default(),
-                chunk_size: value.deserialize_serde("chunk_size")?,
-                continuation_token: value.deserialize_optional_serde("continuation_token")?,
-            })
-        })
+        value.deserialize_map(Self::deserialize_fields)
     }
 }
 
@@ -102,6 +148,60 @@ impl crate::dto::DeserializeForVersion for EventFilter {
 pub async fn get_events(
     context: RpcContext,
     input: GetEventsInput,
+) -> Result<GetEventsResult, GetEventsError> {
+    if !context.config.prefetch_next_event_page {
+        return get_events_page(context, input).await;
+    }
+
+    let page = match context.event_page_cache.take(&input.filter) {
+        Some(page) => page,
+        None => get_events_page(context.clone(), input.clone()).await?,
+    };
+    prefetch_next_page(context, input.filter, &page);
+
+    Ok(page)
+}
+
+/// Fetches the page following `page` in the background and stores it in the
+/// [EventPageCache], so that it can be served as soon as it is requested.
+///
+/// Ranges involving the pending block are not pre-fetched since the pending
+/// block can change before the next page is requested. Neither is anything
+/// while [EVENT_PAGE_PREFETCH_LIMIT] pages are already being pre-fetched.
+fn prefetch_next_page(context: RpcContext, filter: EventFilter, page: &GetEventsResult) {
+    let Some(next) = page.continuation_token.as_ref() else {
+        return;
+    };
+    if matches!(filter.from_block, Some(BlockId::Pending))
+        || matches!(filter.to_block, Some(BlockId::Pending))
+    {
+        return;
+    }
+    let cache = context.event_page_cache.clone();
+    let Ok(permit) = cache.prefetches.clone().try_acquire_owned() else {
+        return;
+    };
+    let generation = cache.generation();
+
+    let filter = EventFilter {
+        continuation_token: Some(next.clone()),
+        ..filter
+    };
+    tokio::spawn(async move {
+        let _permit = permit;
+        let input = GetEventsInput {
+            filter: filter.clone(),
+        };
+        match get_events_page(context, input).await {
+            Ok(page) => cache.insert(filter, page, generation),
+            Err(error) => tracing::debug!(?error, "Pre-fetching next page of events failed"),
+        }
+    });
+}
+
+async fn get_events_page(
+    context: RpcContext,
+    input: GetEventsInput,
 ) -> Result<GetEventsResult, GetEventsError> {
     // The [Block::Pending] in ranges makes things quite complicated. This
     // implementation splits the ranges into the following buckets:
@@ -172,6 +272,7 @@ pub async fn get_events(
                 return Ok(GetEventsResult {
                     events: Vec::new(),
                     continuation_token: None,
+                    corrupt_blocks: vec![],
                 });
             }
             (Some(Pending), Some(Pending) | None) => {
@@ -192,6 +293,7 @@ pub async fn get_events(
                     return Ok(GetEventsResult {
                         events: Vec::new(),
                         continuation_token: None,
+                        corrupt_blocks: vec![],
                     });
                 }
             }
@@ -208,25 +310,24 @@ pub async fn get_events(
             None => (from_block, 0),
         };
 
-        let constraints = pathfinder_storage::EventConstraints {
+        let constraints = EventConstraints {
             from_block,
             to_block,
             contract_address: request.address,
             keys: keys.clone(),
             page_size: request.chunk_size,
             offset: requested_offset,
+            transaction_types: request.transaction_types.clone(),
+            from_addresses: request.from_addresses.clone(),
         };
 
-        let page = transaction
-            .events(
-                &constraints,
-                context.config.get_events_max_blocks_to_scan,
-                context.config.get_events_max_event_filters_to_load,
-            )
-            .map_err(|e| match e {
-                EventFilterError::Internal(e) => GetEventsError::Internal(e),
-                EventFilterError::PageSizeTooSmall => GetEventsError::Custom(e.into()),
-            })?;
+        let page = query_events(
+            &transaction,
+            &context.event_shards,
+            &constraints,
+            context.config.get_events_max_blocks_to_scan,
+            context.config.get_events_max_event_filters_to_load,
+        )?;
 
         let mut events = GetEventsResult {
             events: page.events.into_iter().map(|e| e.into()).collect(),
@@ -237,6 +338,7 @@ pub async fn get_events(
                 }
                 .to_string()
             }),
+            corrupt_blocks: page.corrupt_blocks,
         };
 
         // Append pending data if required.
@@ -258,8 +360,8 @@ pub async fn get_events(
 
                 let keys: Vec<std::collections::HashSet<_>> = request
                     .keys
-                    .into_iter()
-                    .map(|keys| keys.into_iter().collect())
+                    .iter()
+                    .map(|keys| keys.iter().copied().collect())
                     .collect();
 
                 let is_last_page = append_pending_events(
@@ -267,8 +369,9 @@ pub async fn get_events(
                     &mut events.events,
                     current_offset,
                     amount,
-                    request.address,
+                    request.addresses(),
                     keys,
+                    request.transaction_types.as_deref(),
                 );
 
                 events.continuation_token = if is_last_page {
@@ -301,6 +404,114 @@ pub async fn get_events(
         .context("Database read panic or shutting down")?
 }
 
+/// Queries the database and any [event shards](RpcContext::event_shards).
+///
+/// Each database holds a separate block range, so the requested range is split
+/// between them and queried in block order until the page is full.
+fn query_events(
+    transaction: &pathfinder_storage::Transaction<'_>,
+    event_shards: &[Storage],
+    constraints: &EventConstraints,
+    max_blocks_to_scan: NonZeroUsize,
+    max_event_filters_to_load: NonZeroUsize,
+) -> Result<PageOfEvents, GetEventsError> {
+    let query = |transaction: &pathfinder_storage::Transaction<'_>,
+                 constraints: &EventConstraints| {
+        transaction
+            .events(constraints, max_blocks_to_scan, max_event_filters_to_load)
+            .map_err(|e| match e {
+                EventFilterError::Internal(e) => GetEventsError::Internal(e),
+                EventFilterError::PageSizeTooSmall => GetEventsError::Custom(e.into()),
+            })
+    };
+
+    if event_shards.is_empty() {
+        return query(transaction, constraints);
+    }
+
+    // The main database is represented by `None`.
+    let mut sources = Vec::with_capacity(event_shards.len() + 1);
+    if let Some(range) = transaction
+        .stored_block_range()
+        .context("Querying block range")?
+    {
+        sources.push((None, range));
+    }
+    for shard in event_shards {
+        let mut connection = shard
+            .connection()
+            .context("Opening shard database connection")?;
+        let transaction = connection
+            .transaction()
+            .context("Creating shard database transaction")?;
+        if let Some(range) = transaction
+            .stored_block_range()
+            .context("Querying shard block range")?
+        {
+            sources.push((Some(shard), range));
+        }
+    }
+    sources.sort_by_key(|(_, (first, _))| *first);
+
+    let from_block = constraints.from_block.unwrap_or(BlockNumber::GENESIS);
+    let to_block = constraints.to_block.unwrap_or(BlockNumber::MAX);
+    sources.retain(|(_, (first, last))| *first <= to_block && *last >= from_block);
+
+    let mut page = PageOfEvents {
+        events: Vec::new(),
+        continuation_token: None,
+        corrupt_blocks: Vec::new(),
+    };
+    // The offset is relative to `from_block`, which is in the first source.
+    let mut offset = constraints.offset;
+    let mut sources = sources.into_iter().peekable();
+    while let Some((shard, (first, last))) = sources.next() {
+        let source_constraints = EventConstraints {
+            from_block: Some(from_block.max(first)),
+            to_block: Some(to_block.min(last)),
+            contract_address: constraints.contract_address,
+            keys: constraints.keys.clone(),
+            page_size: constraints.page_size - page.events.len(),
+            offset,
+            transaction_types: constraints.transaction_types.clone(),
+            from_addresses: constraints.from_addresses.clone(),
+        };
+        offset = 0;
+
+        let source_page = match shard {
+            Some(shard) => {
+                let mut connection = shard
+                    .connection()
+                    .context("Opening shard database connection")?;
+                let transaction = connection
+                    .transaction()
+                    .context("Creating shard database transaction")?;
+                query(&transaction, &source_constraints)?
+            }
+            None => query(transaction, &source_constraints)?,
+        };
+        page.events.extend(source_page.events);
+        page.corrupt_blocks.extend(source_page.corrupt_blocks);
+
+        if source_page.continuation_token.is_some() {
+            page.continuation_token = source_page.continuation_token;
+            break;
+        }
+
+        if page.events.len() == constraints.page_size {
+            page.continuation_token = sources.peek().map(|(_, (first, _))| {
+                pathfinder_storage::event::ContinuationToken {
+                    block_number: from_block.max(*first),
+                    offset: 0,
+                }
+            });
+            break;
+        }
+    }
+
+    Ok(page)
+}
+
 // Handle the case when we're querying events exclusively from the pending
 // block.
 fn get_pending_events(
@@ -326,8 +537,9 @@ fn get_pending_events(
         &mut events,
         current_offset,
         request.chunk_size,
-        request.address,
+        request.addresses(),
         keys,
+        request.transaction_types.as_deref(),
     );
 
     let continuation_token = if is_last_page {
@@ -345,6 +557,7 @@ fn get_pending_events(
     Ok(GetEventsResult {
         events,
         continuation_token,
+        corrupt_blocks: vec![],
     })
 }
 
@@ -370,6 +583,15 @@ fn map_to_block_to_number(
             Ok(Some(number))
         }
         Some(Number(number)) => Ok(Some(number)),
+        Some(L1Accepted) => {
+            let number = tx
+                .block_id(pathfinder_storage::BlockId::L1Accepted)
+                .context("Querying L1 accepted block number")?
+                .ok_or(GetEventsError::BlockNotFound)?
+                .0;
+
+            Ok(Some(number))
+        }
         Some(Pending) | Some(Latest) | None => Ok(None),
     }
 }
@@ -396,6 +618,15 @@ fn map_from_block_to_number(
             Ok(Some(number))
         }
         Some(Number(number)) => Ok(Some(number)),
+        Some(L1Accepted) => {
+            let number = tx
+                .block_id(pathfinder_storage::BlockId::L1Accepted)
+                .context("Querying L1 accepted block number")?
+                .ok_or(GetEventsError::BlockNotFound)?
+                .0;
+
+            Ok(Some(number))
+        }
         Some(Pending) | Some(Latest) => {
             let number = tx
                 .block_id(pathfinder_storage::BlockId::Latest)
@@ -415,8 +646,9 @@ fn append_pending_events(
     dst: &mut Vec<EmittedEvent>,
     skip: usize,
     amount: usize,
-    address: Option<ContractAddress>,
+    addresses: Option<&[ContractAddress]>,
     keys: Vec<std::collections::HashSet<EventKey>>,
+    transaction_types: Option<&[TransactionKind]>,
 ) -> bool {
     let original_len = dst.len();
 
@@ -425,13 +657,18 @@ fn append_pending_events(
     let pending_events = pending_block
         .transaction_receipts
         .iter()
-        .flat_map(|(receipt, events)| {
+        .zip(pending_block.transactions.iter())
+        .filter(|(_, transaction)| match transaction_types {
+            Some(types) => types.contains(&transaction.variant.kind()),
+            None => true,
+        })
+        .flat_map(|((receipt, events), _)| {
             events
                 .iter()
                 .zip(std::iter::repeat(receipt.transaction_hash))
         })
-        .filter(|(event, _)| match address {
-            Some(address) => event.from_address == address,
+        .filter(|(event, _)| match addresses {
+            Some(addresses) => addresses.contains(&event.from_address),
             None => true,
         })
         .filter(|(event, _)| {
@@ -470,7 +707,7 @@ fn append_pending_events(
     is_last_page
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct ContinuationToken {
     block_number: BlockNumber,
     offset: usize,
@@ -537,6 +774,104 @@ impl ContinuationToken {
 #[derive(Debug, Eq, PartialEq)]
 struct ParseContinuationTokenError;
 
+/// Pages of events pre-fetched for `starknet_getEvents`, keyed by the filter
+/// requesting them, including its continuation token.
+///
+/// Only used if
+/// [prefetch_next_event_page](crate::context::RpcConfig::prefetch_next_event_page)
+/// is enabled. Each page is served at most once and only if it was fetched
+/// less than [EVENT_PAGE_CACHE_TTL] ago. All pages are discarded when a [Reorg]
+/// notification is received, since they may contain events of reorged blocks.
+#[derive(Clone)]
+pub struct EventPageCache {
+    inner: Arc<Mutex<EventPageCacheInner>>,
+    /// Limits the number of pages being pre-fetched at the same time.
+    prefetches: Arc<Semaphore>,
+}
+
+struct EventPageCacheInner {
+    pages: SizedCache<EventFilter, CachedPage>,
+    reorgs: broadcast::Receiver<Arc<Reorg>>,
+    /// Incremented every time pages are discarded because of a reorg.
+    generation: u64,
+}
+
+struct CachedPage {
+    page: GetEventsResult,
+    fetched_at: Instant,
+}
+
+impl EventPageCache {
+    pub fn new(notifications: &Notifications) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(EventPageCacheInner {
+                pages: SizedCache::with_size(EVENT_PAGE_CACHE_LIMIT),
+                reorgs: notifications.reorgs.subscribe(),
+                generation: 0,
+            })),
+            prefetches: Arc::new(Semaphore::new(EVENT_PAGE_PREFETCH_LIMIT)),
+        }
+    }
+
+    /// Removes and returns the page cached for the filter, if it has not
+    /// expired yet.
+    fn take(&self, filter: &EventFilter) -> Option<GetEventsResult> {
+        let cached = self.lock().pages.cache_remove(filter)?;
+        (cached.fetched_at.elapsed() < EVENT_PAGE_CACHE_TTL).then_some(cached.page)
+    }
+
+    /// Caches the page fetched for the filter, unless a reorg happened since
+    /// `generation` was read.
+    fn insert(&self, filter: EventFilter, page: GetEventsResult, generation: u64) {
+        let mut inner = self.lock();
+        if inner.generation == generation {
+            inner.pages.cache_set(
+                filter,
+                CachedPage {
+                    page,
+                    fetched_at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    fn lock(&self) -> MutexGuard<'_, EventPageCacheInner> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.process_reorgs();
+        inner
+    }
+
+    #[cfg(test)]
+    fn contains(&self, filter: &EventFilter) -> bool {
+        self.lock().pages.cache_get(filter).is_some()
+    }
+
+    #[cfg(test)]
+    fn is_empty(&self) -> bool {
+        self.lock().pages.cache_size() == 0
+    }
+}
+
+impl EventPageCacheInner {
+    fn process_reorgs(&mut self) {
+        use broadcast::error::TryRecvError;
+
+        loop {
+            match self.reorgs.try_recv() {
+                Ok(_) | Err(TryRecvError::Lagged(_)) => {
+                    self.pages.cache_clear();
+                    self.generation += 1;
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+    }
+}
+
 /// Describes an emitted event returned by starknet_getEvents
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EmittedEvent {
@@ -570,6 +905,24 @@ pub struct GetEventsResult {
     /// Offset, measured in events, which points to the chunk that follows
     /// currently requested chunk (`events`)
     pub continuation_token: Option<String>,
+    /// Blocks whose events could not be read from the database and were
+    /// skipped. Reported as `warnings`.
+    pub corrupt_blocks: Vec<BlockNumber>,
+}
+
+/// Serializes as a `warnings` entry for a block skipped by
+/// `starknet_getEvents`.
+pub(crate) struct CorruptBlockWarning(pub BlockNumber);
+
+impl SerializeForVersion for CorruptBlockWarning {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_field("block_number", &self.0.get())?;
+        serializer.serialize_field("message", &"Skipped corrupt events of this block")?;
+
+        serializer.end()
+    }
 }
 
 impl SerializeForVersion for EmittedEvent {
@@ -600,6 +953,13 @@ impl SerializeForVersion for GetEventsResult {
 
         serializer.serialize_iter("events", self.events.len(), &mut self.events.iter())?;
         serializer.serialize_optional("continuation_token", self.continuation_token.as_ref())?;
+        if !self.corrupt_blocks.is_empty() {
+            serializer.serialize_iter(
+                "warnings",
+                self.corrupt_blocks.len(),
+                &mut self.corrupt_blocks.iter().copied().map(CorruptBlockWarning),
+            )?;
+        }
 
         serializer.end()
     }
@@ -643,6 +1003,8 @@ mod tests {
                 keys: vec![vec![event_key!("0x2")], vec![]],
                 chunk_size: 3,
                 continuation_token: Some("4".to_string()),
+                transaction_types: None,
+                from_addresses: None,
             }
         } else {
             EventFilter {
@@ -725,8 +1087,65 @@ mod tests {
             GetEventsResult {
                 events,
                 continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn get_events_skips_corrupt_events() {
+        let (context, events) = setup();
+        let corrupt_block = BlockNumber::new_or_panic(2);
+        test_utils::corrupt_block_events(&context.storage, corrupt_block);
+
+        let input = GetEventsInput {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                ..Default::default()
+            },
+        };
+        let result = get_events(context, input).await.unwrap();
+
+        let expected = events
+            .into_iter()
+            .filter(|event| event.block_number != Some(corrupt_block))
+            .collect();
+        assert_eq!(
+            result,
+            GetEventsResult {
+                events: expected,
+                continuation_token: None,
+                corrupt_blocks: vec![corrupt_block],
             }
         );
+
+        let result = result.serialize(Serializer::new(RpcVersion::V08)).unwrap();
+        assert_eq!(
+            result["warnings"],
+            json!([{
+                "block_number": 2,
+                "message": "Skipped corrupt events of this block",
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn storage_error_is_an_internal_error() {
+        let (context, _) = setup();
+        test_utils::drop_transactions_table(&context.storage);
+
+        let input = GetEventsInput {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                ..Default::default()
+            },
+        };
+        let error = get_events(context, input).await.unwrap_err();
+        assert_matches::assert_matches!(error, GetEventsError::Internal(_));
+
+        let error = crate::jsonrpc::RpcError::ApplicationError(error.into());
+        assert_eq!(error.code(), -32603);
+        assert_eq!(error.message(RpcVersion::V08), "Internal error");
     }
 
     #[tokio::test]
@@ -737,6 +1156,7 @@ mod tests {
         let expected_result = GetEventsResult {
             events: vec![expected_event.clone()],
             continuation_token: None,
+            corrupt_blocks: vec![],
         };
         let input = GetEventsInput {
             filter: EventFilter {
@@ -747,6 +1167,8 @@ mod tests {
                 keys: vec![vec![], vec![event_key!("0xdeadbeef")]],
                 chunk_size: test_utils::NUM_EVENTS,
                 continuation_token: None,
+                transaction_types: None,
+                from_addresses: None,
             },
         };
         let result = get_events(context.clone(), input.clone()).await.unwrap();
@@ -776,10 +1198,100 @@ mod tests {
             GetEventsResult {
                 events: expected_events.to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
 
+    /// `DEPLOY` transactions predate Starknet v0.1 and can no longer be
+    /// submitted, but the events they emitted must still be queryable.
+    #[tokio::test]
+    async fn get_events_from_deploy_transactions() {
+        use pathfinder_common::transaction::TransactionVariant;
+
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let deploys = test_data
+            .transactions
+            .iter()
+            .filter(|tx| matches!(tx.variant, TransactionVariant::DeployV0(_)))
+            .map(|tx| tx.hash)
+            .collect::<std::collections::HashSet<_>>();
+        let deploy_events = test_data
+            .events
+            .into_iter()
+            .filter(|event| deploys.contains(&event.transaction_hash))
+            .map(EmittedEvent::from)
+            .collect::<Vec<_>>();
+        assert!(!deploy_events.is_empty());
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        for event in &deploy_events {
+            let input = GetEventsInput {
+                filter: EventFilter {
+                    address: Some(event.from_address),
+                    chunk_size: test_utils::NUM_EVENTS,
+                    ..Default::default()
+                },
+            };
+
+            let result = get_events(context.clone(), input).await.unwrap();
+
+            assert_eq!(result.events, vec![event.clone()]);
+        }
+
+        let input = GetEventsInput {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                transaction_types: Some(vec![TransactionKind::Deploy]),
+                ..Default::default()
+            },
+        };
+        let result = get_events(context, input).await.unwrap();
+
+        assert_eq!(result.events, deploy_events);
+    }
+
+    #[tokio::test]
+    async fn get_events_with_single_block_range() {
+        let (context, events) = setup();
+
+        for block in 0..test_utils::NUM_BLOCKS {
+            let expected_events = &events
+                [test_utils::EVENTS_PER_BLOCK * block..test_utils::EVENTS_PER_BLOCK * (block + 1)];
+            let block_hash = expected_events[0].block_hash.unwrap();
+            let block_number = BlockNumber::new_or_panic(block as u64);
+
+            let ranges: [(BlockId, BlockId); 3] = [
+                (block_number.into(), block_number.into()),
+                (block_hash.into(), block_hash.into()),
+                (block_number.into(), block_hash.into()),
+            ];
+
+            for (from_block, to_block) in ranges {
+                let input = GetEventsInput {
+                    filter: EventFilter {
+                        from_block: Some(from_block),
+                        to_block: Some(to_block),
+                        chunk_size: test_utils::NUM_EVENTS,
+                        ..Default::default()
+                    },
+                };
+
+                let result = get_events(context.clone(), input).await.unwrap();
+
+                assert_eq!(
+                    result,
+                    GetEventsResult {
+                        events: expected_events.to_vec(),
+                        continuation_token: None,
+                        corrupt_blocks: vec![],
+                    },
+                    "block {block}: from {from_block:?} to {to_block:?}"
+                );
+            }
+        }
+    }
+
     #[tokio::test]
     async fn get_events_from_latest_block() {
         let (context, events) = setup();
@@ -803,6 +1315,7 @@ mod tests {
             GetEventsResult {
                 events: expected_events.to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -874,6 +1387,7 @@ mod tests {
             GetEventsResult {
                 events: expected_events[..1].to_vec(),
                 continuation_token: Some("0-1".to_string()),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -891,6 +1405,7 @@ mod tests {
             GetEventsResult {
                 events: expected_events[1..3].to_vec(),
                 continuation_token: Some("3-0".to_string()),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -908,6 +1423,7 @@ mod tests {
             GetEventsResult {
                 events: expected_events[3..].to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
 
@@ -980,6 +1496,124 @@ mod tests {
             assert!(all_events.continuation_token.is_none());
         }
 
+        #[tokio::test]
+        async fn pending_range_excludes_finalized_events() {
+            let context = RpcContext::for_tests_with_pending().await;
+
+            let finalized = get_events(
+                context.clone(),
+                GetEventsInput {
+                    filter: EventFilter {
+                        to_block: Some(BlockId::Latest),
+                        chunk_size: 1024,
+                        ..Default::default()
+                    },
+                },
+            )
+            .await
+            .unwrap()
+            .events;
+            let finalized = finalized.first().unwrap().clone();
+
+            // Replace the events of the pending block with a copy of the finalized
+            // event, so that both match the same filter.
+            let mut pending = context.pending_data.get_unchecked();
+            let mut block = (*pending.block).clone();
+            block.transaction_receipts[0].1 = vec![pathfinder_common::event::Event {
+                data: finalized.data.clone(),
+                from_address: finalized.from_address,
+                keys: finalized.keys.clone(),
+            }];
+            pending.block = std::sync::Arc::new(block);
+            let (tx, rx) = tokio::sync::watch::channel(Default::default());
+            tx.send(pending).unwrap();
+            let context = context.with_pending_data(rx);
+
+            let mut input = GetEventsInput {
+                filter: EventFilter {
+                    from_block: Some(BlockId::Pending),
+                    to_block: Some(BlockId::Pending),
+                    address: Some(finalized.from_address),
+                    keys: vec![finalized.keys.clone()],
+                    chunk_size: 1024,
+                    ..Default::default()
+                },
+            };
+            let result = get_events(context.clone(), input.clone()).await.unwrap();
+
+            let expected = EmittedEvent {
+                block_hash: None,
+                block_number: None,
+                transaction_hash: transaction_hash_bytes!(b"pending tx hash 0"),
+                ..finalized.clone()
+            };
+            assert_eq!(result.events, vec![expected.clone()]);
+            assert_eq!(result.continuation_token, None);
+
+            // Both events are returned once the range includes finalized blocks.
+            input.filter.from_block = None;
+            let result = get_events(context, input).await.unwrap();
+            assert_eq!(result.events, vec![finalized, expected]);
+        }
+
+        /// Pending events, like pending transaction receipts, omit the block
+        /// hash and number instead of setting them to null.
+        #[tokio::test]
+        async fn serialization_shape() {
+            let context = RpcContext::for_tests_with_pending().await;
+            let serialize = |result: GetEventsResult| {
+                result.serialize(Serializer::new(RpcVersion::V08)).unwrap()["events"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|event| event.as_object().unwrap().clone())
+                    .collect::<Vec<_>>()
+            };
+
+            let mut input = GetEventsInput {
+                filter: EventFilter {
+                    to_block: Some(BlockId::Latest),
+                    chunk_size: 1024,
+                    ..Default::default()
+                },
+            };
+            let finalized = serialize(get_events(context.clone(), input.clone()).await.unwrap());
+
+            input.filter.from_block = Some(BlockId::Pending);
+            input.filter.to_block = Some(BlockId::Pending);
+            let pending = serialize(get_events(context.clone(), input).await.unwrap());
+
+            assert!(!pending.is_empty());
+            for event in &finalized {
+                assert!(event["block_hash"].is_string());
+                assert!(event["block_number"].is_u64());
+            }
+            let mut finalized_keys = finalized[0].keys().cloned().collect::<Vec<_>>();
+            finalized_keys.sort();
+            for event in &pending {
+                let mut keys = event.keys().cloned().collect::<Vec<_>>();
+                keys.extend(["block_hash".to_owned(), "block_number".to_owned()]);
+                keys.sort();
+                assert_eq!(keys, finalized_keys);
+            }
+
+            let receipt = crate::method::get_transaction_receipt(
+                context,
+                crate::method::get_transaction_receipt::Input {
+                    transaction_hash: transaction_hash_bytes!(b"pending tx hash 0"),
+                    include_inner_calls: false,
+                    include_fee_trace: false,
+                    include_data_gas: false,
+                },
+            )
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::V08))
+            .unwrap();
+            assert!(receipt.get("block_hash").is_none());
+            assert!(receipt.get("block_number").is_none());
+        }
+
         #[tokio::test]
         async fn paging() {
             let context = RpcContext::for_tests_with_pending().await;
@@ -1063,6 +1697,8 @@ mod tests {
                     ]],
                     chunk_size: 1024,
                     continuation_token: None,
+                    transaction_types: None,
+                    from_addresses: None,
                 },
             };
 
@@ -1079,6 +1715,63 @@ mod tests {
             assert_eq!(result.continuation_token, Some("3-0".to_string()));
         }
 
+        #[tokio::test]
+        async fn paging_within_pending_block() {
+            let context = RpcContext::for_tests_with_pending().await;
+
+            let mut input = GetEventsInput {
+                filter: EventFilter {
+                    from_block: Some(BlockId::Pending),
+                    to_block: Some(BlockId::Pending),
+                    chunk_size: 1024,
+                    ..Default::default()
+                },
+            };
+
+            let all = get_events(context.clone(), input.clone())
+                .await
+                .unwrap()
+                .events;
+            assert_eq!(all.len(), 3);
+
+            // The pending block has more events than fit on a page, so each token
+            // resumes from the next event within the pending block.
+            input.filter.chunk_size = 1;
+            let mut tokens = vec![];
+            let mut events = vec![];
+            loop {
+                let result = get_events(context.clone(), input.clone()).await.unwrap();
+                events.extend(result.events);
+                match result.continuation_token {
+                    Some(token) => {
+                        tokens.push(token.clone());
+                        input.filter.continuation_token = Some(token);
+                    }
+                    None => break,
+                }
+            }
+            assert_eq!(tokens, vec!["3-1".to_string(), "3-2".to_string()]);
+            assert_eq!(events, all);
+
+            // A page size that does not divide the number of events.
+            input.filter.chunk_size = 2;
+            input.filter.continuation_token = None;
+            let result = get_events(context.clone(), input.clone()).await.unwrap();
+            assert_eq!(result.events, &all[0..2]);
+            assert_eq!(result.continuation_token, Some("3-2".to_string()));
+
+            input.filter.continuation_token = result.continuation_token;
+            let result = get_events(context.clone(), input.clone()).await.unwrap();
+            assert_eq!(result.events, &all[2..3]);
+            assert_eq!(result.continuation_token, None);
+
+            // A token from a block which is no longer pending starts from the
+            // beginning of the pending block.
+            input.filter.continuation_token = Some("2-5".to_string());
+            let result = get_events(context.clone(), input.clone()).await.unwrap();
+            assert_eq!(result.events, &all[0..2]);
+        }
+
         #[tokio::test]
         async fn key_matching() {
             let context = RpcContext::for_tests_with_pending().await;
@@ -1091,6 +1784,8 @@ mod tests {
                     keys: vec![],
                     chunk_size: 1024,
                     continuation_token: None,
+                    transaction_types: None,
+                    from_addresses: None,
                 },
             };
 
@@ -1147,4 +1842,369 @@ mod tests {
             assert!(!result.events.is_empty());
         }
     }
+
+    mod sharded {
+        use pretty_assertions_sorted::assert_eq;
+
+        use super::*;
+
+        /// Blocks 0 and 1 are stored in a shard, blocks 2 and 3 in the main
+        /// database.
+        fn setup() -> (RpcContext, Vec<EmittedEvent>) {
+            let (shard, shard_data) = test_utils::setup_custom_test_storage(&[0, 1], 3);
+            let (storage, test_data) = test_utils::setup_custom_test_storage(&[2, 3], 3);
+            let events = shard_data
+                .events
+                .into_iter()
+                .chain(test_data.events)
+                .map(EmittedEvent::from)
+                .collect();
+            let context = RpcContext::for_tests()
+                .with_storage(storage)
+                .with_event_shards(vec![shard]);
+
+            (context, events)
+        }
+
+        #[tokio::test]
+        async fn all_events() {
+            let (context, all) = setup();
+
+            let input = GetEventsInput {
+                filter: EventFilter {
+                    chunk_size: 1024,
+                    ..Default::default()
+                },
+            };
+            let result = get_events(context, input).await.unwrap();
+
+            assert_eq!(
+                result,
+                GetEventsResult {
+                    events: all,
+                    continuation_token: None,
+                    corrupt_blocks: vec![],
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn block_range_within_shard() {
+            let (context, all) = setup();
+
+            let input = GetEventsInput {
+                filter: EventFilter {
+                    from_block: Some(BlockId::Number(BlockNumber::GENESIS)),
+                    to_block: Some(BlockId::Number(BlockNumber::new_or_panic(1))),
+                    chunk_size: 1024,
+                    ..Default::default()
+                },
+            };
+            let result = get_events(context, input).await.unwrap();
+
+            let expected = all
+                .into_iter()
+                .filter(|event| event.block_number <= Some(BlockNumber::new_or_panic(1)))
+                .collect::<Vec<_>>();
+            assert!(!expected.is_empty());
+            assert_eq!(result.events, expected);
+            assert_eq!(result.continuation_token, None);
+        }
+
+        #[tokio::test]
+        async fn paging() {
+            let (context, all) = setup();
+
+            let mut events = Vec::new();
+            let mut continuation_token = None;
+            let mut pages = 0;
+            loop {
+                let input = GetEventsInput {
+                    filter: EventFilter {
+                        chunk_size: 4,
+                        continuation_token,
+                        ..Default::default()
+                    },
+                };
+                let result = get_events(context.clone(), input).await.unwrap();
+                events.extend(result.events);
+                pages += 1;
+
+                continuation_token = result.continuation_token;
+                if continuation_token.is_none() {
+                    break;
+                }
+            }
+
+            assert!(pages > 1);
+            assert_eq!(events, all);
+        }
+    }
+
+    mod pagination {
+        use std::num::NonZeroUsize;
+
+        use proptest::prelude::*;
+        use tokio::runtime::Runtime;
+
+        use super::*;
+
+        /// Follows the continuation tokens until the last page, collecting the
+        /// events of all pages.
+        async fn all_pages(context: RpcContext, filter: EventFilter) -> Vec<EmittedEvent> {
+            let mut events = Vec::new();
+            let mut continuation_token = None;
+            loop {
+                let input = GetEventsInput {
+                    filter: EventFilter {
+                        continuation_token,
+                        ..filter.clone()
+                    },
+                };
+                let result = get_events(context.clone(), input).await.unwrap();
+                events.extend(result.events);
+
+                continuation_token = result.continuation_token;
+                if continuation_token.is_none() {
+                    return events;
+                }
+            }
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(25))]
+            #[test]
+            fn pages_match_single_scan(
+                from_block in 0..test_utils::NUM_BLOCKS as u64,
+                to_block in 0..test_utils::NUM_BLOCKS as u64,
+                chunk_size in 1..=test_utils::EVENTS_PER_BLOCK + 1,
+                max_blocks_to_scan in 1..=test_utils::NUM_BLOCKS,
+                key_filter in proptest::option::of(0..test_utils::NUM_EVENTS),
+            ) {
+                let (context, events) = setup();
+                let filter = EventFilter {
+                    from_block: Some(BlockId::Number(BlockNumber::new_or_panic(from_block))),
+                    to_block: Some(BlockId::Number(BlockNumber::new_or_panic(to_block))),
+                    keys: key_filter
+                        .map(|i| vec![vec![events[i].keys[0]]])
+                        .unwrap_or_default(),
+                    chunk_size: test_utils::NUM_EVENTS,
+                    ..Default::default()
+                };
+
+                let (single_scan, paginated) = Runtime::new().unwrap().block_on(async {
+                    let input = GetEventsInput {
+                        filter: filter.clone(),
+                    };
+                    let single_scan = get_events(context.clone(), input).await.unwrap();
+
+                    // The scan limit applies to each page separately.
+                    let mut context = context;
+                    context.config.get_events_max_blocks_to_scan =
+                        NonZeroUsize::new(max_blocks_to_scan).unwrap();
+                    let paginated = all_pages(
+                        context,
+                        EventFilter {
+                            chunk_size,
+                            ..filter
+                        },
+                    )
+                    .await;
+
+                    (single_scan, paginated)
+                });
+
+                prop_assert_eq!(single_scan.continuation_token, None);
+                prop_assert_eq!(paginated, single_scan.events);
+            }
+        }
+    }
+
+    mod prefetch {
+        use std::time::Duration;
+
+        use pretty_assertions_sorted::assert_eq;
+
+        use super::*;
+
+        fn setup() -> (RpcContext, Vec<EmittedEvent>) {
+            let (mut context, events) = super::setup();
+            context.config.prefetch_next_event_page = true;
+            (context, events)
+        }
+
+        fn input(chunk_size: usize, continuation_token: Option<&str>) -> GetEventsInput {
+            GetEventsInput {
+                filter: EventFilter {
+                    chunk_size,
+                    continuation_token: continuation_token.map(ToOwned::to_owned),
+                    ..Default::default()
+                },
+            }
+        }
+
+        async fn wait_for_prefetch(context: &RpcContext, token: &str) {
+            let filter = input(3, Some(token)).filter;
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while !context.event_page_cache.contains(&filter) {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .expect("Next page should be pre-fetched");
+        }
+
+        fn empty_page() -> GetEventsResult {
+            GetEventsResult {
+                events: vec![],
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        }
+
+        #[tokio::test]
+        async fn next_page_is_prefetched() {
+            let (context, events) = setup();
+
+            let result = get_events(context.clone(), input(3, None)).await.unwrap();
+            assert_eq!(result.events, events[..3].to_vec());
+            let token = result.continuation_token.unwrap();
+            wait_for_prefetch(&context, &token).await;
+
+            let expected = get_events_page(context.clone(), input(3, Some(&token)))
+                .await
+                .unwrap();
+            let result = get_events(context.clone(), input(3, Some(&token)))
+                .await
+                .unwrap();
+            assert_eq!(result, expected);
+            assert_eq!(result.events, events[3..6].to_vec());
+        }
+
+        #[tokio::test]
+        async fn cache_hit_serves_cached_page() {
+            let (context, _) = setup();
+            let cached = GetEventsResult {
+                corrupt_blocks: vec![BlockNumber::new_or_panic(1)],
+                ..empty_page()
+            };
+            let cache = &context.event_page_cache;
+            cache.insert(
+                input(3, Some("1-2")).filter,
+                cached.clone(),
+                cache.generation(),
+            );
+
+            let result = get_events(context.clone(), input(3, Some("1-2")))
+                .await
+                .unwrap();
+            assert_eq!(result, cached);
+            // Cached pages are only served once.
+            assert!(!context
+                .event_page_cache
+                .contains(&input(3, Some("1-2")).filter));
+        }
+
+        #[tokio::test]
+        async fn different_filter_is_not_served_from_cache() {
+            let (context, events) = setup();
+            let cache = &context.event_page_cache;
+            cache.insert(
+                input(5, Some("1-2")).filter,
+                empty_page(),
+                cache.generation(),
+            );
+
+            let result = get_events(context.clone(), input(3, Some("1-2")))
+                .await
+                .unwrap();
+            let offset = test_utils::EVENTS_PER_BLOCK + 2;
+            assert_eq!(result.events, events[offset..offset + 3].to_vec());
+        }
+
+        #[tokio::test]
+        async fn expired_page_is_not_served() {
+            let (context, events) = setup();
+            let filter = input(3, Some("1-2")).filter;
+            context.event_page_cache.lock().pages.cache_set(
+                filter.clone(),
+                CachedPage {
+                    page: empty_page(),
+                    fetched_at: Instant::now() - EVENT_PAGE_CACHE_TTL,
+                },
+            );
+
+            let result = get_events(context.clone(), GetEventsInput { filter })
+                .await
+                .unwrap();
+            let offset = test_utils::EVENTS_PER_BLOCK + 2;
+            assert_eq!(result.events, events[offset..offset + 3].to_vec());
+        }
+
+        #[tokio::test]
+        async fn reorg_clears_cache() {
+            let (context, _) = setup();
+            let cache = &context.event_page_cache;
+            let generation = cache.generation();
+            cache.insert(input(3, Some("1-2")).filter, empty_page(), generation);
+
+            context
+                .notifications
+                .reorgs
+                .send(Arc::new(Reorg {
+                    first_block_number: BlockNumber::new_or_panic(1),
+                    first_block_hash: BlockHash::ZERO,
+                    last_block_number: BlockNumber::new_or_panic(2),
+                    last_block_hash: BlockHash::ZERO,
+                }))
+                .unwrap();
+
+            assert!(cache.is_empty());
+            // Pages fetched before the reorg are not cached.
+            cache.insert(input(3, Some("1-3")).filter, empty_page(), generation);
+            assert!(cache.is_empty());
+        }
+
+        #[tokio::test]
+        async fn concurrent_prefetches_are_limited() {
+            let (context, _) = setup();
+            let _permits = context
+                .event_page_cache
+                .prefetches
+                .clone()
+                .try_acquire_many_owned(EVENT_PAGE_PREFETCH_LIMIT as u32)
+                .unwrap();
+
+            let result = get_events(context.clone(), input(3, None)).await.unwrap();
+            assert!(result.continuation_token.is_some());
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            assert!(context.event_page_cache.is_empty());
+        }
+
+        #[tokio::test]
+        async fn disabled() {
+            let (context, _) = super::setup();
+
+            let result = get_events(context.clone(), input(3, None)).await.unwrap();
+            assert!(result.continuation_token.is_some());
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            assert!(context.event_page_cache.is_empty());
+        }
+
+        #[tokio::test]
+        async fn pending_range_is_not_prefetched() {
+            let mut context = RpcContext::for_tests_with_pending().await;
+            context.config.prefetch_next_event_page = true;
+            let mut input = input(1, None);
+            input.filter.from_block = Some(BlockId::Pending);
+
+            let result = get_events(context.clone(), input).await.unwrap();
+            assert!(result.continuation_token.is_some());
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            assert!(context.event_page_cache.is_empty());
+        }
+    }
 }
@@ -172,6 +172,74 @@ mod tests {
         assert_eq!(nonce.0, contract_nonce_bytes!(b"pending nonce"));
     }
 
+    /// The pending state update already contains the nonce after all pending
+    /// transactions of the account, so transactions must not be counted again.
+    #[tokio::test]
+    async fn pending_includes_all_pending_transactions() {
+        use pathfinder_common::transaction::{
+            InvokeTransactionV1,
+            Transaction,
+            TransactionVariant,
+        };
+        use pathfinder_common::StateUpdate;
+        use starknet_gateway_types::reply::PendingBlock;
+
+        use crate::pending::PendingData;
+
+        let context = RpcContext::for_tests();
+        let latest = {
+            let mut db = context.storage.connection().unwrap();
+            let tx = db.transaction().unwrap();
+            tx.block_header(pathfinder_storage::BlockId::Latest)
+                .unwrap()
+                .unwrap()
+        };
+
+        // This contract is created in `setup_storage` and has a nonce set to 0x1.
+        let account = contract_address_bytes!(b"contract 0");
+        let transactions = [
+            (
+                transaction_hash_bytes!(b"pending txn 0"),
+                transaction_nonce!("0x1"),
+            ),
+            (
+                transaction_hash_bytes!(b"pending txn 1"),
+                transaction_nonce!("0x2"),
+            ),
+        ]
+        .into_iter()
+        .map(|(hash, nonce)| Transaction {
+            hash,
+            variant: TransactionVariant::InvokeV1(InvokeTransactionV1 {
+                sender_address: account,
+                nonce,
+                ..Default::default()
+            }),
+        })
+        .collect();
+        let pending_data = PendingData {
+            block: PendingBlock {
+                parent_hash: latest.hash,
+                transactions,
+                ..Default::default()
+            }
+            .into(),
+            state_update: StateUpdate::default()
+                .with_contract_nonce(account, contract_nonce!("0x3"))
+                .into(),
+            number: latest.number + 1,
+        };
+        let (_tx, rx) = tokio::sync::watch::channel(pending_data);
+        let context = context.with_pending_data(rx);
+
+        let input = Input {
+            block_id: BlockId::Pending,
+            contract_address: account,
+        };
+        let nonce = get_nonce(context, input).await.unwrap();
+        assert_eq!(nonce.0, contract_nonce!("0x3"));
+    }
+
     #[tokio::test]
     async fn pending_defaults_to_latest() {
         let context = RpcContext::for_tests();
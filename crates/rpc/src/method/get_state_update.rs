@@ -584,6 +584,38 @@ mod tests {
         assert_eq!(result, Err(Error::BlockNotFound));
     }
 
+    #[tokio::test]
+    async fn nonces_of_invoking_contracts() {
+        use dto::serialize::SerializeForVersion;
+
+        let context = RpcContext::for_tests();
+        let input = Input {
+            block_id: BlockId::Latest,
+        };
+
+        let result = get_state_update(context, input).await.unwrap();
+        let result = result
+            .serialize(dto::serialize::Serializer::new(RpcVersion::V07))
+            .unwrap();
+
+        let mut nonces = result["state_diff"]["nonces"].as_array().unwrap().clone();
+        nonces.sort_by_key(|n| n["contract_address"].as_str().unwrap().to_owned());
+
+        let mut expected = vec![
+            json!({
+                "contract_address": contract_address_bytes!(b"contract 1"),
+                "nonce": "0x10",
+            }),
+            json!({
+                "contract_address": contract_address_bytes!(b"contract 2"),
+                "nonce": "0xfeed",
+            }),
+        ];
+        expected.sort_by_key(|n| n["contract_address"].as_str().unwrap().to_owned());
+
+        assert_eq!(nonces, expected);
+    }
+
     #[tokio::test]
     async fn pending() {
         let context = RpcContext::for_tests_with_pending().await;
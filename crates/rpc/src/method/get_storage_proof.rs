@@ -306,8 +306,9 @@ pub async fn get_storage_proof(context: RpcContext, input: Input) -> Result<Outp
         // Use internal error to indicate that the process of querying for a particular
         // block failed, which is not the same as being sure that the block is
         // not in the db.
-        let header = tx
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&tx, block_id)
             .context("Fetching block header")?
             .ok_or(Error::BlockNotFound)?;
 
@@ -780,6 +781,90 @@ mod tests {
         get_storage_proof(context, input).await.unwrap();
     }
 
+    /// Follows `key` from `root` through the proof nodes, checking the hash of
+    /// every node on the way, and returns the value of the leaf reached.
+    ///
+    /// Returns [None] if a node is missing or hashes to something else than
+    /// its parent committed to, or if the proof shows `key` to not be in the
+    /// trie.
+    fn verify_proof(
+        root: Felt,
+        key: &bitvec::slice::BitSlice<u8, bitvec::order::Msb0>,
+        proof: &NodeHashToNodeMappings,
+    ) -> Option<Felt> {
+        let nodes = proof
+            .0
+            .iter()
+            .map(|mapping| (mapping.node_hash, &mapping.node.0))
+            .collect::<std::collections::HashMap<_, _>>();
+
+        let mut expected_hash = root;
+        let mut remaining_path = key;
+        while !remaining_path.is_empty() {
+            let node = nodes.get(&expected_hash)?;
+            if node.hash::<pathfinder_common::hash::PedersenHash>() != expected_hash {
+                return None;
+            }
+
+            match node {
+                TrieNode::Binary { left, right } => {
+                    expected_hash = if remaining_path[0] { *right } else { *left };
+                    remaining_path = &remaining_path[1..];
+                }
+                TrieNode::Edge { child, path } => {
+                    if !remaining_path.starts_with(path) {
+                        return None;
+                    }
+                    expected_hash = *child;
+                    remaining_path = &remaining_path[path.len()..];
+                }
+            }
+        }
+
+        Some(expected_hash)
+    }
+
+    #[tokio::test]
+    async fn proofs_verify_against_roots() {
+        let context = RpcContext::for_tests();
+        let block_number = BlockNumber::GENESIS + 2;
+        let contract = contract_address_bytes!(b"contract 1");
+        let key = storage_address_bytes!(b"storage addr 0");
+        let input = Input {
+            block_id: BlockId::Number(block_number),
+            class_hashes: None,
+            contract_addresses: Some(vec![contract]),
+            contracts_storage_keys: Some(vec![ContractStorageKeys {
+                contract_address: contract,
+                storage_keys: vec![key],
+            }]),
+        };
+
+        let output = get_storage_proof(context.clone(), input).await.unwrap();
+
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        let contract_state_hash = tx
+            .contract_state_hash(block_number, contract)
+            .unwrap()
+            .unwrap();
+        let contract_root = tx.contract_root(block_number, contract).unwrap().unwrap();
+
+        let leaf = verify_proof(
+            output.global_roots.contracts_tree_root,
+            contract.0.view_bits(),
+            &output.contracts_proof.nodes,
+        );
+        assert_eq!(leaf, Some(contract_state_hash.0));
+
+        let leaf = verify_proof(
+            contract_root.0,
+            key.0.view_bits(),
+            &output.contracts_storage_proofs[0],
+        );
+        assert_eq!(leaf, Some(storage_value_bytes!(b"storage value 2").0));
+    }
+
     #[tokio::test]
     async fn pending_block() {
         let context = RpcContext::for_tests();
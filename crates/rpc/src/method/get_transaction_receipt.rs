@@ -2,13 +2,29 @@ use anyhow::Context;
 use pathfinder_common::event::Event;
 use pathfinder_common::receipt::Receipt;
 use pathfinder_common::transaction::Transaction;
-use pathfinder_common::{BlockHash, BlockNumber, TransactionHash};
+use pathfinder_common::{BlockHash, BlockNumber, GasPrice, TransactionHash};
 
 use crate::context::RpcContext;
 use crate::dto::{self, serialize};
+use crate::method::trace_transaction::{complete_trace, trace_locally, TraceTransactionError};
+use crate::pending::PendingData;
 
 pub struct Input {
     pub transaction_hash: TransactionHash,
+    /// Non-standard extension which adds the execution resources of each call,
+    /// including nested calls, to the receipt. These come from the execution
+    /// trace, so the transaction's block is re-executed unless the trace is
+    /// cached.
+    pub include_inner_calls: bool,
+    /// Non-standard extension which adds the `fee_transfer_invocation` of the
+    /// execution trace to the receipt. Like
+    /// [`include_inner_calls`](Self::include_inner_calls) this requires the
+    /// transaction to be traced.
+    pub include_fee_trace: bool,
+    /// Non-standard extension which adds the `data_gas_consumed` and
+    /// `data_gas_price` of transactions in blocks using blob data availability
+    /// to the `execution_resources` of the receipt.
+    pub include_data_gas: bool,
 }
 
 impl crate::dto::DeserializeForVersion for Input {
@@ -16,12 +32,30 @@ impl crate::dto::DeserializeForVersion for Input {
         value.deserialize_map(|value| {
             Ok(Self {
                 transaction_hash: value.deserialize("transaction_hash").map(TransactionHash)?,
+                include_inner_calls: value
+                    .deserialize_optional_serde("include_inner_calls")?
+                    .unwrap_or_default(),
+                include_fee_trace: value
+                    .deserialize_optional_serde("include_fee_trace")?
+                    .unwrap_or_default(),
+                include_data_gas: value
+                    .deserialize_optional_serde("include_data_gas")?
+                    .unwrap_or_default(),
             })
         })
     }
 }
 
-pub enum Output {
+pub struct Output {
+    receipt: TransactionReceipt,
+    /// Only set if [`Input::include_inner_calls`] or
+    /// [`Input::include_fee_trace`] was requested.
+    trace: Option<pathfinder_executor::types::TransactionTrace>,
+    include_inner_calls: bool,
+    include_fee_trace: bool,
+}
+
+pub enum TransactionReceipt {
     Full {
         block_hash: BlockHash,
         block_number: BlockNumber,
@@ -29,27 +63,55 @@ pub enum Output {
         transaction: Transaction,
         events: Vec<Event>,
         finality: dto::TxnFinalityStatus,
+        data_gas_price: Option<GasPrice>,
     },
     Pending {
         receipt: Receipt,
         transaction: Transaction,
         events: Vec<Event>,
+        data_gas_price: Option<GasPrice>,
     },
 }
 
 impl serialize::SerializeForVersion for Output {
+    fn serialize(
+        &self,
+        serializer: serialize::Serializer,
+    ) -> Result<serialize::Ok, serialize::Error> {
+        let Some(trace) = &self.trace else {
+            return self.receipt.serialize(serializer);
+        };
+
+        let mut serializer = serializer.serialize_struct()?;
+        serializer.flatten(&self.receipt)?;
+        if self.include_inner_calls {
+            serializer.serialize_field("inner_calls", &dto::InnerCallResources(trace))?;
+        }
+        if self.include_fee_trace {
+            // L1 handlers do not pay a fee, so there is nothing to include.
+            serializer.serialize_optional(
+                "fee_transfer_invocation",
+                trace.fee_transfer_invocation().map(dto::FunctionInvocation),
+            )?;
+        }
+        serializer.end()
+    }
+}
+
+impl serialize::SerializeForVersion for TransactionReceipt {
     fn serialize(
         &self,
         serializer: serialize::Serializer,
     ) -> Result<serialize::Ok, serialize::Error> {
         match self {
-            Output::Full {
+            TransactionReceipt::Full {
                 block_hash,
                 block_number,
                 receipt,
                 transaction,
                 events,
                 finality,
+                data_gas_price,
             } => dto::TxnReceiptWithBlockInfo {
                 block_hash: Some(block_hash),
                 block_number: Some(*block_number),
@@ -57,11 +119,13 @@ impl serialize::SerializeForVersion for Output {
                 transaction,
                 events,
                 finality: *finality,
+                data_gas_price: *data_gas_price,
             },
-            Output::Pending {
+            TransactionReceipt::Pending {
                 receipt,
                 transaction,
                 events,
+                data_gas_price,
             } => dto::TxnReceiptWithBlockInfo {
                 block_hash: None,
                 block_number: None,
@@ -69,6 +133,7 @@ impl serialize::SerializeForVersion for Output {
                 transaction,
                 events,
                 finality: dto::TxnFinalityStatus::AcceptedOnL2,
+                data_gas_price: *data_gas_price,
             },
         }
         .serialize(serializer)
@@ -77,67 +142,662 @@ impl serialize::SerializeForVersion for Output {
 
 crate::error::generate_rpc_error_subset!(Error: TxnHashNotFound);
 
+impl From<TraceTransactionError> for Error {
+    fn from(e: TraceTransactionError) -> Self {
+        match e {
+            TraceTransactionError::TxnHashNotFound => Self::TxnHashNotFound,
+            TraceTransactionError::Internal(e) => Self::Internal(e),
+            TraceTransactionError::Custom(e) => Self::Custom(e),
+            TraceTransactionError::NoTraceAvailable(status) => Self::Custom(anyhow::anyhow!(
+                "No trace available for transaction: {status:?}"
+            )),
+        }
+    }
+}
+
 pub async fn get_transaction_receipt(context: RpcContext, input: Input) -> Result<Output, Error> {
     let span = tracing::Span::current();
+    let traced = input.include_inner_calls || input.include_fee_trace;
 
-    tokio::task::spawn_blocking(move || {
-        let _g = span.enter();
-        let mut db = context
-            .storage
-            .connection()
-            .context("Opening database connection")?;
-
-        let db_tx = db.transaction().context("Creating database transaction")?;
-
-        // Check pending transactions.
-        let pending = context
-            .pending_data
-            .get(&db_tx)
-            .context("Querying pending data")?;
-
-        if let Some((transaction, (receipt, events))) = pending
-            .block
-            .transactions
-            .iter()
-            .zip(pending.block.transaction_receipts.iter())
-            .find_map(|(t, r)| (t.hash == input.transaction_hash).then(|| (t.clone(), r.clone())))
-        {
-            return Ok(Output::Pending {
-                receipt,
-                transaction,
-                events,
-            });
-        }
+    let (receipt, local) = {
+        let context = context.clone();
+        tokio::task::spawn_blocking(move || -> Result<_, Error> {
+            let _g = span.enter();
+            // Tracing executes against the execution database, so the receipt is read from
+            // it as well when a trace is requested.
+            let storage = if traced {
+                &context.execution_storage
+            } else {
+                &context.storage
+            };
+            let mut db = storage
+                .connection()
+                .context("Opening database connection")?;
 
-        let (transaction, receipt, events, block_number) = db_tx
-            .transaction_with_receipt(input.transaction_hash)
-            .context("Reading transaction receipt from database")?
+            let db_tx = db.transaction().context("Creating database transaction")?;
+
+            let pending = context
+                .pending_data
+                .get(&db_tx)
+                .context("Querying pending data")?;
+
+            let receipt = read_receipt(
+                &db_tx,
+                &pending,
+                input.transaction_hash,
+                input.include_data_gas,
+            )?
             .ok_or(Error::TxnHashNotFound)?;
+            // The receipt and the trace are read using the same database transaction so
+            // that they cannot disagree about the state of the chain.
+            let local = if traced {
+                Some(trace_locally(
+                    &context,
+                    &db_tx,
+                    &pending,
+                    input.transaction_hash,
+                )?)
+            } else {
+                None
+            };
 
-        let block_hash = db_tx
-            .block_hash(block_number.into())
-            .context("Querying block hash")?
-            .context("Block hash info missing")?;
+            Ok((receipt, local))
+        })
+        .await
+        .context("Joining blocking task")??
+    };
 
-        let l1_accepted = db_tx
-            .block_is_l1_accepted(block_number.into())
-            .context("Querying block status")?;
+    let trace = match local {
+        // Transactions of old blocks are traced by the gateway, which has to happen
+        // outside of the blocking task.
+        Some(local) => Some(
+            complete_trace(&context, input.transaction_hash, local)
+                .await?
+                .trace,
+        ),
+        None => None,
+    };
 
-        let finality = if l1_accepted {
-            dto::TxnFinalityStatus::AcceptedOnL1
-        } else {
-            dto::TxnFinalityStatus::AcceptedOnL2
-        };
+    Ok(Output {
+        receipt,
+        trace,
+        include_inner_calls: input.include_inner_calls,
+        include_fee_trace: input.include_fee_trace,
+    })
+}
 
-        Ok(Output::Full {
-            transaction,
+/// Reads the receipt of a transaction, which is either part of `pending` or of
+/// a block stored in the database.
+///
+/// The data gas price is only looked up if `include_data_gas` is set.
+pub(crate) fn read_receipt(
+    db_tx: &pathfinder_storage::Transaction<'_>,
+    pending: &PendingData,
+    transaction_hash: TransactionHash,
+    include_data_gas: bool,
+) -> anyhow::Result<Option<TransactionReceipt>> {
+    // Check pending transactions.
+    if let Some((transaction, (receipt, events))) = pending
+        .block
+        .transactions
+        .iter()
+        .zip(pending.block.transaction_receipts.iter())
+        .find_map(|(t, r)| (t.hash == transaction_hash).then(|| (t.clone(), r.clone())))
+    {
+        let header = pending.header();
+        let data_gas_price = include_data_gas
+            .then(|| {
+                dto::blob_data_gas_price(
+                    header.l1_da_mode,
+                    header.eth_l1_data_gas_price,
+                    header.strk_l1_data_gas_price,
+                    transaction.version(),
+                )
+            })
+            .flatten();
+
+        return Ok(Some(TransactionReceipt::Pending {
             receipt,
+            transaction,
             events,
-            block_hash,
-            block_number,
-            finality,
+            data_gas_price,
+        }));
+    }
+
+    let Some((transaction, receipt, events, block_number)) = db_tx
+        .transaction_with_receipt(transaction_hash)
+        .context("Reading transaction receipt from database")?
+    else {
+        return Ok(None);
+    };
+
+    let header = db_tx
+        .block_header(block_number.into())
+        .context("Querying block header")?
+        .context("Block header missing")?;
+    let data_gas_price = include_data_gas
+        .then(|| {
+            dto::blob_data_gas_price(
+                header.l1_da_mode,
+                header.eth_l1_data_gas_price,
+                header.strk_l1_data_gas_price,
+                transaction.version(),
+            )
         })
-    })
-    .await
-    .context("Joining blocking task")?
+        .flatten();
+
+    let l1_accepted = db_tx
+        .block_is_l1_accepted(block_number.into())
+        .context("Querying block status")?;
+
+    let finality = if l1_accepted {
+        dto::TxnFinalityStatus::AcceptedOnL1
+    } else {
+        dto::TxnFinalityStatus::AcceptedOnL2
+    };
+
+    Ok(Some(TransactionReceipt::Full {
+        transaction,
+        receipt,
+        events,
+        block_hash: header.hash,
+        block_number,
+        finality,
+        data_gas_price,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::L1DataAvailabilityMode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::serialize::{SerializeForVersion, Serializer};
+    use crate::dto::DeserializeForVersion;
+    use crate::method::trace_block_transactions::tests::setup_multi_tx_trace_test;
+    use crate::method::trace_transaction::trace_transaction;
+    use crate::v06::method::trace_transaction::TraceTransactionInput;
+    use crate::RpcVersion;
+
+    #[rstest::rstest]
+    #[case::default(json!({ "transaction_hash": "0x1" }), false, false, false)]
+    #[case::include_inner_calls(
+        json!({ "transaction_hash": "0x1", "include_inner_calls": true }),
+        true,
+        false,
+        false
+    )]
+    #[case::include_fee_trace(
+        json!({ "transaction_hash": "0x1", "include_fee_trace": true }),
+        false,
+        true,
+        false
+    )]
+    #[case::include_data_gas(
+        json!({ "transaction_hash": "0x1", "include_data_gas": true }),
+        false,
+        false,
+        true
+    )]
+    #[test]
+    fn parsing(
+        #[case] json: serde_json::Value,
+        #[case] include_inner_calls: bool,
+        #[case] include_fee_trace: bool,
+        #[case] include_data_gas: bool,
+    ) {
+        let input = Input::deserialize(crate::dto::Value::new(json, RpcVersion::V08)).unwrap();
+
+        assert_eq!(input.transaction_hash, transaction_hash!("0x1"));
+        assert_eq!(input.include_inner_calls, include_inner_calls);
+        assert_eq!(input.include_fee_trace, include_fee_trace);
+        assert_eq!(input.include_data_gas, include_data_gas);
+    }
+
+    #[tokio::test]
+    async fn messages_sent_include_full_payload() {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            transaction_hash: transaction_hash_bytes!(b"txn 6"),
+            include_inner_calls: false,
+            include_fee_trace: false,
+            include_data_gas: false,
+        };
+
+        let output = get_transaction_receipt(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::V07))
+            .unwrap();
+
+        assert_eq!(
+            output["messages_sent"],
+            json!([{
+                "from_address": "0xcafebabe",
+                "payload": ["0x1", "0x2", "0x3"],
+                "to_address": "0x0",
+            }])
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::l1_accepted(transaction_hash_bytes!(b"txn 0"), block_hash_bytes!(b"genesis"), 0)]
+    #[case::l2_accepted(transaction_hash_bytes!(b"txn 1"), block_hash_bytes!(b"block 1"), 1)]
+    #[case::latest(transaction_hash_bytes!(b"txn 3"), block_hash_bytes!(b"latest"), 2)]
+    #[tokio::test]
+    async fn finalized_receipts_include_block_info(
+        #[case] transaction_hash: TransactionHash,
+        #[case] block_hash: BlockHash,
+        #[case] block_number: u64,
+    ) {
+        for version in [RpcVersion::V06, RpcVersion::V07, RpcVersion::V08] {
+            let context = RpcContext::for_tests();
+            let input = Input {
+                transaction_hash,
+                include_inner_calls: false,
+                include_fee_trace: false,
+                include_data_gas: false,
+            };
+
+            let output = get_transaction_receipt(context, input)
+                .await
+                .unwrap()
+                .serialize(Serializer::new(version))
+                .unwrap();
+
+            let expected_hash = dto::BlockHash(&block_hash)
+                .serialize(Serializer::new(version))
+                .unwrap();
+            assert_eq!(output["block_hash"], expected_hash, "{version:?}");
+            assert_eq!(output["block_number"], json!(block_number), "{version:?}");
+        }
+    }
+
+    /// Events are part of the receipt itself in all supported versions of the
+    /// specification, and are not nested in any other structure.
+    #[rstest::rstest]
+    #[case::v06(RpcVersion::V06)]
+    #[case::v07(RpcVersion::V07)]
+    #[case::v08(RpcVersion::V08)]
+    #[tokio::test]
+    async fn events_are_top_level(#[case] version: RpcVersion) {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            transaction_hash: transaction_hash_bytes!(b"txn 0"),
+            include_inner_calls: false,
+            include_fee_trace: false,
+            include_data_gas: false,
+        };
+
+        let output = get_transaction_receipt(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(version))
+            .unwrap();
+
+        let events = output["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        let mut fields = events[0]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        fields.sort();
+        assert_eq!(fields, ["data", "from_address", "keys"]);
+
+        for (key, value) in output.as_object().unwrap() {
+            assert!(
+                value.get("events").is_none(),
+                "Events nested in {key}: {value}"
+            );
+        }
+    }
+
+    /// Invoke v0 receipts have the same shape as those of later invoke
+    /// versions. The v0 specific `entry_point_selector` and `calldata` are
+    /// transaction fields and are not part of the receipt.
+    #[rstest::rstest]
+    #[case::v06(RpcVersion::V06)]
+    #[case::v07(RpcVersion::V07)]
+    #[case::v08(RpcVersion::V08)]
+    #[tokio::test]
+    async fn invoke_v0_receipt(#[case] version: RpcVersion) {
+        use pathfinder_common::transaction::TransactionVariant;
+
+        let context = RpcContext::for_tests();
+        let transaction_hash = transaction_hash_bytes!(b"txn 0");
+        let input = Input {
+            transaction_hash,
+            include_inner_calls: false,
+            include_fee_trace: false,
+            include_data_gas: false,
+        };
+
+        let output = get_transaction_receipt(context, input).await.unwrap();
+        let TransactionReceipt::Full { transaction, .. } = &output.receipt else {
+            panic!("Expected a finalized receipt");
+        };
+        assert!(matches!(
+            transaction.variant,
+            TransactionVariant::InvokeV0(_)
+        ));
+
+        let output = output.serialize(Serializer::new(version)).unwrap();
+
+        let mut fields = output
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        fields.sort();
+        assert_eq!(
+            fields,
+            [
+                "actual_fee",
+                "block_hash",
+                "block_number",
+                "events",
+                "execution_resources",
+                "execution_status",
+                "finality_status",
+                "messages_sent",
+                "transaction_hash",
+                "type",
+            ]
+        );
+        assert_eq!(output["type"], json!("INVOKE"));
+        assert_eq!(output["actual_fee"]["unit"], json!("WEI"));
+        assert_eq!(output["finality_status"], json!("ACCEPTED_ON_L1"));
+        assert_eq!(output["execution_status"], json!("SUCCEEDED"));
+        assert_eq!(
+            output["transaction_hash"],
+            dto::TxnHash(&transaction_hash)
+                .serialize(Serializer::new(version))
+                .unwrap()
+        );
+    }
+
+    /// Stores block 3 using `l1_da_mode`, containing an invoke v1 transaction
+    /// which pays its fee in WEI and an invoke v3 transaction which pays in
+    /// FRI. Both consumed 0x80 data gas.
+    fn context_with_da_mode(l1_da_mode: L1DataAvailabilityMode) -> RpcContext {
+        use pathfinder_common::receipt::{ExecutionResources, L1Gas};
+        use pathfinder_common::transaction::TransactionVariant;
+        use pathfinder_common::TransactionIndex;
+
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let header = pathfinder_common::BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .eth_l1_data_gas_price(GasPrice(0x10))
+            .strk_l1_data_gas_price(GasPrice(0x20))
+            .l1_da_mode(l1_da_mode)
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+
+        let body = [
+            (
+                transaction_hash_bytes!(b"data gas v1"),
+                TransactionVariant::InvokeV1(Default::default()),
+            ),
+            (
+                transaction_hash_bytes!(b"data gas v3"),
+                TransactionVariant::InvokeV3(Default::default()),
+            ),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (hash, variant))| {
+            let receipt = Receipt {
+                transaction_hash: hash,
+                transaction_index: TransactionIndex::new_or_panic(i as u64),
+                execution_resources: ExecutionResources {
+                    data_availability: L1Gas {
+                        l1_gas: 0,
+                        l1_data_gas: 0x80,
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            (Transaction { hash, variant }, receipt)
+        })
+        .collect::<Vec<_>>();
+        tx.insert_transaction_data(header.number, &body, Some(&[vec![], vec![]]))
+            .unwrap();
+        tx.commit().unwrap();
+
+        context
+    }
+
+    #[rstest::rstest]
+    #[case::v06(RpcVersion::V06)]
+    #[case::v07(RpcVersion::V07)]
+    #[case::v08(RpcVersion::V08)]
+    #[tokio::test]
+    async fn blob_data_gas(#[case] version: RpcVersion) {
+        let context = context_with_da_mode(L1DataAvailabilityMode::Blob);
+
+        for (transaction_hash, data_gas_price) in [
+            (transaction_hash_bytes!(b"data gas v1"), "0x10"),
+            (transaction_hash_bytes!(b"data gas v3"), "0x20"),
+        ] {
+            let input = Input {
+                transaction_hash,
+                include_inner_calls: false,
+                include_fee_trace: false,
+                include_data_gas: true,
+            };
+
+            let output = get_transaction_receipt(context.clone(), input)
+                .await
+                .unwrap()
+                .serialize(Serializer::new(version))
+                .unwrap();
+
+            let resources = &output["execution_resources"];
+            assert_eq!(resources["data_gas_consumed"], json!(0x80), "{version:?}");
+            assert_eq!(
+                resources["data_gas_price"],
+                json!(data_gas_price),
+                "{version:?}"
+            );
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::calldata(L1DataAvailabilityMode::Calldata, true)]
+    #[case::not_requested(L1DataAvailabilityMode::Blob, false)]
+    #[tokio::test]
+    async fn no_data_gas(
+        #[case] l1_da_mode: L1DataAvailabilityMode,
+        #[case] include_data_gas: bool,
+        #[values(RpcVersion::V06, RpcVersion::V07, RpcVersion::V08)] version: RpcVersion,
+    ) {
+        let context = context_with_da_mode(l1_da_mode);
+
+        for transaction_hash in [
+            transaction_hash_bytes!(b"data gas v1"),
+            transaction_hash_bytes!(b"data gas v3"),
+        ] {
+            let input = Input {
+                transaction_hash,
+                include_inner_calls: false,
+                include_fee_trace: false,
+                include_data_gas,
+            };
+
+            let output = get_transaction_receipt(context.clone(), input)
+                .await
+                .unwrap()
+                .serialize(Serializer::new(version))
+                .unwrap();
+
+            let resources = output["execution_resources"].as_object().unwrap();
+            assert!(!resources.contains_key("data_gas_consumed"), "{version:?}");
+            assert!(!resources.contains_key("data_gas_price"), "{version:?}");
+        }
+    }
+
+    /// Flattens the invocations of a serialized trace, with nested calls
+    /// following the call that made them.
+    fn expected_inner_calls(trace: &serde_json::Value) -> Vec<serde_json::Value> {
+        fn flatten(invocation: &serde_json::Value, calls: &mut Vec<serde_json::Value>) {
+            // Reverted executions only have a revert reason.
+            if invocation.get("contract_address").is_none() {
+                return;
+            }
+            calls.push(json!({
+                "contract_address": invocation["contract_address"],
+                "entry_point_selector": invocation["entry_point_selector"],
+                "execution_resources": invocation["execution_resources"],
+            }));
+            for inner in invocation["calls"].as_array().unwrap() {
+                flatten(inner, calls);
+            }
+        }
+
+        let top_level: &[&str] = match trace["type"].as_str().unwrap() {
+            "DECLARE" => &["validate_invocation", "fee_transfer_invocation"],
+            "DEPLOY_ACCOUNT" => &[
+                "constructor_invocation",
+                "validate_invocation",
+                "fee_transfer_invocation",
+            ],
+            "INVOKE" => &[
+                "validate_invocation",
+                "execute_invocation",
+                "fee_transfer_invocation",
+            ],
+            "L1_HANDLER" => &["function_invocation"],
+            other => panic!("Unexpected trace type {other}"),
+        };
+
+        let mut calls = Vec::new();
+        for field in top_level {
+            if let Some(invocation) = trace.get(field) {
+                flatten(invocation, &mut calls);
+            }
+        }
+        calls
+    }
+
+    #[rstest::rstest]
+    #[case::v07(RpcVersion::V07)]
+    #[case::v08(RpcVersion::V08)]
+    #[tokio::test]
+    async fn inner_calls(#[case] version: RpcVersion) {
+        let (context, _, traces) = setup_multi_tx_trace_test().await.unwrap();
+
+        let mut nested_calls = false;
+        for trace in traces {
+            let transaction_hash = trace.transaction_hash;
+
+            let without = get_transaction_receipt(
+                context.clone(),
+                Input {
+                    transaction_hash,
+                    include_inner_calls: false,
+                    include_fee_trace: false,
+                    include_data_gas: false,
+                },
+            )
+            .await
+            .unwrap()
+            .serialize(Serializer::new(version))
+            .unwrap();
+            assert!(without.get("inner_calls").is_none());
+
+            let mut with = get_transaction_receipt(
+                context.clone(),
+                Input {
+                    transaction_hash,
+                    include_inner_calls: true,
+                    include_fee_trace: false,
+                    include_data_gas: false,
+                },
+            )
+            .await
+            .unwrap()
+            .serialize(Serializer::new(version))
+            .unwrap();
+
+            let trace =
+                trace_transaction(context.clone(), TraceTransactionInput { transaction_hash })
+                    .await
+                    .unwrap()
+                    .serialize(Serializer::new(version))
+                    .unwrap();
+            let expected = expected_inner_calls(&trace);
+
+            let inner_calls = with.as_object_mut().unwrap().remove("inner_calls").unwrap();
+            nested_calls |= trace.as_object().unwrap().values().any(|invocation| {
+                invocation["calls"]
+                    .as_array()
+                    .is_some_and(|calls| !calls.is_empty())
+            });
+            assert_eq!(inner_calls, json!(expected));
+            // Apart from the inner calls the receipt is unchanged.
+            assert_eq!(with, without);
+        }
+
+        assert!(nested_calls, "Expected a transaction with nested calls");
+    }
+
+    #[rstest::rstest]
+    #[case::v07(RpcVersion::V07)]
+    #[case::v08(RpcVersion::V08)]
+    #[tokio::test]
+    async fn fee_trace(#[case] version: RpcVersion) {
+        let (context, _, traces) = setup_multi_tx_trace_test().await.unwrap();
+
+        for trace in traces {
+            let transaction_hash = trace.transaction_hash;
+            let input = |include_fee_trace| Input {
+                transaction_hash,
+                include_inner_calls: false,
+                include_fee_trace,
+                include_data_gas: false,
+            };
+
+            let without = get_transaction_receipt(context.clone(), input(false))
+                .await
+                .unwrap()
+                .serialize(Serializer::new(version))
+                .unwrap();
+            assert!(without.get("fee_transfer_invocation").is_none());
+
+            let mut with = get_transaction_receipt(context.clone(), input(true))
+                .await
+                .unwrap()
+                .serialize(Serializer::new(version))
+                .unwrap();
+
+            let trace =
+                trace_transaction(context.clone(), TraceTransactionInput { transaction_hash })
+                    .await
+                    .unwrap()
+                    .serialize(Serializer::new(version))
+                    .unwrap();
+
+            let fee_transfer_invocation = with
+                .as_object_mut()
+                .unwrap()
+                .remove("fee_transfer_invocation");
+            assert!(fee_transfer_invocation.is_some());
+            assert_eq!(
+                fee_transfer_invocation.as_ref(),
+                trace.get("fee_transfer_invocation")
+            );
+            // Apart from the fee transfer the receipt is unchanged.
+            assert_eq!(with, without);
+        }
+    }
 }
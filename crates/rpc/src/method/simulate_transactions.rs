@@ -46,8 +46,9 @@ pub async fn simulate_transactions(
             other => {
                 let block_id = other.try_into().expect("Only pending should fail");
 
-                let header = db
-                    .block_header(block_id)
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_id)
                     .context("Fetching block header")?
                     .ok_or(SimulateTransactionError::BlockNotFound)?;
 
@@ -324,11 +325,7 @@ pub(crate) mod tests {
         let expected = serde_json::to_value(expected).unwrap();
 
         let result = simulate_transactions(context, input).await.expect("result");
-        let result = result
-            .serialize(Serializer {
-                version: RpcVersion::V07,
-            })
-            .unwrap();
+        let result = result.serialize(Serializer::new(RpcVersion::V07)).unwrap();
         pretty_assertions_sorted::assert_eq!(result, expected);
     }
 
@@ -376,9 +373,7 @@ pub(crate) mod tests {
         use crate::method::get_state_update::types::{StorageDiff, StorageEntry};
 
         pretty_assertions_sorted::assert_eq!(
-            result.serialize(Serializer {
-                version: RpcVersion::V07,
-            }).unwrap(),
+            result.serialize(Serializer::new(RpcVersion::V07)).unwrap(),
             serde_json::to_value(
                 vec![SimulatedTransaction {
                     fee_estimation: FeeEstimate {
@@ -1606,11 +1601,7 @@ pub(crate) mod tests {
         let result = simulate_transactions(context, input).await.unwrap();
 
         pretty_assertions_sorted::assert_eq!(
-            result
-                .serialize(Serializer {
-                    version: RpcVersion::V07
-                })
-                .unwrap(),
+            result.serialize(Serializer::new(RpcVersion::V07)).unwrap(),
             serde_json::to_value(vec![
                 fixtures::expected_output_0_13_1_1::declare(
                     account_contract_address,
@@ -1663,11 +1654,7 @@ pub(crate) mod tests {
         let result = simulate_transactions(context, input).await.unwrap();
 
         pretty_assertions_sorted::assert_eq!(
-            result
-                .serialize(Serializer {
-                    version: RpcVersion::V07
-                })
-                .unwrap(),
+            result.serialize(Serializer::new(RpcVersion::V07)).unwrap(),
             serde_json::to_value(vec![
                 fixtures::expected_output_0_13_1_1::declare_without_fee_transfer(
                     account_contract_address
@@ -1716,11 +1703,7 @@ pub(crate) mod tests {
         let result = simulate_transactions(context, input).await.unwrap();
 
         pretty_assertions_sorted::assert_eq!(
-            result
-                .serialize(Serializer {
-                    version: RpcVersion::V07
-                })
-                .unwrap(),
+            result.serialize(Serializer::new(RpcVersion::V07)).unwrap(),
             serde_json::to_value(vec![
                 fixtures::expected_output_0_13_1_1::declare_without_validate(
                     account_contract_address,
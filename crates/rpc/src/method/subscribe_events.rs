@@ -3,12 +3,17 @@ use std::sync::Arc;
 use axum::async_trait;
 use pathfinder_common::{BlockId, BlockNumber, ContractAddress, EventKey};
 use pathfinder_storage::EVENT_KEY_FILTER_LIMIT;
-use tokio::sync::mpsc;
 
 use super::REORG_SUBSCRIPTION_NAME;
 use crate::context::RpcContext;
 use crate::error::ApplicationError;
-use crate::jsonrpc::{CatchUp, RpcError, RpcSubscriptionFlow, SubscriptionMessage};
+use crate::jsonrpc::{
+    CatchUp,
+    RpcError,
+    RpcSubscriptionFlow,
+    SubscriptionMessage,
+    SubscriptionMessageSender,
+};
 use crate::method::get_events::EmittedEvent;
 use crate::Reorg;
 
@@ -136,7 +141,7 @@ impl RpcSubscriptionFlow for SubscribeEvents {
     async fn subscribe(
         state: RpcContext,
         params: Self::Params,
-        tx: mpsc::Sender<SubscriptionMessage<Self::Notification>>,
+        tx: SubscriptionMessageSender<Self::Notification>,
     ) -> Result<(), RpcError> {
         let mut blocks = state.notifications.l2_blocks.subscribe();
         let mut reorgs = state.notifications.reorgs.subscribe();
@@ -522,6 +527,68 @@ mod tests {
         assert!(sender_rx.is_empty());
     }
 
+    #[tokio::test]
+    async fn filter_without_historical_matches() {
+        let router = setup(2000).await;
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router.clone(), sender_tx, receiver_rx);
+        // Only the next block emits events from this address.
+        let params = serde_json::json!(
+            {
+                "block_id": {"block_number": 0},
+                "from_address": "0x7d0",
+            }
+        );
+        receiver_tx
+            .send(Ok(Message::Text(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "starknet_subscribeEvents",
+                    "params": params
+                })
+                .to_string(),
+            )))
+            .await
+            .unwrap();
+        let res = sender_rx.recv().await.unwrap().unwrap();
+        let subscription_id = match res {
+            Message::Text(json) => {
+                let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+                assert_eq!(json["jsonrpc"], "2.0");
+                assert_eq!(json["id"], 1);
+                json["result"].as_u64().unwrap()
+            }
+            _ => panic!("Expected text message"),
+        };
+        retry(|| {
+            router
+                .context
+                .notifications
+                .l2_blocks
+                .send(sample_block(0x8f).into())
+        })
+        .await
+        .unwrap();
+        router
+            .context
+            .notifications
+            .l2_blocks
+            .send(sample_block(0x7d0).into())
+            .unwrap();
+        // The catch-up phase sends nothing, so the first notification is the live
+        // event.
+        let expected = sample_event_message(0x7d0, subscription_id);
+        let event = sender_rx.recv().await.unwrap().unwrap();
+        let json: serde_json::Value = match event {
+            Message::Text(json) => serde_json::from_str(&json).unwrap(),
+            _ => panic!("Expected text message"),
+        };
+        assert_eq!(json, expected);
+        assert!(sender_rx.is_empty());
+    }
+
     #[tokio::test]
     async fn too_many_keys_filter() {
         let router = setup(2000).await;
@@ -721,8 +788,14 @@ mod tests {
         let notifications = Notifications::default();
         let ctx = RpcContext {
             cache: Default::default(),
+            event_page_cache: crate::method::get_events::EventPageCache::new(&notifications),
+            header_cache: crate::header_cache::BlockHeaderCache::new(
+                1024.try_into().unwrap(),
+                &notifications,
+            ),
             storage,
             execution_storage: StorageBuilder::in_memory().unwrap(),
+            event_shards: Vec::new(),
             pending_data: PendingWatcher::new(pending_data),
             sync_status: SyncState {
                 status: Syncing::False(false).into(),
@@ -740,6 +813,18 @@ mod tests {
                 get_events_max_blocks_to_scan: 1024.try_into().unwrap(),
                 get_events_max_event_filters_to_load: 1.try_into().unwrap(),
                 custom_versioned_constants: None,
+                large_block_threshold: 10_000,
+                class_fallback_url: None,
+                wait_for_block_timeout: std::time::Duration::from_secs(30),
+                validate_class_hash: false,
+                prefetch_next_event_page: false,
+                subscription_send_timeout: std::time::Duration::from_secs(30),
+                websocket_keepalive_interval: None,
+                subscription_catchup_concurrency: 1.try_into().unwrap(),
+                max_subscriptions_per_connection: 100.try_into().unwrap(),
+                block_header_cache_size: 1024.try_into().unwrap(),
+                websocket_max_message_size_bytes: 10 * 1024 * 1024,
+                approved_class_hashes: Vec::new(),
             },
         };
         v08::register_routes().build(ctx)
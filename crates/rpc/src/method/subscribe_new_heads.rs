@@ -2,12 +2,19 @@ use std::sync::Arc;
 
 use axum::async_trait;
 use pathfinder_common::{BlockId, BlockNumber};
-use tokio::sync::mpsc;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::TryRecvError;
 
 use super::REORG_SUBSCRIPTION_NAME;
 use crate::context::RpcContext;
 use crate::error::ApplicationError;
-use crate::jsonrpc::{CatchUp, RpcError, RpcSubscriptionFlow, SubscriptionMessage};
+use crate::jsonrpc::{
+    CatchUp,
+    RpcError,
+    RpcSubscriptionFlow,
+    SubscriptionMessage,
+    SubscriptionMessageSender,
+};
 use crate::Reorg;
 
 pub struct SubscribeNewHeads;
@@ -15,6 +22,15 @@ pub struct SubscribeNewHeads;
 #[derive(Debug, Clone)]
 pub struct Params {
     block_id: Option<BlockId>,
+    /// Only blocks with at least this many transactions are sent.
+    min_transaction_count: Option<u32>,
+}
+
+impl Params {
+    fn is_included(&self, header: &pathfinder_common::BlockHeader) -> bool {
+        self.min_transaction_count
+            .map_or(true, |min| header.transaction_count >= min as usize)
+    }
 }
 
 impl crate::dto::DeserializeForVersion for Option<Params> {
@@ -26,6 +42,7 @@ impl crate::dto::DeserializeForVersion for Option<Params> {
         value.deserialize_map(|value| {
             Ok(Some(Params {
                 block_id: value.deserialize_optional_serde("block_id")?,
+                min_transaction_count: value.deserialize_optional_serde("min_transaction_count")?,
             }))
         })
     }
@@ -74,7 +91,7 @@ impl RpcSubscriptionFlow for SubscribeNewHeads {
 
     async fn catch_up(
         state: &RpcContext,
-        _params: &Self::Params,
+        params: &Self::Params,
         from: BlockNumber,
         to: BlockNumber,
     ) -> Result<CatchUp<Self::Notification>, RpcError> {
@@ -86,8 +103,15 @@ impl RpcSubscriptionFlow for SubscribeNewHeads {
         })
         .await
         .map_err(|e| RpcError::InternalError(e.into()))??;
-        let messages: Vec<_> = headers
+        // Filtered out blocks have still been caught up to.
+        let last_block = headers.last().map(|header| header.number);
+        let messages = headers
             .into_iter()
+            .filter(|header| {
+                params
+                    .as_ref()
+                    .map_or(true, |params| params.is_included(header))
+            })
             .map(|header| {
                 let block_number = header.number;
                 SubscriptionMessage {
@@ -97,25 +121,45 @@ impl RpcSubscriptionFlow for SubscribeNewHeads {
                 }
             })
             .collect();
-        let last_block = messages.last().map(|m| m.block_number);
         Ok(CatchUp {
             messages,
             last_block,
         })
     }
 
+    fn should_notify(params: &Self::Params, notification: &Self::Notification) -> bool {
+        match (params, notification) {
+            (Some(params), Notification::BlockHeader(header)) => params.is_included(header),
+            _ => true,
+        }
+    }
+
     async fn subscribe(
         state: RpcContext,
         _params: Self::Params,
-        tx: mpsc::Sender<SubscriptionMessage<Self::Notification>>,
+        tx: SubscriptionMessageSender<Self::Notification>,
     ) -> Result<(), RpcError> {
         let mut headers = state.notifications.block_headers.subscribe();
         let mut reorgs = state.notifications.reorgs.subscribe();
         loop {
             tokio::select! {
+                // A reorg is published before the headers replacing the reorged blocks, so it
+                // must be forwarded first when both are ready.
+                biased;
                 reorg = reorgs.recv() => {
                     match reorg {
                         Ok(reorg) => {
+                            let queued = match new_chain_headers(&mut headers, &reorg) {
+                                Ok(queued) => queued,
+                                Err(e) => {
+                                    tracing::debug!(
+                                        "Error receiving block header from notifications channel, \
+                                         node might be lagging: {:?}",
+                                        e
+                                    );
+                                    break;
+                                }
+                            };
                             let block_number = reorg.first_block_number;
                             if tx.send(SubscriptionMessage {
                                 notification: Notification::Reorg(reorg),
@@ -124,6 +168,16 @@ impl RpcSubscriptionFlow for SubscribeNewHeads {
                             }).await.is_err() {
                                 break;
                             }
+                            for header in queued {
+                                let block_number = header.number;
+                                if tx.send(SubscriptionMessage {
+                                    notification: Notification::BlockHeader(header),
+                                    block_number,
+                                    subscription_name: SUBSCRIPTION_NAME,
+                                }).await.is_err() {
+                                    return Ok(());
+                                }
+                            }
                         }
                         Err(e) => {
                             tracing::debug!(
@@ -163,6 +217,35 @@ impl RpcSubscriptionFlow for SubscribeNewHeads {
     }
 }
 
+/// Returns the headers which are queued behind a reorg, without those of the
+/// reorged blocks.
+///
+/// Headers of the reorged blocks may have been published before the reorg
+/// without having been forwarded yet. They end with the reorged head, so
+/// everything up to and including it is dropped.
+fn new_chain_headers(
+    headers: &mut broadcast::Receiver<Arc<pathfinder_common::BlockHeader>>,
+    reorg: &Reorg,
+) -> Result<Vec<Arc<pathfinder_common::BlockHeader>>, TryRecvError> {
+    let mut queued = Vec::new();
+    loop {
+        match headers.try_recv() {
+            Ok(header) => queued.push(header),
+            Err(TryRecvError::Empty) => break,
+            Err(e) => return Err(e),
+        }
+    }
+
+    if let Some(reorged_head) = queued
+        .iter()
+        .position(|header| header.hash == reorg.last_block_hash)
+    {
+        queued.drain(..=reorged_head);
+    }
+
+    Ok(queued)
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -199,11 +282,121 @@ mod tests {
         happy_path_test(2 * (CATCH_UP_BATCH_SIZE + 1)).await;
     }
 
+    #[tokio::test]
+    async fn happy_path_with_concurrent_catch_up() {
+        for concurrency in [2, 4, 8] {
+            let mut router = setup(2000).await;
+            router.context.config.subscription_catchup_concurrency =
+                concurrency.try_into().unwrap();
+            happy_path_test_on(router, 2000).await;
+        }
+    }
+
     #[tokio::test]
     async fn happy_path_with_no_historic_blocks() {
         happy_path_test(0).await;
     }
 
+    #[tokio::test]
+    async fn catch_up_and_live_notifications_have_same_shape() {
+        let router = setup(1).await;
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router.clone(), sender_tx, receiver_rx);
+        receiver_tx
+            .send(Ok(Message::Text(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "starknet_subscribeNewHeads",
+                    "params": {"block_id": {"block_number": 0}}
+                })
+                .to_string(),
+            )))
+            .await
+            .unwrap();
+        let subscription_id = recv_json(&mut sender_rx).await["result"].as_u64().unwrap();
+
+        let catch_up = recv_json(&mut sender_rx).await;
+        retry(|| {
+            router
+                .context
+                .notifications
+                .block_headers
+                .send(sample_header(1).into())
+        })
+        .await
+        .unwrap();
+        let live = recv_json(&mut sender_rx).await;
+
+        assert_eq!(catch_up["params"]["subscription_id"], subscription_id);
+        assert_eq!(live["params"]["subscription_id"], subscription_id);
+        assert_eq!(shape(&catch_up), shape(&live));
+    }
+
+    #[tokio::test]
+    async fn min_transaction_count_filters_empty_blocks() {
+        // Blocks with an odd number have a transaction, the rest are empty.
+        let header = |block_number: u64| BlockHeader {
+            transaction_count: (block_number % 2) as usize,
+            ..sample_header(block_number)
+        };
+        let router = setup(0).await;
+        let storage = router.context.storage.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = storage.connection().unwrap();
+            let db = conn.transaction().unwrap();
+            for i in 0..6 {
+                db.insert_block_header(&header(i)).unwrap();
+            }
+            db.commit().unwrap();
+        })
+        .await
+        .unwrap();
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router.clone(), sender_tx, receiver_rx);
+        receiver_tx
+            .send(Ok(Message::Text(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "starknet_subscribeNewHeads",
+                    "params": {"block_id": {"block_number": 0}, "min_transaction_count": 1}
+                })
+                .to_string(),
+            )))
+            .await
+            .unwrap();
+        let subscription_id = recv_json(&mut sender_rx).await["result"].as_u64().unwrap();
+
+        for i in [1, 3, 5] {
+            assert_eq!(
+                recv_json(&mut sender_rx).await,
+                sample_new_heads_message(i, subscription_id)
+            );
+        }
+        // Empty blocks must not break the continuity of active updates either.
+        for i in 6..10 {
+            retry(|| {
+                router
+                    .context
+                    .notifications
+                    .block_headers
+                    .send(header(i).into())
+            })
+            .await
+            .unwrap();
+            if i % 2 == 1 {
+                assert_eq!(
+                    recv_json(&mut sender_rx).await,
+                    sample_new_heads_message(i, subscription_id)
+                );
+            }
+        }
+        assert!(sender_rx.is_empty());
+    }
+
     #[tokio::test]
     async fn reorg() {
         let (_, mut rx, subscription_id, router) = happy_path_test(0).await;
@@ -244,6 +437,96 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn reorg_followed_by_replacement_headers() {
+        let (_, mut rx, subscription_id, router) = happy_path_test(0).await;
+        let notifications = &router.context.notifications;
+        let replacement = |block_number: u64| BlockHeader {
+            hash: BlockHash(Felt::from_u64(block_number + 100)),
+            ..sample_header(block_number)
+        };
+
+        // Blocks 7 to 9 are replaced. The reorg and the new headers are all queued
+        // before the subscription gets to process any of them.
+        notifications
+            .reorgs
+            .send(
+                Reorg {
+                    first_block_number: BlockNumber::new_or_panic(7),
+                    first_block_hash: BlockHash(Felt::from_u64(7)),
+                    last_block_number: BlockNumber::new_or_panic(9),
+                    last_block_hash: BlockHash(Felt::from_u64(9)),
+                }
+                .into(),
+            )
+            .unwrap();
+        for i in 7..10 {
+            notifications
+                .block_headers
+                .send(replacement(i).into())
+                .unwrap();
+        }
+
+        let json = recv_json(&mut rx).await;
+        assert_eq!(json["method"], "starknet_subscriptionReorg");
+        assert_eq!(json["params"]["subscription_id"], subscription_id.0);
+        assert_eq!(json["params"]["result"]["first_block_number"], 7);
+        assert_eq!(json["params"]["result"]["last_block_number"], 9);
+        for i in 7..10 {
+            let mut expected = sample_new_heads_message(i, subscription_id.0.into());
+            expected["params"]["result"]["block_hash"] = serde_json::json!(Felt::from_u64(i + 100));
+            assert_eq!(recv_json(&mut rx).await, expected);
+        }
+        assert!(rx.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reorged_headers_queued_before_reorg_are_dropped() {
+        let (_, mut rx, subscription_id, router) = happy_path_test(0).await;
+        let notifications = &router.context.notifications;
+        let replacement = |block_number: u64| BlockHeader {
+            hash: BlockHash(Felt::from_u64(block_number + 100)),
+            ..sample_header(block_number)
+        };
+
+        // Blocks 10 to 12 are published and then reorged away before the
+        // subscription gets to forward them.
+        for i in 10..13 {
+            notifications
+                .block_headers
+                .send(sample_header(i).into())
+                .unwrap();
+        }
+        notifications
+            .reorgs
+            .send(
+                Reorg {
+                    first_block_number: BlockNumber::new_or_panic(10),
+                    first_block_hash: BlockHash(Felt::from_u64(10)),
+                    last_block_number: BlockNumber::new_or_panic(12),
+                    last_block_hash: BlockHash(Felt::from_u64(12)),
+                }
+                .into(),
+            )
+            .unwrap();
+        for i in 10..12 {
+            notifications
+                .block_headers
+                .send(replacement(i).into())
+                .unwrap();
+        }
+
+        let json = recv_json(&mut rx).await;
+        assert_eq!(json["method"], "starknet_subscriptionReorg");
+        assert_eq!(json["params"]["result"]["first_block_number"], 10);
+        for i in 10..12 {
+            let mut expected = sample_new_heads_message(i, subscription_id.0.into());
+            expected["params"]["result"]["block_hash"] = serde_json::json!(Felt::from_u64(i + 100));
+            assert_eq!(recv_json(&mut rx).await, expected);
+        }
+        assert!(rx.is_empty());
+    }
+
     #[tokio::test]
     async fn race_condition_with_historic_blocks() {
         let num_blocks = 1000;
@@ -530,8 +813,14 @@ mod tests {
         let notifications = Notifications::default();
         let ctx = RpcContext {
             cache: Default::default(),
+            event_page_cache: crate::method::get_events::EventPageCache::new(&notifications),
+            header_cache: crate::header_cache::BlockHeaderCache::new(
+                1024.try_into().unwrap(),
+                &notifications,
+            ),
             storage,
             execution_storage: StorageBuilder::in_memory().unwrap(),
+            event_shards: Vec::new(),
             pending_data: PendingWatcher::new(pending_data),
             sync_status: SyncState {
                 status: Syncing::False(false).into(),
@@ -549,6 +838,18 @@ mod tests {
                 get_events_max_blocks_to_scan: 1.try_into().unwrap(),
                 get_events_max_event_filters_to_load: 1.try_into().unwrap(),
                 custom_versioned_constants: None,
+                large_block_threshold: 10_000,
+                class_fallback_url: None,
+                wait_for_block_timeout: std::time::Duration::from_secs(30),
+                validate_class_hash: false,
+                prefetch_next_event_page: false,
+                subscription_send_timeout: std::time::Duration::from_secs(30),
+                websocket_keepalive_interval: None,
+                subscription_catchup_concurrency: 1.try_into().unwrap(),
+                max_subscriptions_per_connection: 100.try_into().unwrap(),
+                block_header_cache_size: 1024.try_into().unwrap(),
+                websocket_max_message_size_bytes: 10 * 1024 * 1024,
+                approved_class_hashes: Vec::new(),
             },
         };
         v08::register_routes().build(ctx)
@@ -562,7 +863,18 @@ mod tests {
         SubscriptionId,
         RpcRouter,
     ) {
-        let router = setup(num_blocks).await;
+        happy_path_test_on(setup(num_blocks).await, num_blocks).await
+    }
+
+    async fn happy_path_test_on(
+        router: RpcRouter,
+        num_blocks: u64,
+    ) -> (
+        mpsc::Sender<Result<Message, axum::Error>>,
+        mpsc::Receiver<Result<Message, RpcResponse>>,
+        SubscriptionId,
+        RpcRouter,
+    ) {
         let (sender_tx, mut sender_rx) = mpsc::channel(1024);
         let (receiver_tx, receiver_rx) = mpsc::channel(1024);
         handle_json_rpc_socket(router.clone(), sender_tx, receiver_rx);
@@ -666,6 +978,30 @@ mod tests {
         })
     }
 
+    async fn recv_json(rx: &mut mpsc::Receiver<Result<Message, RpcResponse>>) -> serde_json::Value {
+        match rx.recv().await.unwrap().unwrap() {
+            Message::Text(json) => serde_json::from_str(&json).unwrap(),
+            _ => panic!("Expected text message"),
+        }
+    }
+
+    /// Replaces the leaves of a JSON value with their type, keeping its
+    /// structure.
+    fn shape(value: &serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| (key.clone(), shape(value)))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+            serde_json::Value::Array(values) => values.iter().map(shape).collect(),
+            serde_json::Value::Null => "null".into(),
+            serde_json::Value::Bool(_) => "bool".into(),
+            serde_json::Value::Number(_) => "number".into(),
+            serde_json::Value::String(_) => "string".into(),
+        }
+    }
+
     // Retry to let other tasks make progress.
     async fn retry<T, E>(cb: impl Fn() -> Result<T, E>) -> Result<T, E>
     where
@@ -3,10 +3,14 @@ use std::collections::HashSet;
 use axum::async_trait;
 use pathfinder_common::transaction::Transaction;
 use pathfinder_common::{BlockNumber, ContractAddress, TransactionHash};
-use tokio::sync::mpsc;
 
 use crate::context::RpcContext;
-use crate::jsonrpc::{RpcError, RpcSubscriptionFlow, SubscriptionMessage};
+use crate::jsonrpc::{
+    RpcError,
+    RpcSubscriptionFlow,
+    SubscriptionMessage,
+    SubscriptionMessageSender,
+};
 
 pub struct SubscribePendingTransactions;
 
@@ -66,7 +70,7 @@ impl RpcSubscriptionFlow for SubscribePendingTransactions {
     async fn subscribe(
         state: RpcContext,
         params: Self::Params,
-        tx: mpsc::Sender<SubscriptionMessage<Self::Notification>>,
+        tx: SubscriptionMessageSender<Self::Notification>,
     ) -> Result<(), RpcError> {
         let params = params.unwrap_or_default();
         let mut pending_data = state.pending_data.0.clone();
@@ -475,8 +479,14 @@ mod tests {
         let notifications = Notifications::default();
         let ctx = RpcContext {
             cache: Default::default(),
+            event_page_cache: crate::method::get_events::EventPageCache::new(&notifications),
+            header_cache: crate::header_cache::BlockHeaderCache::new(
+                1024.try_into().unwrap(),
+                &notifications,
+            ),
             storage,
             execution_storage: StorageBuilder::in_memory().unwrap(),
+            event_shards: Vec::new(),
             pending_data: PendingWatcher::new(pending_data),
             sync_status: SyncState {
                 status: Syncing::False(false).into(),
@@ -494,6 +504,18 @@ mod tests {
                 get_events_max_blocks_to_scan: 1.try_into().unwrap(),
                 get_events_max_event_filters_to_load: 1.try_into().unwrap(),
                 custom_versioned_constants: None,
+                large_block_threshold: 10_000,
+                class_fallback_url: None,
+                wait_for_block_timeout: std::time::Duration::from_secs(30),
+                validate_class_hash: false,
+                prefetch_next_event_page: false,
+                subscription_send_timeout: std::time::Duration::from_secs(30),
+                websocket_keepalive_interval: None,
+                subscription_catchup_concurrency: 1.try_into().unwrap(),
+                max_subscriptions_per_connection: 100.try_into().unwrap(),
+                block_header_cache_size: 1024.try_into().unwrap(),
+                websocket_max_message_size_bytes: 10 * 1024 * 1024,
+                approved_class_hashes: Vec::new(),
             },
         };
         let router = v08::register_routes().build(ctx);
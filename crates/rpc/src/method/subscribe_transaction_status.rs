@@ -13,7 +13,12 @@ use tokio::time::MissedTickBehavior;
 use super::REORG_SUBSCRIPTION_NAME;
 use crate::context::RpcContext;
 use crate::error::ApplicationError;
-use crate::jsonrpc::{RpcError, RpcSubscriptionFlow, SubscriptionMessage};
+use crate::jsonrpc::{
+    RpcError,
+    RpcSubscriptionFlow,
+    SubscriptionMessage,
+    SubscriptionMessageSender,
+};
 use crate::Reorg;
 
 pub struct SubscribeTransactionStatus;
@@ -128,7 +133,7 @@ impl RpcSubscriptionFlow for SubscribeTransactionStatus {
     async fn subscribe(
         state: RpcContext,
         params: Self::Params,
-        tx: mpsc::Sender<SubscriptionMessage<Self::Notification>>,
+        tx: SubscriptionMessageSender<Self::Notification>,
     ) -> Result<(), RpcError> {
         'reorg: loop {
             let tx_hash = params.transaction_hash;
@@ -380,7 +385,7 @@ impl RpcSubscriptionFlow for SubscribeTransactionStatus {
 }
 
 struct Sender<'a> {
-    tx: &'a mpsc::Sender<SubscriptionMessage<Notification>>,
+    tx: &'a SubscriptionMessageSender<Notification>,
     tx_hash: TransactionHash,
     last_finality_status: Option<FinalityStatus>,
     last_execution_status: Option<ExecutionStatus>,
@@ -540,6 +545,7 @@ mod tests {
                     state_root: Default::default(),
                     block_number: BlockNumber::GENESIS + 2,
                     block_hash: Default::default(),
+                    ..Default::default()
                 })
                 .unwrap();
                 db.commit().unwrap();
@@ -640,6 +646,7 @@ mod tests {
                 state_root: Default::default(),
                 block_number: BlockNumber::GENESIS + 1,
                 block_hash: Default::default(),
+                ..Default::default()
             }),
             |subscription_id| {
                 vec![
@@ -700,6 +707,7 @@ mod tests {
                 state_root: Default::default(),
                 block_number: BlockNumber::GENESIS + 1,
                 block_hash: Default::default(),
+                ..Default::default()
             }),
             |subscription_id| {
                 vec![
@@ -752,6 +760,106 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn rejected() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/feeder_gateway/get_transaction_status")
+                .query_param("transactionHash", "0x1");
+            then.status(200).body(
+                serde_json::json!({
+                    "tx_status": "REJECTED",
+                    "finality_status": "RECEIVED",
+                    "execution_status": "REJECTED",
+                    "tx_failure_reason": {
+                        "code": "INVALID_TRANSACTION_NONCE",
+                        "error_message": "Invalid transaction nonce",
+                    },
+                })
+                .to_string(),
+            );
+        });
+        let (mut router, _pending_sender) = setup().await;
+        router.context.sequencer =
+            Client::with_base_url(server.base_url().parse().unwrap(), Duration::from_secs(10))
+                .unwrap()
+                .disable_retry_for_tests();
+        let (sender_tx, mut sender_rx) = mpsc::channel(1024);
+        let (receiver_tx, receiver_rx) = mpsc::channel(1024);
+        handle_json_rpc_socket(router.clone(), sender_tx, receiver_rx);
+        receiver_tx
+            .send(Ok(Message::Text(
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "starknet_subscribeTransactionStatus",
+                    "params": {"transaction_hash": "0x1"}
+                })
+                .to_string(),
+            )))
+            .await
+            .unwrap();
+        let res = sender_rx.recv().await.unwrap().unwrap();
+        let subscription_id = match res {
+            Message::Text(json) => {
+                let mut json: serde_json::Value = serde_json::from_str(&json).unwrap();
+                assert_eq!(json["jsonrpc"], "2.0");
+                assert_eq!(json["id"], 1);
+                json["result"].take()
+            }
+            _ => panic!("Expected text message"),
+        };
+        let status = sender_rx.recv().await.unwrap().unwrap();
+        let json: serde_json::Value = match status {
+            Message::Text(json) => serde_json::from_str(&json).unwrap(),
+            _ => panic!("Expected text message"),
+        };
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "starknet_subscriptionTransactionStatus",
+                "params": {
+                    "result": {
+                        "transaction_hash": "0x1",
+                        "status": {
+                            "finality_status": "REJECTED",
+                            "failure_reason": "Invalid transaction nonce",
+                        }
+                    },
+                    "subscription_id": subscription_id
+                }
+            })
+        );
+        // The subscription ends after the terminal status, so that block updates
+        // no longer produce messages.
+        router
+            .context
+            .notifications
+            .l2_blocks
+            .send(
+                Block {
+                    block_number: BlockNumber::GENESIS + 1,
+                    block_hash: BlockHash(Felt::from_u64(1)),
+                    transaction_receipts: vec![(
+                        Receipt {
+                            transaction_hash: TransactionHash(Felt::from_u64(1)),
+                            ..Default::default()
+                        },
+                        vec![],
+                    )],
+                    ..Default::default()
+                }
+                .into(),
+            )
+            .ok();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(sender_rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn transaction_status_streaming() {
         test_transaction_status_streaming(|subscription_id| {
@@ -850,6 +958,7 @@ mod tests {
                     state_root: Default::default(),
                     block_number: BlockNumber::GENESIS + 3,
                     block_hash: Default::default(),
+                    ..Default::default()
                 }),
                 TestEvent::L2Block(
                     Block {
@@ -1151,8 +1260,14 @@ mod tests {
         let notifications = Notifications::default();
         let ctx = RpcContext {
             cache: Default::default(),
+            event_page_cache: crate::method::get_events::EventPageCache::new(&notifications),
+            header_cache: crate::header_cache::BlockHeaderCache::new(
+                1024.try_into().unwrap(),
+                &notifications,
+            ),
             storage,
             execution_storage: StorageBuilder::in_memory().unwrap(),
+            event_shards: Vec::new(),
             pending_data: PendingWatcher::new(pending_data),
             sync_status: SyncState {
                 status: Syncing::False(false).into(),
@@ -1170,6 +1285,18 @@ mod tests {
                 get_events_max_blocks_to_scan: 1.try_into().unwrap(),
                 get_events_max_event_filters_to_load: 1.try_into().unwrap(),
                 custom_versioned_constants: None,
+                large_block_threshold: 10_000,
+                class_fallback_url: None,
+                wait_for_block_timeout: std::time::Duration::from_secs(30),
+                validate_class_hash: false,
+                prefetch_next_event_page: false,
+                subscription_send_timeout: std::time::Duration::from_secs(30),
+                websocket_keepalive_interval: None,
+                subscription_catchup_concurrency: 1.try_into().unwrap(),
+                max_subscriptions_per_connection: 100.try_into().unwrap(),
+                block_header_cache_size: 1024.try_into().unwrap(),
+                websocket_max_message_size_bytes: 10 * 1024 * 1024,
+                approved_class_hashes: Vec::new(),
             },
         };
         (v08::register_routes().build(ctx), pending_data_sender)
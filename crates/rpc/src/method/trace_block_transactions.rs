@@ -26,7 +26,7 @@ pub async fn trace_block_transactions(
 ) -> Result<Output, TraceBlockTransactionsError> {
     enum LocalExecution {
         Success(Output),
-        Unsupported(Vec<pathfinder_common::transaction::Transaction>),
+        Unsupported(BlockId, Vec<pathfinder_common::transaction::Transaction>),
     }
 
     let span = tracing::Span::current();
@@ -57,8 +57,9 @@ pub async fn trace_block_transactions(
             }
             other => {
                 let block_id = other.try_into().expect("Only pending should fail");
-                let header = db
-                    .block_header(block_id)?
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_id)?
                     .ok_or(TraceBlockTransactionsError::BlockNotFound)?;
 
                 let transactions = db
@@ -72,6 +73,13 @@ pub async fn trace_block_transactions(
             }
         };
 
+        // The feeder gateway does not accept all block ids, such as `l1_accepted`, so
+        // the resolved block is forwarded by its hash.
+        let gateway_block_id = match input.block_id {
+            BlockId::Pending => BlockId::Pending,
+            _ => BlockId::Hash(header.hash),
+        };
+
         if header.starknet_version
             < VERSIONS_LOWER_THAN_THIS_SHOULD_FALL_BACK_TO_FETCHING_TRACE_FROM_GATEWAY
         {
@@ -83,6 +91,7 @@ pub async fn trace_block_transactions(
                 }
                 _ => {
                     return Ok::<_, TraceBlockTransactionsError>(LocalExecution::Unsupported(
+                        gateway_block_id,
                         transactions,
                     ))
                 }
@@ -105,7 +114,7 @@ pub async fn trace_block_transactions(
         let traces = match pathfinder_executor::trace(state, cache, hash, executor_transactions) {
             Ok(traces) => traces,
             Err(TransactionExecutionError::ExecutionError { .. }) => {
-                return Ok(LocalExecution::Unsupported(transactions))
+                return Ok(LocalExecution::Unsupported(gateway_block_id, transactions))
             }
             Err(e) => return Err(e.into()),
         };
@@ -123,14 +132,14 @@ pub async fn trace_block_transactions(
     .await
     .context("trace_block_transactions: fetch block & transactions")??;
 
-    let transactions = match traces {
+    let (gateway_block_id, transactions) = match traces {
         LocalExecution::Success(output) => return Ok(output),
-        LocalExecution::Unsupported(transactions) => transactions,
+        LocalExecution::Unsupported(block_id, transactions) => (block_id, transactions),
     };
 
     context
         .sequencer
-        .block_traces(input.block_id)
+        .block_traces(gateway_block_id)
         .await
         .context("Forwarding to feeder gateway")
         .map_err(TraceBlockTransactionsError::from)
@@ -747,16 +756,115 @@ pub(crate) mod tests {
         let expected = TraceBlockTransactionsOutput(traces);
 
         pretty_assertions_sorted::assert_eq!(
-            output
-                .serialize(Serializer {
-                    version: RpcVersion::V06,
-                })
-                .unwrap(),
+            output.serialize(Serializer::new(RpcVersion::V06)).unwrap(),
             serde_json::to_value(expected).unwrap()
         );
         Ok(())
     }
 
+    /// All transactions of a block are executed in a single pass, with each
+    /// transaction seeing the state changes of the ones before it. The
+    /// repeated invokes would fail nonce validation otherwise.
+    #[tokio::test]
+    async fn test_ten_transactions() -> anyhow::Result<()> {
+        use pathfinder_common::TransactionNonce;
+        use pathfinder_crypto::Felt;
+
+        use super::super::simulate_transactions::tests::fixtures;
+        use crate::types::request::{BroadcastedInvokeTransaction, BroadcastedTransaction};
+
+        let (storage, last_block_header, account_contract_address, universal_deployer_address, _) =
+            setup_storage_with_starknet_version(StarknetVersion::new(0, 13, 1, 1)).await;
+        let context = RpcContext::for_tests().with_storage(storage.clone());
+
+        let mut transactions = vec![
+            fixtures::input::declare(account_contract_address).into_common(context.chain_id),
+            fixtures::input::universal_deployer(
+                account_contract_address,
+                universal_deployer_address,
+            )
+            .into_common(context.chain_id),
+        ];
+        for nonce in 2..10 {
+            let BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(mut invoke)) =
+                fixtures::input::invoke(account_contract_address)
+            else {
+                unreachable!("The fixture is an invoke v1 transaction");
+            };
+            invoke.nonce = TransactionNonce(Felt::from_u64(nonce));
+            transactions.push(
+                BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(invoke))
+                    .into_common(context.chain_id),
+            );
+        }
+
+        let block_hash = {
+            let mut db = storage.connection()?;
+            let tx = db.transaction()?;
+
+            tx.insert_sierra_class(
+                &SierraHash(fixtures::SIERRA_HASH.0),
+                fixtures::SIERRA_DEFINITION,
+                &fixtures::CASM_HASH,
+                fixtures::CASM_DEFINITION,
+            )?;
+
+            let header = BlockHeader::builder()
+                .number(last_block_header.number + 1)
+                .eth_l1_gas_price(GasPrice(1))
+                .eth_l1_data_gas_price(GasPrice(2))
+                .parent_hash(last_block_header.hash)
+                .sequencer_address(last_block_header.sequencer_address)
+                .timestamp(last_block_header.timestamp)
+                .starknet_version(StarknetVersion::new(0, 13, 1, 1))
+                .l1_da_mode(L1DataAvailabilityMode::Blob)
+                .finalize_with_hash(block_hash!("0x1"));
+            tx.insert_block_header(&header)?;
+
+            let dummy_receipt = Receipt {
+                transaction_hash: transaction_hash!("0x1"),
+                transaction_index: TransactionIndex::new_or_panic(0),
+                ..Default::default()
+            };
+            let data = transactions
+                .iter()
+                .map(|transaction| (transaction.clone(), dummy_receipt.clone()))
+                .collect::<Vec<_>>();
+            tx.insert_transaction_data(header.number, &data, Some(&vec![vec![]; data.len()]))?;
+            tx.commit()?;
+
+            header.hash
+        };
+
+        let input = TraceBlockTransactionsInput {
+            block_id: block_hash.into(),
+        };
+        let output = trace_block_transactions(context, input).await.unwrap();
+
+        let hashes = output
+            .traces
+            .iter()
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        let expected = transactions
+            .iter()
+            .map(|transaction| transaction.hash)
+            .collect::<Vec<_>>();
+        assert_eq!(hashes.len(), 10);
+        assert_eq!(hashes, expected);
+
+        let output = output.serialize(Serializer::new(RpcVersion::V07)).unwrap();
+        for trace in output.as_array().unwrap() {
+            assert!(
+                trace["trace_root"]["execute_invocation"]
+                    .get("revert_reason")
+                    .is_none(),
+                "Unexpected revert: {trace}"
+            );
+        }
+        Ok(())
+    }
+
     /// Test that multiple requests for the same block return correctly. This
     /// checks that the trace request coalescing doesn't do anything
     /// unexpected.
@@ -780,9 +888,7 @@ pub(crate) mod tests {
             outputs.push(
                 output
                     .unwrap()
-                    .serialize(Serializer {
-                        version: RpcVersion::V06,
-                    })
+                    .serialize(Serializer::new(RpcVersion::V06))
                     .unwrap(),
             );
         }
@@ -926,13 +1032,51 @@ pub(crate) mod tests {
         let expected = TraceBlockTransactionsOutput(traces);
 
         pretty_assertions_sorted::assert_eq!(
-            output
-                .serialize(Serializer {
-                    version: RpcVersion::V06,
-                })
-                .unwrap(),
+            output.serialize(Serializer::new(RpcVersion::V06)).unwrap(),
             serde_json::to_value(expected).unwrap()
         );
         Ok(())
     }
+
+    /// Blocks which cannot be traced locally are fetched from the feeder
+    /// gateway, which does not accept every block id.
+    #[tokio::test]
+    async fn gateway_fallback_forwards_block_hash() {
+        use httpmock::prelude::*;
+
+        let storage = pathfinder_storage::StorageBuilder::in_memory().unwrap();
+        let header = BlockHeader::builder()
+            .starknet_version(StarknetVersion::new(0, 13, 1, 0))
+            .finalize_with_hash(block_hash!("0xabcd"));
+        let mut connection = storage.connection().unwrap();
+        let transaction = connection.transaction().unwrap();
+        transaction.insert_block_header(&header).unwrap();
+        transaction
+            .update_l1_l2_pointer(Some(header.number))
+            .unwrap();
+        transaction.commit().unwrap();
+        drop(connection);
+
+        let server = MockServer::start_async().await;
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/feeder_gateway/get_block_traces")
+                .query_param("blockHash", "0xabcd");
+            then.status(200).body(r#"{"traces":[]}"#);
+        });
+        let mut context = RpcContext::for_tests().with_storage(storage);
+        context.sequencer = starknet_gateway_client::Client::with_base_url(
+            server.base_url().parse().unwrap(),
+            std::time::Duration::from_secs(10),
+        )
+        .unwrap()
+        .disable_retry_for_tests();
+
+        let input = TraceBlockTransactionsInput {
+            block_id: BlockId::L1Accepted,
+        };
+        trace_block_transactions(context, input).await.unwrap();
+
+        mock.assert();
+    }
 }
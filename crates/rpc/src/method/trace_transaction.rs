@@ -1,4 +1,5 @@
 use anyhow::Context;
+use pathfinder_common::TransactionHash;
 use pathfinder_executor::TransactionExecutionError;
 use starknet_gateway_client::GatewayApi;
 
@@ -10,26 +11,23 @@ use crate::executor::{
     VERSIONS_LOWER_THAN_THIS_SHOULD_FALL_BACK_TO_FETCHING_TRACE_FROM_GATEWAY,
 };
 use crate::method::trace_block_transactions::map_gateway_trace;
+use crate::pending::PendingData;
 use crate::v06::method::trace_transaction as v06;
 
 #[derive(Debug)]
 pub struct Output {
-    trace: pathfinder_executor::types::TransactionTrace,
-    include_state_diff: bool,
+    pub(crate) trace: pathfinder_executor::types::TransactionTrace,
+    /// Unset for traces fetched from the gateway, which have no state diff.
+    pub(crate) include_state_diff: bool,
 }
 
 pub async fn trace_transaction(
     context: RpcContext,
     input: v06::TraceTransactionInput,
 ) -> Result<Output, TraceTransactionError> {
-    #[allow(clippy::large_enum_variant)]
-    enum LocalExecution {
-        Success(pathfinder_executor::types::TransactionTrace),
-        Unsupported(pathfinder_common::transaction::Transaction),
-    }
-
     let span = tracing::Span::current();
-    let local =
+    let local = {
+        let context = context.clone();
         tokio::task::spawn_blocking(move || -> Result<LocalExecution, TraceTransactionError> {
             let _g = span.enter();
 
@@ -39,112 +37,139 @@ pub async fn trace_transaction(
                 .context("Creating database connection")?;
             let db = db.transaction().context("Creating database transaction")?;
 
-            // Find the transaction's block.
             let pending = context
                 .pending_data
                 .get(&db)
                 .context("Querying pending data")?;
 
-            let (header, transactions, cache) = if let Some(pending_tx) = pending
-                .block
-                .transactions
-                .iter()
-                .find(|tx| tx.hash == input.transaction_hash)
-            {
-                let header = pending.header();
-
-                if header.starknet_version
-                    < VERSIONS_LOWER_THAN_THIS_SHOULD_FALL_BACK_TO_FETCHING_TRACE_FROM_GATEWAY
-                {
-                    return Ok(LocalExecution::Unsupported(pending_tx.clone()));
-                }
-
-                (
-                    header,
-                    pending.block.transactions.clone(),
-                    // Can't use the cache for pending blocks since they have no block hash.
-                    pathfinder_executor::TraceCache::default(),
-                )
-            } else {
-                let block_hash = db
-                    .transaction_block_hash(input.transaction_hash)?
-                    .ok_or(TraceTransactionError::TxnHashNotFound)?;
-
-                let header = db
-                    .block_header(block_hash.into())
-                    .context("Fetching block header")?
-                    .context("Block header is missing")?;
-
-                if header.starknet_version
-                    < VERSIONS_LOWER_THAN_THIS_SHOULD_FALL_BACK_TO_FETCHING_TRACE_FROM_GATEWAY
-                {
-                    let transaction = db
-                        .transaction(input.transaction_hash)
-                        .context("Fetching transaction data")?
-                        .context("Transaction data missing")?;
-
-                    return Ok(LocalExecution::Unsupported(transaction));
-                }
-
-                let transactions = db
-                    .transactions_for_block(header.number.into())
-                    .context("Fetching block transactions")?
-                    .context("Block transactions missing")?
-                    .into_iter()
-                    .map(Into::into)
-                    .collect::<Vec<_>>();
-
-                (header, transactions.clone(), context.cache.clone())
-            };
+            trace_locally(&context, &db, &pending, input.transaction_hash)
+        })
+        .await
+        .context("trace_transaction: execution")??
+    };
 
-            let hash = header.hash;
-            let state = pathfinder_executor::ExecutionState::trace(
-                &db,
-                context.chain_id,
-                header,
-                None,
-                context.config.custom_versioned_constants,
-            );
+    complete_trace(&context, input.transaction_hash, local).await
+}
+
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum LocalExecution {
+    Success(pathfinder_executor::types::TransactionTrace),
+    Unsupported(pathfinder_common::transaction::Transaction),
+}
+
+/// Traces a transaction by re-executing its block, unless the block is too old
+/// to be re-executed locally.
+pub(crate) fn trace_locally(
+    context: &RpcContext,
+    db: &pathfinder_storage::Transaction<'_>,
+    pending: &PendingData,
+    transaction_hash: TransactionHash,
+) -> Result<LocalExecution, TraceTransactionError> {
+    // Find the transaction's block.
+    let (header, transactions, cache) = if let Some(pending_tx) = pending
+        .block
+        .transactions
+        .iter()
+        .find(|tx| tx.hash == transaction_hash)
+    {
+        let header = pending.header();
+
+        if header.starknet_version
+            < VERSIONS_LOWER_THAN_THIS_SHOULD_FALL_BACK_TO_FETCHING_TRACE_FROM_GATEWAY
+        {
+            return Ok(LocalExecution::Unsupported(pending_tx.clone()));
+        }
 
-            let executor_transactions = transactions
-                .iter()
-                .map(|transaction| compose_executor_transaction(transaction, &db))
-                .collect::<Result<Vec<_>, _>>()?;
-
-            match pathfinder_executor::trace(state, cache, hash, executor_transactions) {
-                Ok(txs) => {
-                    let trace = txs
-                        .into_iter()
-                        .find_map(|(tx_hash, trace)| {
-                            if tx_hash == input.transaction_hash {
-                                Some(trace)
-                            } else {
-                                None
-                            }
-                        })
-                        .ok_or_else(|| {
-                            TraceTransactionError::Internal(anyhow::anyhow!(
-                                "Transaction trace missing from block: {}",
-                                input.transaction_hash
-                            ))
-                        })?;
-                    Ok(LocalExecution::Success(trace))
-                }
-                Err(TransactionExecutionError::ExecutionError { .. }) => {
-                    Ok(LocalExecution::Unsupported(
-                        transactions
-                            .into_iter()
-                            .find(|tx| tx.hash == input.transaction_hash)
-                            .unwrap()
-                            .clone(),
+        (
+            header,
+            pending.block.transactions.clone(),
+            // Can't use the cache for pending blocks since they have no block hash.
+            pathfinder_executor::TraceCache::default(),
+        )
+    } else {
+        let block_hash = db
+            .transaction_block_hash(transaction_hash)?
+            .ok_or(TraceTransactionError::TxnHashNotFound)?;
+
+        let header = context
+            .header_cache
+            .block_header(&db, block_hash.into())
+            .context("Fetching block header")?
+            .context("Block header is missing")?;
+
+        if header.starknet_version
+            < VERSIONS_LOWER_THAN_THIS_SHOULD_FALL_BACK_TO_FETCHING_TRACE_FROM_GATEWAY
+        {
+            let transaction = db
+                .transaction(transaction_hash)
+                .context("Fetching transaction data")?
+                .context("Transaction data missing")?;
+
+            return Ok(LocalExecution::Unsupported(transaction));
+        }
+
+        let transactions = db
+            .transactions_for_block(header.number.into())
+            .context("Fetching block transactions")?
+            .context("Block transactions missing")?
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+
+        (header, transactions.clone(), context.cache.clone())
+    };
+
+    let hash = header.hash;
+    let state = pathfinder_executor::ExecutionState::trace(
+        db,
+        context.chain_id,
+        header,
+        None,
+        context.config.custom_versioned_constants.clone(),
+    );
+
+    let executor_transactions = transactions
+        .iter()
+        .map(|transaction| compose_executor_transaction(transaction, db))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match pathfinder_executor::trace(state, cache, hash, executor_transactions) {
+        Ok(txs) => {
+            let trace = txs
+                .into_iter()
+                .find_map(|(tx_hash, trace)| {
+                    if tx_hash == transaction_hash {
+                        Some(trace)
+                    } else {
+                        None
+                    }
+                })
+                .ok_or_else(|| {
+                    TraceTransactionError::Internal(anyhow::anyhow!(
+                        "Transaction trace missing from block: {}",
+                        transaction_hash
                     ))
-                }
-                Err(e) => Err(e.into()),
-            }
-        })
-        .await
-        .context("trace_transaction: execution")??;
+                })?;
+            Ok(LocalExecution::Success(trace))
+        }
+        Err(TransactionExecutionError::ExecutionError { .. }) => Ok(LocalExecution::Unsupported(
+            transactions
+                .into_iter()
+                .find(|tx| tx.hash == transaction_hash)
+                .unwrap()
+                .clone(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
 
+/// Fetches the trace from the gateway if the transaction could not be traced
+/// locally.
+pub(crate) async fn complete_trace(
+    context: &RpcContext,
+    transaction_hash: TransactionHash,
+    local: LocalExecution,
+) -> Result<Output, TraceTransactionError> {
     let transaction = match local {
         LocalExecution::Success(trace) => {
             return Ok(Output {
@@ -157,7 +182,7 @@ pub async fn trace_transaction(
 
     let trace = context
         .sequencer
-        .transaction_trace(input.transaction_hash)
+        .transaction_trace(transaction_hash)
         .await
         .context("Proxying call to feeder gateway")?;
 
@@ -271,11 +296,7 @@ pub mod tests {
             let output = trace_transaction(context.clone(), input).await.unwrap();
             let expected = TraceTransactionOutput(trace.trace_root);
             pretty_assertions_sorted::assert_eq!(
-                output
-                    .serialize(Serializer {
-                        version: RpcVersion::V06
-                    })
-                    .unwrap(),
+                output.serialize(Serializer::new(RpcVersion::V06)).unwrap(),
                 serde_json::to_value(expected).unwrap()
             );
         }
@@ -294,11 +315,7 @@ pub mod tests {
             let output = trace_transaction(context.clone(), input).await.unwrap();
             let expected = TraceTransactionOutput(trace.trace_root);
             pretty_assertions_sorted::assert_eq!(
-                output
-                    .serialize(Serializer {
-                        version: RpcVersion::V06
-                    })
-                    .unwrap(),
+                output.serialize(Serializer::new(RpcVersion::V06)).unwrap(),
                 serde_json::to_value(expected).unwrap()
             );
         }
@@ -1,3 +1,4 @@
+pub(crate) mod cache_control;
 pub mod cors;
 pub(crate) mod request_id;
 pub(crate) mod tracing;
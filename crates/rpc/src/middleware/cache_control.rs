@@ -0,0 +1,161 @@
+//! Sets `Cache-Control` headers on `starknet_getBlockWithTxHashes` responses.
+//!
+//! Blocks requested by hash are immutable once accepted on L1, so such
+//! responses may be cached by proxies and CDNs. Until then the block status
+//! still changes, as does the block a number refers to after a reorg, so these
+//! responses are only cached briefly. Responses for block tags such as
+//! `latest` must not be cached.
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::header::CACHE_CONTROL;
+use http::{HeaderValue, StatusCode};
+
+const METHOD: &str = "starknet_getBlockWithTxHashes";
+
+const IMMUTABLE: &str = "public, max-age=86400, immutable";
+const SHORT_LIVED: &str = "public, max-age=10";
+const NO_STORE: &str = "no-store";
+
+pub(crate) async fn cache_control(request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let Ok(body) = axum::body::to_bytes(body, crate::REQUEST_MAX_SIZE).await else {
+        return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+    };
+
+    let policy = cache_policy(&body);
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let Some(policy) = policy else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(body) = axum::body::to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let response = serde_json::from_slice::<serde_json::Value>(&body).ok();
+    let result = response
+        .as_ref()
+        .and_then(|response| response.get("result"));
+    let policy = result.map_or(policy, |result| result_policy(policy, result));
+
+    // Only successful results may be cached, a block that is not found now may
+    // well exist later on.
+    if policy == NO_STORE || result.is_some() {
+        parts
+            .headers
+            .insert(CACHE_CONTROL, HeaderValue::from_static(policy));
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Returns the `Cache-Control` header value for the given request body, or
+/// [`None`] if the request is not a single `starknet_getBlockWithTxHashes`
+/// call.
+fn cache_policy(body: &[u8]) -> Option<&'static str> {
+    let request = serde_json::from_slice::<serde_json::Value>(body).ok()?;
+
+    if request.get("method")?.as_str()? != METHOD {
+        return None;
+    }
+
+    let block_id = match request.get("params")? {
+        serde_json::Value::Array(params) => params.first()?,
+        serde_json::Value::Object(params) => params.get("block_id")?,
+        _ => return None,
+    };
+
+    let policy = match block_id {
+        serde_json::Value::Object(id) if id.contains_key("block_hash") => IMMUTABLE,
+        serde_json::Value::Object(id) if id.contains_key("block_number") => SHORT_LIVED,
+        _ => NO_STORE,
+    };
+
+    Some(policy)
+}
+
+/// Blocks requested by hash are only immutable once they have been accepted on
+/// L1, before that their status still changes.
+fn result_policy(policy: &'static str, result: &serde_json::Value) -> &'static str {
+    let status = result.get("status").and_then(|status| status.as_str());
+
+    if policy == IMMUTABLE && status != Some("ACCEPTED_ON_L1") {
+        SHORT_LIVED
+    } else {
+        policy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[rstest::rstest]
+    #[case::hash_by_position(json!([{"block_hash": "0xabcd"}]), IMMUTABLE)]
+    #[case::hash_by_name(json!({"block_id": {"block_hash": "0xabcd"}}), IMMUTABLE)]
+    #[case::number_by_position(json!([{"block_number": 1}]), SHORT_LIVED)]
+    #[case::number_by_name(json!({"block_id": {"block_number": 1}}), SHORT_LIVED)]
+    #[case::genesis(json!(["genesis"]), NO_STORE)]
+    #[case::latest(json!(["latest"]), NO_STORE)]
+    #[case::pending(json!({"block_id": "pending"}), NO_STORE)]
+    #[case::safe(json!(["safe"]), NO_STORE)]
+    #[case::finalized(json!(["finalized"]), NO_STORE)]
+    fn policy(#[case] params: serde_json::Value, #[case] expected: &str) {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": METHOD,
+            "params": params,
+        });
+        let body = serde_json::to_vec(&request).unwrap();
+
+        assert_eq!(cache_policy(&body), Some(expected));
+    }
+
+    #[rstest::rstest]
+    #[case::accepted_on_l1(IMMUTABLE, "ACCEPTED_ON_L1", IMMUTABLE)]
+    #[case::accepted_on_l2(IMMUTABLE, "ACCEPTED_ON_L2", SHORT_LIVED)]
+    #[case::number(SHORT_LIVED, "ACCEPTED_ON_L1", SHORT_LIVED)]
+    fn policy_for_result(
+        #[case] policy: &'static str,
+        #[case] status: &str,
+        #[case] expected: &str,
+    ) {
+        let result = json!({"block_hash": "0xabcd", "status": status});
+
+        assert_eq!(result_policy(policy, &result), expected);
+    }
+
+    #[test]
+    fn other_methods_are_not_affected() {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_getBlockWithTxs",
+            "params": [{"block_number": 1}],
+        });
+        let body = serde_json::to_vec(&request).unwrap();
+
+        assert_eq!(cache_policy(&body), None);
+    }
+
+    #[test]
+    fn batches_are_not_affected() {
+        let request = json!([{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": METHOD,
+            "params": [{"block_number": 1}],
+        }]);
+        let body = serde_json::to_vec(&request).unwrap();
+
+        assert_eq!(cache_policy(&body), None);
+    }
+}
@@ -5,8 +5,24 @@ pub(crate) mod methods;
 #[rustfmt::skip]
 pub fn register_routes() -> RpcRouterBuilder {
     RpcRouter::builder(crate::RpcVersion::PathfinderV01)
-        .register("pathfinder_version",              || { pathfinder_common::consts::VERGEN_GIT_DESCRIBE })
-        .register("pathfinder_getProof",             methods::get_proof)
-        .register("pathfinder_getClassProof",        methods::get_class_proof)
-        .register("pathfinder_getTransactionStatus", methods::get_transaction_status)
+        .register("pathfinder_version",                             || { pathfinder_common::consts::VERGEN_GIT_DESCRIBE })
+        .register("pathfinder_detectContractType",                  methods::detect_contract_type)
+        .register("pathfinder_getBlockDaStats",                     methods::get_block_da_stats)
+        .register("pathfinder_getBlockEventStats",                  methods::get_block_event_stats)
+        .register("pathfinder_getBlockL1AcceptanceInfo",            methods::get_block_l1_acceptance_info)
+        .register("pathfinder_getBlockSummary",                     methods::get_block_summary)
+        .register("pathfinder_getClassInfo",                        methods::get_class_info)
+        .register("pathfinder_getClassUsage",                       methods::get_class_usage)
+        .register("pathfinder_getEvents",                           methods::get_events)
+        .register("pathfinder_getEventsInTimeRange",                methods::get_events_in_time_range)
+        .register("pathfinder_getProof",                            methods::get_proof)
+        .register("pathfinder_getClassProof",                       methods::get_class_proof)
+        .register("pathfinder_getTransactionReceiptWithTrace",      methods::get_transaction_receipt_with_trace)
+        .register("pathfinder_getTransactionReceiptWithValidation", methods::get_transaction_receipt_with_validation)
+        .register("pathfinder_getTransactionStateDiff",             methods::get_transaction_state_diff)
+        .register("pathfinder_getTransactionStatus",                methods::get_transaction_status)
+        .register("pathfinder_isClassApproved",                     methods::is_class_approved)
+        .register("pathfinder_searchEvents",                        methods::search_events)
+        .register("pathfinder_verifyBlockChain",                    methods::verify_block_chain)
+        .register("pathfinder_waitForBlock",                        methods::wait_for_block)
 }
@@ -1,5 +1,37 @@
+mod detect_contract_type;
+mod get_block_da_stats;
+mod get_block_event_stats;
+mod get_block_l1_acceptance_info;
+mod get_block_summary;
+mod get_class_info;
+mod get_class_usage;
+mod get_events;
+mod get_events_in_time_range;
 mod get_proof;
+mod get_transaction_receipt_with_trace;
+mod get_transaction_receipt_with_validation;
+mod get_transaction_state_diff;
 mod get_transaction_status;
+mod is_class_approved;
+mod search_events;
+mod verify_block_chain;
+mod wait_for_block;
 
+pub(crate) use detect_contract_type::detect_contract_type;
+pub(crate) use get_block_da_stats::get_block_da_stats;
+pub(crate) use get_block_event_stats::get_block_event_stats;
+pub(crate) use get_block_l1_acceptance_info::get_block_l1_acceptance_info;
+pub(crate) use get_block_summary::get_block_summary;
+pub(crate) use get_class_info::get_class_info;
+pub(crate) use get_class_usage::get_class_usage;
+pub(crate) use get_events::get_events;
+pub(crate) use get_events_in_time_range::get_events_in_time_range;
 pub(crate) use get_proof::{get_class_proof, get_proof};
+pub(crate) use get_transaction_receipt_with_trace::get_transaction_receipt_with_trace;
+pub(crate) use get_transaction_receipt_with_validation::get_transaction_receipt_with_validation;
+pub(crate) use get_transaction_state_diff::get_transaction_state_diff;
 pub(crate) use get_transaction_status::get_transaction_status;
+pub(crate) use is_class_approved::is_class_approved;
+pub(crate) use search_events::search_events;
+pub(crate) use verify_block_chain::verify_block_chain;
+pub(crate) use wait_for_block::wait_for_block;
@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+
+use anyhow::Context;
+use pathfinder_common::{BlockId, ClassHash};
+
+use crate::context::RpcContext;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+use crate::method::get_class::ClassLookup;
+
+crate::error::generate_rpc_error_subset!(Error: BlockNotFound, ClassHashNotFound);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    block_id: BlockId,
+    class_hash: ClassHash,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                block_id: value.deserialize("block_id")?,
+                class_hash: ClassHash(value.deserialize("class_hash")?),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractType {
+    Account,
+    Erc20,
+    Erc721,
+    Erc1155,
+    Unknown,
+}
+
+impl ContractType {
+    /// Guesses the contract type from the names of the functions in a class'
+    /// ABI.
+    ///
+    /// Both the `snake_case` names of Cairo 1 and the `camelCase` names used by
+    /// most Cairo 0 contracts are recognised.
+    fn detect(functions: &HashSet<String>) -> Self {
+        let has_any = |names: &[&str]| names.iter().any(|name| functions.contains(*name));
+
+        let transfer_from = ["transfer_from", "transferFrom"];
+
+        if has_any(&["__execute__"]) && has_any(&["__validate__"]) {
+            Self::Account
+        } else if has_any(&["safe_batch_transfer_from", "safeBatchTransferFrom"])
+            && has_any(&["balance_of_batch", "balanceOfBatch"])
+        {
+            Self::Erc1155
+        } else if has_any(&["owner_of", "ownerOf"]) && has_any(&transfer_from) {
+            Self::Erc721
+        } else if has_any(&["transfer"])
+            && has_any(&transfer_from)
+            && has_any(&["balance_of", "balanceOf"])
+        {
+            Self::Erc20
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    contract_type: ContractType,
+}
+
+/// Heuristically detects whether a class implements an account, an ERC-20, an
+/// ERC-721 or an ERC-1155 contract by looking for the functions of the standard
+/// interfaces in its ABI.
+///
+/// This lets explorers label contracts without maintaining an off-chain
+/// database. Classes which match none of the interfaces, or which have no
+/// readable ABI, are reported as `unknown`.
+pub async fn detect_contract_type(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let definition = ClassLookup::new(&context, &tx, input.block_id, input.class_hash)?
+            .ok_or(Error::BlockNotFound)?
+            .class_definition(&tx, input.class_hash)?
+            .ok_or(Error::ClassHashNotFound)?;
+
+        let functions = abi_functions(&definition).context("Parsing class ABI")?;
+
+        Ok(Output {
+            contract_type: ContractType::detect(&functions),
+        })
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+/// Returns the names of all functions in the ABI of a class definition,
+/// including those nested in Cairo 1 interfaces.
+fn abi_functions(definition: &[u8]) -> anyhow::Result<HashSet<String>> {
+    #[derive(serde::Deserialize)]
+    struct Definition {
+        #[serde(default)]
+        abi: serde_json::Value,
+    }
+
+    fn collect(entries: &[serde_json::Value], functions: &mut HashSet<String>) {
+        for entry in entries {
+            match entry["type"].as_str() {
+                Some("function") => {
+                    if let Some(name) = entry["name"].as_str() {
+                        functions.insert(name.to_owned());
+                    }
+                }
+                Some("interface") => {
+                    if let Some(items) = entry["items"].as_array() {
+                        collect(items, functions);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let definition: Definition =
+        serde_json::from_slice(definition).context("Parsing class definition")?;
+
+    // Sierra classes store the ABI as a JSON encoded string while Cairo 0
+    // classes store it as an array. The string is not validated when a class is
+    // declared, so it may not be JSON, in which case no functions are found.
+    let abi = match definition.abi {
+        serde_json::Value::String(abi) => serde_json::from_str(&abi).unwrap_or_default(),
+        abi => abi,
+    };
+
+    let mut functions = HashSet::new();
+    if let Some(entries) = abi.as_array() {
+        collect(entries, &mut functions);
+    }
+
+    Ok(functions)
+}
+
+impl SerializeForVersion for ContractType {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        serializer.serialize_str(match self {
+            ContractType::Account => "account",
+            ContractType::Erc20 => "erc20",
+            ContractType::Erc721 => "erc721",
+            ContractType::Erc1155 => "erc1155",
+            ContractType::Unknown => "unknown",
+        })
+    }
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+        serializer.serialize_field("contract_type", &self.contract_type)?;
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::BlockNumber;
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    const CASM_DEFINITION: &[u8] = br#"{"compiler_version":"2.6.0","bytecode":[]}"#;
+
+    /// Declares a class with the given ABI in block 3. A string ABI is stored
+    /// as a Sierra class, anything else as a Cairo 0 class.
+    fn setup(abi: serde_json::Value) -> (RpcContext, ClassHash) {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let header = pathfinder_common::BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+
+        let state_update = header.init_state_update();
+        let (class_hash, state_update) = if abi.is_string() {
+            let definition = json!({ "abi": abi, "sierra_program": [] }).to_string();
+            let sierra_hash = sierra_hash_bytes!(b"detect sierra");
+            tx.insert_sierra_class(
+                &sierra_hash,
+                definition.as_bytes(),
+                &casm_hash_bytes!(b"detect casm"),
+                CASM_DEFINITION,
+            )
+            .unwrap();
+            (
+                ClassHash(sierra_hash.0),
+                state_update
+                    .with_declared_sierra_class(sierra_hash, casm_hash_bytes!(b"detect casm")),
+            )
+        } else {
+            let definition = json!({ "abi": abi, "program": {} }).to_string();
+            let class_hash = class_hash_bytes!(b"detect cairo");
+            tx.insert_cairo_class(class_hash, definition.as_bytes())
+                .unwrap();
+            (
+                class_hash,
+                state_update.with_declared_cairo_class(class_hash),
+            )
+        };
+        tx.insert_state_update(header.number, &state_update)
+            .unwrap();
+        tx.commit().unwrap();
+
+        (context, class_hash)
+    }
+
+    /// A Cairo 1 ABI exposing `functions` through a single interface, as
+    /// produced by the compiler.
+    fn sierra_abi(functions: &[&str]) -> serde_json::Value {
+        let items = functions
+            .iter()
+            .map(|name| json!({ "type": "function", "name": name, "inputs": [], "outputs": [] }))
+            .collect::<Vec<_>>();
+        let abi = json!([
+            { "type": "impl", "name": "Impl", "interface_name": "IContract" },
+            { "type": "interface", "name": "IContract", "items": items },
+            { "type": "constructor", "name": "constructor", "inputs": [] },
+        ]);
+        serde_json::Value::String(abi.to_string())
+    }
+
+    /// A Cairo 0 ABI exposing `functions` at the top level.
+    fn cairo_abi(functions: &[&str]) -> serde_json::Value {
+        functions
+            .iter()
+            .map(|name| json!({ "type": "function", "name": name, "inputs": [], "outputs": [] }))
+            .chain(std::iter::once(
+                json!({ "type": "event", "name": "Transfer" }),
+            ))
+            .collect()
+    }
+
+    #[test]
+    fn parsing() {
+        let json = json!({ "block_id": "latest", "class_hash": "0x123" });
+
+        let parsed =
+            Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            parsed,
+            Input {
+                block_id: BlockId::Latest,
+                class_hash: class_hash!("0x123"),
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::account(
+        sierra_abi(&["__validate__", "__execute__", "is_valid_signature"]),
+        "account"
+    )]
+    #[case::cairo_account(cairo_abi(&["__validate__", "__execute__", "getPublicKey"]), "account")]
+    #[case::erc20(
+        sierra_abi(&["total_supply", "balance_of", "allowance", "transfer", "transfer_from", "approve"]),
+        "erc20"
+    )]
+    #[case::cairo_erc20(
+        cairo_abi(&["totalSupply", "balanceOf", "allowance", "transfer", "transferFrom", "approve"]),
+        "erc20"
+    )]
+    #[case::erc721(
+        sierra_abi(&["balance_of", "owner_of", "safe_transfer_from", "transfer_from", "approve"]),
+        "erc721"
+    )]
+    #[case::cairo_erc721(
+        cairo_abi(&["balanceOf", "ownerOf", "safeTransferFrom", "transferFrom", "approve"]),
+        "erc721"
+    )]
+    #[case::erc1155(
+        sierra_abi(&["balance_of", "balance_of_batch", "safe_transfer_from", "safe_batch_transfer_from"]),
+        "erc1155"
+    )]
+    #[case::unknown(sierra_abi(&["increase_balance", "get_balance"]), "unknown")]
+    #[case::without_abi(serde_json::Value::Null, "unknown")]
+    #[case::invalid_sierra_abi(json!("not a json encoded abi"), "unknown")]
+    #[tokio::test]
+    async fn contract_type(#[case] abi: serde_json::Value, #[case] expected: &str) {
+        let (context, class_hash) = setup(abi);
+        let input = Input {
+            block_id: BlockId::Latest,
+            class_hash,
+        };
+
+        let output = detect_contract_type(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(output, json!({ "contract_type": expected }));
+    }
+
+    #[tokio::test]
+    async fn class_not_found() {
+        let (context, _) = setup(sierra_abi(&[]));
+        let input = Input {
+            block_id: BlockId::Latest,
+            class_hash: class_hash_bytes!(b"unknown"),
+        };
+
+        let error = detect_contract_type(context, input).await.unwrap_err();
+
+        assert_matches!(error, Error::ClassHashNotFound);
+    }
+
+    #[tokio::test]
+    async fn block_not_found() {
+        let (context, class_hash) = setup(sierra_abi(&[]));
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::MAX),
+            class_hash,
+        };
+
+        let error = detect_contract_type(context, input).await.unwrap_err();
+
+        assert_matches!(error, Error::BlockNotFound);
+    }
+}
@@ -0,0 +1,297 @@
+use anyhow::Context;
+use pathfinder_common::receipt::Receipt;
+use pathfinder_common::{BlockHeader, BlockId, BlockNumber, L1DataAvailabilityMode};
+
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+
+crate::error::generate_rpc_error_subset!(Error: BlockNotFound);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    block_id: BlockId,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                block_id: value.deserialize("block_id")?,
+            })
+        })
+    }
+}
+
+/// Data availability gas consumed by the transactions of a block.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    block_number: BlockNumber,
+    l1_da_mode: L1DataAvailabilityMode,
+    blob_gas_consumed: u128,
+    calldata_gas_consumed: u128,
+    /// The consumed gas priced at the block's L1 gas and L1 data gas prices.
+    estimated_cost_wei: u128,
+}
+
+impl Output {
+    fn new(header: &BlockHeader, receipts: &[Receipt]) -> Self {
+        let (blob_gas_consumed, calldata_gas_consumed) =
+            receipts
+                .iter()
+                .fold((0u128, 0u128), |(blob, calldata), receipt| {
+                    let gas = &receipt.execution_resources.data_availability;
+                    (
+                        blob.saturating_add(gas.l1_data_gas),
+                        calldata.saturating_add(gas.l1_gas),
+                    )
+                });
+
+        let estimated_cost_wei = blob_gas_consumed
+            .saturating_mul(header.eth_l1_data_gas_price.0)
+            .saturating_add(calldata_gas_consumed.saturating_mul(header.eth_l1_gas_price.0));
+
+        Self {
+            block_number: header.number,
+            l1_da_mode: header.l1_da_mode,
+            blob_gas_consumed,
+            calldata_gas_consumed,
+            estimated_cost_wei,
+        }
+    }
+}
+
+/// Returns the data availability gas consumed by a block's transactions, as
+/// reported by their receipts.
+///
+/// Blocks using `BLOB` data availability consume blob (L1 data) gas while
+/// `CALLDATA` blocks consume L1 gas.
+pub async fn get_block_da_stats(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        if input.block_id.is_pending() {
+            let pending = context
+                .pending_data
+                .get(&tx)
+                .context("Querying pending data")?;
+            let receipts = pending
+                .block
+                .transaction_receipts
+                .iter()
+                .map(|(receipt, _)| receipt.clone())
+                .collect::<Vec<_>>();
+
+            return Ok(Output::new(&pending.header(), &receipts));
+        }
+
+        let block_id = input
+            .block_id
+            .try_into()
+            .expect("Only pending cast should fail");
+        let header = context
+            .header_cache
+            .block_header(&tx, block_id)
+            .context("Querying block header")?
+            .ok_or(Error::BlockNotFound)?;
+        let receipts = tx
+            .transactions_with_receipts_for_block(header.number.into())
+            .context("Querying receipts")?
+            .context("Transaction data missing")?
+            .into_iter()
+            .map(|(_, receipt)| receipt)
+            .collect::<Vec<_>>();
+
+        Ok(Output::new(&header, &receipts))
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_field("block_number", &self.block_number.get())?;
+        serializer.serialize_field(
+            "l1_da_mode",
+            &match self.l1_da_mode {
+                L1DataAvailabilityMode::Blob => "BLOB",
+                L1DataAvailabilityMode::Calldata => "CALLDATA",
+            },
+        )?;
+        serializer.serialize_field("blob_gas_consumed", &dto::U128Hex(self.blob_gas_consumed))?;
+        serializer.serialize_field(
+            "calldata_gas_consumed",
+            &dto::U128Hex(self.calldata_gas_consumed),
+        )?;
+        serializer.serialize_field("estimated_cost_wei", &dto::U128Hex(self.estimated_cost_wei))?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::receipt::{ExecutionResources, L1Gas};
+    use pathfinder_common::transaction::{Transaction, TransactionVariant};
+    use pathfinder_common::{GasPrice, TransactionIndex};
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    /// Stores block 3 with two transactions which consumed the given data
+    /// availability gas each.
+    fn context_with_block(l1_da_mode: L1DataAvailabilityMode, gas: L1Gas) -> RpcContext {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let header = BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .eth_l1_gas_price(GasPrice(10))
+            .eth_l1_data_gas_price(GasPrice(3))
+            .l1_da_mode(l1_da_mode)
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+
+        let body = [
+            transaction_hash_bytes!(b"da txn 0"),
+            transaction_hash_bytes!(b"da txn 1"),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, hash)| {
+            let transaction = Transaction {
+                hash,
+                variant: TransactionVariant::InvokeV0(Default::default()),
+            };
+            let receipt = Receipt {
+                transaction_hash: transaction.hash,
+                transaction_index: TransactionIndex::new_or_panic(i as u64),
+                execution_resources: ExecutionResources {
+                    data_availability: gas.clone(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            (transaction, receipt)
+        })
+        .collect::<Vec<_>>();
+        tx.insert_transaction_data(header.number, &body, None)
+            .unwrap();
+        tx.commit().unwrap();
+
+        context
+    }
+
+    #[test]
+    fn parsing() {
+        let input = json!({ "block_id": { "block_number": 1 } });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input,
+            Input {
+                block_id: BlockId::Number(BlockNumber::new_or_panic(1))
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn blob() {
+        let context = context_with_block(
+            L1DataAvailabilityMode::Blob,
+            L1Gas {
+                l1_gas: 0,
+                l1_data_gas: 128,
+            },
+        );
+        let input = Input {
+            block_id: BlockId::Latest,
+        };
+
+        let output = get_block_da_stats(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "block_number": 3,
+                "l1_da_mode": "BLOB",
+                "blob_gas_consumed": "0x100",
+                "calldata_gas_consumed": "0x0",
+                // 256 blob gas at a data gas price of 3 wei.
+                "estimated_cost_wei": "0x300",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn calldata() {
+        let context = context_with_block(
+            L1DataAvailabilityMode::Calldata,
+            L1Gas {
+                l1_gas: 1000,
+                l1_data_gas: 0,
+            },
+        );
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::new_or_panic(3)),
+        };
+
+        let output = get_block_da_stats(context, input).await.unwrap();
+
+        assert_eq!(
+            output,
+            Output {
+                block_number: BlockNumber::new_or_panic(3),
+                l1_da_mode: L1DataAvailabilityMode::Calldata,
+                blob_gas_consumed: 0,
+                calldata_gas_consumed: 2000,
+                // 2000 gas at a gas price of 10 wei.
+                estimated_cost_wei: 20_000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn pending() {
+        let context = RpcContext::for_tests_with_pending().await;
+        let input = Input {
+            block_id: BlockId::Pending,
+        };
+
+        let output = get_block_da_stats(context, input).await.unwrap();
+
+        assert_eq!(output.block_number, BlockNumber::new_or_panic(3));
+    }
+
+    #[tokio::test]
+    async fn block_not_found() {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::MAX),
+        };
+
+        let error = get_block_da_stats(context, input).await.unwrap_err();
+
+        assert_matches!(error, Error::BlockNotFound);
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::HashSet;
+
+use anyhow::Context;
+use pathfinder_common::event::Event;
+use pathfinder_common::{BlockId, BlockNumber};
+
+use crate::context::RpcContext;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+
+crate::error::generate_rpc_error_subset!(Error: BlockNotFound);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    block_id: BlockId,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                block_id: value.deserialize("block_id")?,
+            })
+        })
+    }
+}
+
+/// Aggregate statistics of the events emitted in a block.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Output {
+    event_count: usize,
+    unique_from_addresses: usize,
+    /// The number of distinct first keys, which for Cairo 1 events is the
+    /// selector of the event name.
+    unique_key_types: usize,
+    total_data_elements: usize,
+}
+
+impl<'a> FromIterator<&'a Event> for Output {
+    fn from_iter<T: IntoIterator<Item = &'a Event>>(events: T) -> Self {
+        let mut from_addresses = HashSet::new();
+        let mut key_types = HashSet::new();
+        let mut output = Output::default();
+
+        for event in events {
+            output.event_count += 1;
+            output.total_data_elements += event.data.len();
+            from_addresses.insert(event.from_address);
+            if let Some(key) = event.keys.first() {
+                key_types.insert(*key);
+            }
+        }
+
+        output.unique_from_addresses = from_addresses.len();
+        output.unique_key_types = key_types.len();
+        output
+    }
+}
+
+/// Returns aggregate statistics of the events emitted in a block, without
+/// having to fetch the events themselves.
+///
+/// Events are stored compressed per block so they cannot be aggregated by the
+/// database. Instead the block's events are decompressed and counted here.
+/// Blocks are read from the database or any of the
+/// [event shards](RpcContext::event_shards).
+pub async fn get_block_event_stats(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        if input.block_id.is_pending() {
+            let pending = context
+                .pending_data
+                .get(&tx)
+                .context("Querying pending data")?;
+
+            return Ok(pending
+                .block
+                .transaction_receipts
+                .iter()
+                .flat_map(|(_, events)| events)
+                .collect());
+        }
+
+        let block_id = input
+            .block_id
+            .try_into()
+            .expect("Only pending cast should fail");
+        if let Some(header) = context
+            .header_cache
+            .block_header(&tx, block_id)
+            .context("Querying block header")?
+        {
+            return Ok(stats_for_block(&tx, header.number)?);
+        }
+
+        // Blocks missing from the main database may be stored in an event shard.
+        for shard in &context.event_shards {
+            let mut db = shard
+                .connection()
+                .context("Opening shard database connection")?;
+            let tx = db
+                .transaction()
+                .context("Creating shard database transaction")?;
+            if let Some(header) = tx
+                .block_header(block_id)
+                .context("Querying shard block header")?
+            {
+                return Ok(stats_for_block(&tx, header.number)?);
+            }
+        }
+
+        Err(Error::BlockNotFound)
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+/// Aggregates the events of a block stored in the database of `tx`.
+fn stats_for_block(
+    tx: &pathfinder_storage::Transaction<'_>,
+    block: BlockNumber,
+) -> anyhow::Result<Output> {
+    // Nothing is stored for blocks without transactions.
+    let events = tx
+        .events_for_block(block.into())
+        .context("Querying events")?
+        .unwrap_or_default();
+
+    Ok(events.iter().flat_map(|(_, events)| events).collect())
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_field("event_count", &self.event_count)?;
+        serializer.serialize_field("unique_from_addresses", &self.unique_from_addresses)?;
+        serializer.serialize_field("unique_key_types", &self.unique_key_types)?;
+        serializer.serialize_field("total_data_elements", &self.total_data_elements)?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::receipt::Receipt;
+    use pathfinder_common::transaction::{Transaction, TransactionVariant};
+    use pathfinder_common::{BlockHeader, TransactionIndex};
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    /// Stores block 3 with three transactions. The first emits two `Transfer`
+    /// events from the same contract, the second emits a `Transfer` and an
+    /// event without keys from another contract and the third emits nothing.
+    fn context_with_block() -> RpcContext {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let header = BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+
+        let transfer = |from_address, data: Vec<_>| Event {
+            from_address,
+            keys: vec![event_key_bytes!(b"Transfer"), event_key!("0x1")],
+            data,
+        };
+        let events = vec![
+            vec![
+                transfer(
+                    contract_address_bytes!(b"token"),
+                    vec![event_data!("0x1"), event_data!("0x2")],
+                ),
+                transfer(
+                    contract_address_bytes!(b"token"),
+                    vec![event_data!("0x3"), event_data!("0x4")],
+                ),
+            ],
+            vec![
+                transfer(contract_address_bytes!(b"other"), vec![event_data!("0x5")]),
+                Event {
+                    from_address: contract_address_bytes!(b"other"),
+                    keys: vec![],
+                    data: vec![event_data!("0x6")],
+                },
+            ],
+            vec![],
+        ];
+
+        let body = (0..events.len())
+            .map(|i| {
+                let transaction = Transaction {
+                    hash: transaction_hash_bytes!(format!("event stats txn {i}").as_bytes()),
+                    variant: TransactionVariant::InvokeV0(Default::default()),
+                };
+                let receipt = Receipt {
+                    transaction_hash: transaction.hash,
+                    transaction_index: TransactionIndex::new_or_panic(i as u64),
+                    ..Default::default()
+                };
+                (transaction, receipt)
+            })
+            .collect::<Vec<_>>();
+        tx.insert_transaction_data(header.number, &body, Some(&events))
+            .unwrap();
+        tx.commit().unwrap();
+
+        context
+    }
+
+    #[test]
+    fn parsing() {
+        let input = json!({ "block_id": { "block_number": 1 } });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input,
+            Input {
+                block_id: BlockId::Number(BlockNumber::new_or_panic(1))
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn known_events() {
+        let context = context_with_block();
+        let input = Input {
+            block_id: BlockId::Latest,
+        };
+
+        let output = get_block_event_stats(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "event_count": 4,
+                "unique_from_addresses": 2,
+                // Only `Transfer`, the event without keys is not counted.
+                "unique_key_types": 1,
+                "total_data_elements": 6,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn block_without_transactions() {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        let header = BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+        tx.commit().unwrap();
+
+        let input = Input {
+            block_id: BlockId::Latest,
+        };
+
+        let output = get_block_event_stats(context, input).await.unwrap();
+
+        assert_eq!(output, Output::default());
+    }
+
+    #[tokio::test]
+    async fn pending() {
+        let context = RpcContext::for_tests_with_pending().await;
+        let input = Input {
+            block_id: BlockId::Pending,
+        };
+
+        let output = get_block_event_stats(context, input).await.unwrap();
+
+        assert_eq!(
+            output,
+            Output {
+                event_count: 3,
+                unique_from_addresses: 2,
+                unique_key_types: 2,
+                total_data_elements: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn block_in_event_shard() {
+        use pathfinder_storage::test_utils;
+
+        // Blocks 0 and 1 are stored in a shard, blocks 2 and 3 in the main database.
+        let (shard, shard_data) = test_utils::setup_custom_test_storage(&[0, 1], 3);
+        let (storage, _) = test_utils::setup_custom_test_storage(&[2, 3], 3);
+        let context = RpcContext::for_tests()
+            .with_storage(storage)
+            .with_event_shards(vec![shard]);
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::new_or_panic(1)),
+        };
+
+        let output = get_block_event_stats(context, input).await.unwrap();
+
+        let expected = shard_data
+            .events
+            .iter()
+            .filter(|event| event.block_number == BlockNumber::new_or_panic(1))
+            .count();
+        assert_ne!(expected, 0);
+        assert_eq!(output.event_count, expected);
+    }
+
+    #[tokio::test]
+    async fn block_not_found() {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::MAX),
+        };
+
+        let error = get_block_event_stats(context, input).await.unwrap_err();
+
+        assert_matches!(error, Error::BlockNotFound);
+    }
+}
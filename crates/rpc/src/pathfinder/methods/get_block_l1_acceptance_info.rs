@@ -0,0 +1,247 @@
+use anyhow::Context;
+use pathfinder_common::{BlockId, BlockNumber, L1BlockNumber, L1TransactionHash, StateCommitment};
+
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+
+crate::error::generate_rpc_error_subset!(Error: BlockNotFound);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    block_id: BlockId,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                block_id: value.deserialize("block_id")?,
+            })
+        })
+    }
+}
+
+/// The L1 state update which accepted a block, [None] if the block is not yet
+/// accepted on L1 or the state update which accepted it is not known.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output(Option<L1AcceptanceInfo>);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct L1AcceptanceInfo {
+    /// The latest Starknet block covered by the L1 state update. State updates
+    /// usually cover a range of blocks, so this may be later than the
+    /// requested block.
+    starknet_block_number: BlockNumber,
+    l1_block_number: L1BlockNumber,
+    /// [None] for state updates stored before this information was tracked.
+    l1_transaction_hash: Option<L1TransactionHash>,
+    l1_state_root: StateCommitment,
+}
+
+/// Returns the Ethereum block and transaction through which a Starknet block
+/// was accepted on L1.
+pub async fn get_block_l1_acceptance_info(
+    context: RpcContext,
+    input: Input,
+) -> Result<Output, Error> {
+    if input.block_id.is_pending() {
+        return Ok(Output(None));
+    }
+
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let block_id = input
+            .block_id
+            .try_into()
+            .expect("Only pending cast should fail");
+        let block_number = tx
+            .block_number(block_id)
+            .context("Querying block number")?
+            .ok_or(Error::BlockNotFound)?;
+
+        let l1_accepted = tx
+            .block_is_l1_accepted(block_number.into())
+            .context("Querying block's status")?;
+        if !l1_accepted {
+            return Ok(Output(None));
+        }
+
+        let info = tx
+            .l1_state_accepting_number(block_number)
+            .context("Querying L1 state")?
+            .and_then(|update| {
+                Some(L1AcceptanceInfo {
+                    starknet_block_number: update.block_number,
+                    l1_block_number: update.l1_block_number?,
+                    l1_transaction_hash: update.l1_transaction_hash,
+                    l1_state_root: update.state_root,
+                })
+            });
+
+        Ok(Output(info))
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        match &self.0 {
+            Some(info) => info.serialize(serializer),
+            None => serializer.serialize(&serde_json::Value::Null),
+        }
+    }
+}
+
+impl SerializeForVersion for L1AcceptanceInfo {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_field("starknet_block_number", &self.starknet_block_number.get())?;
+        serializer.serialize_field("l1_block_number", &self.l1_block_number.get())?;
+        serializer.serialize_optional(
+            "l1_transaction_hash",
+            self.l1_transaction_hash
+                .map(|hash| dto::H256Hex(hash.into())),
+        )?;
+        serializer.serialize_field("l1_state_root", &dto::Felt(&self.l1_state_root.0))?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_ethereum::EthereumStateUpdate;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    /// Stores an L1 state update covering the L1 accepted genesis block of the
+    /// test storage.
+    fn context_with_l1_state() -> RpcContext {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        tx.upsert_l1_state(&EthereumStateUpdate {
+            state_root: state_commitment!("0x1234"),
+            block_number: BlockNumber::GENESIS,
+            block_hash: block_hash!("0x1"),
+            l1_block_number: Some(L1BlockNumber::new_or_panic(100)),
+            l1_transaction_hash: Some(L1TransactionHash::from([0xab; 32])),
+        })
+        .unwrap();
+        tx.commit().unwrap();
+        context
+    }
+
+    #[test]
+    fn parsing() {
+        let input = json!({ "block_id": { "block_number": 1 } });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input,
+            Input {
+                block_id: BlockId::Number(BlockNumber::new_or_panic(1))
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn l1_accepted() {
+        let context = context_with_l1_state();
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::GENESIS),
+        };
+
+        let output = get_block_l1_acceptance_info(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "starknet_block_number": 0,
+                "l1_block_number": 100,
+                "l1_transaction_hash": format!("0x{}", "ab".repeat(32)),
+                "l1_state_root": "0x1234",
+            })
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::l2_accepted(BlockId::Number(BlockNumber::new_or_panic(1)))]
+    #[case::latest(BlockId::Latest)]
+    #[case::pending(BlockId::Pending)]
+    #[tokio::test]
+    async fn not_l1_accepted(#[case] block_id: BlockId) {
+        let context = context_with_l1_state();
+        let input = Input { block_id };
+
+        let output = get_block_l1_acceptance_info(context, input).await.unwrap();
+
+        assert_eq!(output, Output(None));
+        assert_eq!(
+            output
+                .serialize(Serializer::new(RpcVersion::PathfinderV01))
+                .unwrap(),
+            serde_json::Value::Null
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_for_l1_state_snapshot() {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        // The L1 state read on startup has no L1 block number.
+        tx.upsert_l1_state(&EthereumStateUpdate {
+            state_root: state_commitment!("0x1234"),
+            block_number: BlockNumber::GENESIS,
+            block_hash: block_hash!("0x1"),
+            l1_block_number: None,
+            l1_transaction_hash: None,
+        })
+        .unwrap();
+        tx.commit().unwrap();
+        drop(db);
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::GENESIS),
+        };
+
+        let output = get_block_l1_acceptance_info(context, input).await.unwrap();
+
+        assert_eq!(output, Output(None));
+    }
+
+    #[tokio::test]
+    async fn block_not_found() {
+        let context = context_with_l1_state();
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::MAX),
+        };
+
+        let error = get_block_l1_acceptance_info(context, input)
+            .await
+            .unwrap_err();
+
+        assert_matches!(error, Error::BlockNotFound);
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::Context;
+use pathfinder_common::event::Event;
+use pathfinder_common::receipt::Receipt;
+use pathfinder_common::{BlockId, BlockNumber};
+
+use crate::context::RpcContext;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+
+crate::error::generate_rpc_error_subset!(Error: BlockNotFound);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    block_id: BlockId,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                block_id: value.deserialize("block_id")?,
+            })
+        })
+    }
+}
+
+/// Transaction, event and message counts of a block.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    block_number: BlockNumber,
+    total_transactions: usize,
+    successful_transactions: usize,
+    /// Transactions which were included in the block but reverted.
+    failed_transactions: usize,
+    total_events: usize,
+    /// The number of L2 to L1 messages sent by the block's transactions.
+    total_messages_sent: usize,
+}
+
+impl Output {
+    fn new<'a>(
+        block_number: BlockNumber,
+        receipts: impl IntoIterator<Item = (&'a Receipt, &'a [Event])>,
+    ) -> Self {
+        let mut output = Self {
+            block_number,
+            total_transactions: 0,
+            successful_transactions: 0,
+            failed_transactions: 0,
+            total_events: 0,
+            total_messages_sent: 0,
+        };
+
+        for (receipt, events) in receipts {
+            output.total_transactions += 1;
+            if receipt.is_reverted() {
+                output.failed_transactions += 1;
+            } else {
+                output.successful_transactions += 1;
+            }
+            output.total_events += events.len();
+            output.total_messages_sent += receipt.l2_to_l1_messages.len();
+        }
+
+        output
+    }
+}
+
+/// Summarises the outcome of a block's transactions.
+///
+/// Receipts are stored compressed together with the block's transactions, so
+/// the counts are computed from the decompressed receipts rather than by the
+/// database. For the pending block, `block_number` is the number it will have
+/// once accepted.
+pub async fn get_block_summary(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        if input.block_id.is_pending() {
+            let pending = context
+                .pending_data
+                .get(&tx)
+                .context("Querying pending data")?;
+
+            return Ok(Output::new(
+                pending.number,
+                pending
+                    .block
+                    .transaction_receipts
+                    .iter()
+                    .map(|(receipt, events)| (receipt, events.as_slice())),
+            ));
+        }
+
+        let block_id = input
+            .block_id
+            .try_into()
+            .expect("Only pending cast should fail");
+        let block_number = tx
+            .block_number(block_id)
+            .context("Querying block number")?
+            .ok_or(Error::BlockNotFound)?;
+        let transaction_data = tx
+            .transaction_data_for_block(block_number.into())
+            .context("Querying transaction data")?
+            .context("Transaction data missing")?;
+
+        Ok(Output::new(
+            block_number,
+            transaction_data
+                .iter()
+                .map(|(_, receipt, events)| (receipt, events.as_slice())),
+        ))
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_field("block_number", &self.block_number.get())?;
+        serializer.serialize_field("total_transactions", &self.total_transactions)?;
+        serializer.serialize_field("successful_transactions", &self.successful_transactions)?;
+        serializer.serialize_field("failed_transactions", &self.failed_transactions)?;
+        serializer.serialize_field("total_events", &self.total_events)?;
+        serializer.serialize_field("total_messages_sent", &self.total_messages_sent)?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::receipt::{ExecutionStatus, L2ToL1Message};
+    use pathfinder_common::transaction::{Transaction, TransactionVariant};
+    use pathfinder_common::{BlockHeader, TransactionIndex};
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    /// Stores block 3 with four transactions:
+    /// - a successful one emitting two events and sending a message,
+    /// - a reverted one emitting nothing,
+    /// - a successful one sending two messages,
+    /// - a reverted one emitting an event.
+    fn context_with_block() -> RpcContext {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let header = BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+
+        let event = Event {
+            from_address: contract_address_bytes!(b"emitter"),
+            keys: vec![event_key_bytes!(b"key")],
+            data: vec![],
+        };
+        let message = L2ToL1Message {
+            from_address: contract_address_bytes!(b"sender"),
+            payload: vec![],
+            to_address: contract_address_bytes!(b"l1 recipient"),
+        };
+        let reverted = ExecutionStatus::Reverted {
+            reason: "Reverted!".to_owned(),
+        };
+        let outcomes = [
+            (
+                ExecutionStatus::Succeeded,
+                vec![event.clone(), event.clone()],
+                1,
+            ),
+            (reverted.clone(), vec![], 0),
+            (ExecutionStatus::Succeeded, vec![], 2),
+            (reverted, vec![event], 0),
+        ];
+
+        let (body, events): (Vec<_>, Vec<_>) = outcomes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (execution_status, events, messages))| {
+                let transaction = Transaction {
+                    hash: transaction_hash_bytes!(format!("summary txn {i}").as_bytes()),
+                    variant: TransactionVariant::InvokeV0(Default::default()),
+                };
+                let receipt = Receipt {
+                    transaction_hash: transaction.hash,
+                    transaction_index: TransactionIndex::new_or_panic(i as u64),
+                    execution_status,
+                    l2_to_l1_messages: vec![message.clone(); messages],
+                    ..Default::default()
+                };
+                ((transaction, receipt), events)
+            })
+            .unzip();
+        tx.insert_transaction_data(header.number, &body, Some(&events))
+            .unwrap();
+        tx.commit().unwrap();
+
+        context
+    }
+
+    #[test]
+    fn parsing() {
+        let input = json!({ "block_id": { "block_number": 1 } });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input,
+            Input {
+                block_id: BlockId::Number(BlockNumber::new_or_panic(1))
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn successful_and_failed_transactions() {
+        let context = context_with_block();
+        let input = Input {
+            block_id: BlockId::Latest,
+        };
+
+        let output = get_block_summary(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "block_number": 3,
+                "total_transactions": 4,
+                "successful_transactions": 2,
+                "failed_transactions": 2,
+                "total_events": 3,
+                "total_messages_sent": 3,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn block_without_transactions() {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        let header = BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+        tx.commit().unwrap();
+
+        let input = Input {
+            block_id: BlockId::Latest,
+        };
+
+        let output = get_block_summary(context, input).await.unwrap();
+
+        assert_eq!(
+            output,
+            Output {
+                block_number: header.number,
+                total_transactions: 0,
+                successful_transactions: 0,
+                failed_transactions: 0,
+                total_events: 0,
+                total_messages_sent: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn pending() {
+        let context = RpcContext::for_tests_with_pending().await;
+        let pending_number = {
+            let mut db = context.storage.connection().unwrap();
+            let tx = db.transaction().unwrap();
+            context.pending_data.get(&tx).unwrap().number
+        };
+        let input = Input {
+            block_id: BlockId::Pending,
+        };
+
+        let output = get_block_summary(context, input).await.unwrap();
+
+        assert_eq!(
+            output,
+            Output {
+                block_number: pending_number,
+                total_transactions: 3,
+                successful_transactions: 2,
+                failed_transactions: 1,
+                total_events: 3,
+                total_messages_sent: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn block_not_found() {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            block_id: BlockId::Number(BlockNumber::MAX),
+        };
+
+        let error = get_block_summary(context, input).await.unwrap_err();
+
+        assert_matches!(error, Error::BlockNotFound);
+    }
+}
@@ -0,0 +1,267 @@
+use anyhow::Context;
+use pathfinder_common::{BlockId, ClassHash};
+use pathfinder_storage::ClassMetadata;
+
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+use crate::method::get_class::ClassLookup;
+
+crate::error::generate_rpc_error_subset!(Error: BlockNotFound, ClassHashNotFound);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    block_id: BlockId,
+    class_hash: ClassHash,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                block_id: value.deserialize("block_id")?,
+                class_hash: ClassHash(value.deserialize("class_hash")?),
+            })
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    class_hash: ClassHash,
+    metadata: ClassMetadata,
+}
+
+/// Returns the size of a class and the compiler version which produced it,
+/// without the class definition itself.
+///
+/// Only the class type is returned for definitions which could not be parsed.
+///
+/// This lets clients decide whether to fetch and cache a class before
+/// downloading it using `starknet_getClass`.
+pub async fn get_class_info(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let metadata = ClassLookup::new(&context, &tx, input.block_id, input.class_hash)?
+            .ok_or(Error::BlockNotFound)?
+            .class_metadata(&tx, input.class_hash)?
+            .ok_or(Error::ClassHashNotFound)?;
+
+        Ok(Output {
+            class_hash: input.class_hash,
+            metadata,
+        })
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        let class_type = if self.metadata.is_sierra {
+            "SIERRA"
+        } else {
+            "CAIRO_0"
+        };
+
+        serializer.serialize_field("class_hash", &dto::Felt(&self.class_hash.0))?;
+        serializer.serialize_field("class_type", &class_type)?;
+        serializer.serialize_optional(
+            "sierra_program_length_words",
+            self.metadata.sierra_program_length,
+        )?;
+        serializer.serialize_optional("abi_length_bytes", self.metadata.abi_length)?;
+        serializer
+            .serialize_optional("compiler_version", self.metadata.compiler_version.clone())?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::BlockNumber;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    const SIERRA_DEFINITION: &[u8] =
+        br#"{"abi":"[{\"type\":\"function\"}]","sierra_program":["0x1","0x2","0x3"]}"#;
+    const CASM_DEFINITION: &[u8] = br#"{"compiler_version":"2.6.0","bytecode":[]}"#;
+    const CAIRO_DEFINITION: &[u8] = br#"{"abi":[{"type":"event"}],"program":{}}"#;
+
+    /// Declares two Sierra classes, one of which cannot be parsed, and a Cairo
+    /// 0 class in block 3.
+    fn setup() -> RpcContext {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let header = pathfinder_common::BlockHeader::builder()
+            .number(BlockNumber::new_or_panic(3))
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+        tx.insert_sierra_class(
+            &sierra_hash_bytes!(b"info sierra"),
+            SIERRA_DEFINITION,
+            &casm_hash_bytes!(b"info casm"),
+            CASM_DEFINITION,
+        )
+        .unwrap();
+        tx.insert_sierra_class(
+            &sierra_hash_bytes!(b"unparseable sierra"),
+            b"not a sierra definition",
+            &casm_hash_bytes!(b"unparseable casm"),
+            CASM_DEFINITION,
+        )
+        .unwrap();
+        tx.insert_cairo_class(class_hash_bytes!(b"info cairo"), CAIRO_DEFINITION)
+            .unwrap();
+        tx.insert_state_update(
+            header.number,
+            &header
+                .init_state_update()
+                .with_declared_sierra_class(
+                    sierra_hash_bytes!(b"info sierra"),
+                    casm_hash_bytes!(b"info casm"),
+                )
+                .with_declared_sierra_class(
+                    sierra_hash_bytes!(b"unparseable sierra"),
+                    casm_hash_bytes!(b"unparseable casm"),
+                )
+                .with_declared_cairo_class(class_hash_bytes!(b"info cairo")),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        context
+    }
+
+    fn input(block_id: BlockId, class_hash: ClassHash) -> Input {
+        Input {
+            block_id,
+            class_hash,
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        let json = json!({ "block_id": "latest", "class_hash": "0x123" });
+
+        let parsed =
+            Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(parsed, input(BlockId::Latest, class_hash!("0x123")));
+    }
+
+    #[tokio::test]
+    async fn sierra_class() {
+        let context = setup();
+        let class_hash = ClassHash(sierra_hash_bytes!(b"info sierra").0);
+
+        let output = get_class_info(context, input(BlockId::Latest, class_hash))
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "class_hash": dto::Felt(&class_hash.0)
+                    .serialize(Serializer::new(RpcVersion::PathfinderV01))
+                    .unwrap(),
+                "class_type": "SIERRA",
+                "sierra_program_length_words": 3,
+                // The decoded `[{"type":"function"}]`.
+                "abi_length_bytes": 21,
+                "compiler_version": "2.6.0",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn cairo_class() {
+        let context = setup();
+        let class_hash = class_hash_bytes!(b"info cairo");
+
+        let output = get_class_info(context, input(BlockId::Latest, class_hash))
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "class_hash": dto::Felt(&class_hash.0)
+                    .serialize(Serializer::new(RpcVersion::PathfinderV01))
+                    .unwrap(),
+                "class_type": "CAIRO_0",
+                "abi_length_bytes": r#"[{"type":"event"}]"#.len(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unparseable_sierra_class() {
+        let context = setup();
+        let class_hash = ClassHash(sierra_hash_bytes!(b"unparseable sierra").0);
+
+        let output = get_class_info(context, input(BlockId::Latest, class_hash))
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!({
+                "class_hash": dto::Felt(&class_hash.0)
+                    .serialize(Serializer::new(RpcVersion::PathfinderV01))
+                    .unwrap(),
+                "class_type": "SIERRA",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn not_yet_declared() {
+        let context = setup();
+        let class_hash = class_hash_bytes!(b"info cairo");
+        let block_id = BlockId::Number(BlockNumber::new_or_panic(2));
+
+        let error = get_class_info(context, input(block_id, class_hash))
+            .await
+            .unwrap_err();
+
+        assert_matches!(error, Error::ClassHashNotFound);
+    }
+
+    #[tokio::test]
+    async fn block_not_found() {
+        let context = setup();
+        let class_hash = class_hash_bytes!(b"info cairo");
+        let block_id = BlockId::Number(BlockNumber::MAX);
+
+        let error = get_class_info(context, input(block_id, class_hash))
+            .await
+            .unwrap_err();
+
+        assert_matches!(error, Error::BlockNotFound);
+    }
+}
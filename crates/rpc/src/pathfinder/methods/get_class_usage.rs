@@ -0,0 +1,330 @@
+use anyhow::Context;
+use pathfinder_common::{BlockNumber, ClassHash, ContractAddress};
+use serde::de::Error as _;
+
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+
+crate::error::generate_rpc_error_subset!(
+    Error: ClassHashNotFound,
+    PageSizeTooBig,
+    InvalidContinuationToken
+);
+
+/// The maximum number of replacements returned per page.
+const MAX_CHUNK_SIZE: usize = 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    class_hash: ClassHash,
+    chunk_size: usize,
+    /// The number of replacements returned by previous pages.
+    continuation_token: Option<String>,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            let chunk_size: usize = value.deserialize_serde("chunk_size")?;
+            // An empty page would never advance the continuation token.
+            if chunk_size == 0 {
+                return Err(serde_json::Error::custom("Chunk size must be positive"));
+            }
+
+            Ok(Self {
+                class_hash: ClassHash(value.deserialize("class_hash")?),
+                chunk_size,
+                continuation_token: value.deserialize_optional_serde("continuation_token")?,
+            })
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    replacements: Vec<(ContractAddress, BlockNumber)>,
+    continuation_token: Option<String>,
+}
+
+/// Returns the contracts which replaced their class with the given class,
+/// together with the block of each replacement, ordered by block number.
+///
+/// This lets auditors find the contracts which upgraded to an implementation.
+/// Contracts deployed with the class are not included, and neither are
+/// replacements in the pending block.
+pub async fn get_class_usage(context: RpcContext, input: Input) -> Result<Output, Error> {
+    if input.chunk_size > MAX_CHUNK_SIZE {
+        return Err(Error::PageSizeTooBig);
+    }
+
+    let offset = match &input.continuation_token {
+        Some(token) => token
+            .parse::<usize>()
+            .map_err(|_| Error::InvalidContinuationToken)?,
+        None => 0,
+    };
+
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let class_exists = tx
+            .class_definitions_exist(&[input.class_hash])
+            .context("Querying class existence")?;
+        if class_exists != [true] {
+            return Err(Error::ClassHashNotFound);
+        }
+
+        // Query one more than requested to find out whether there is another page.
+        let mut replacements = tx
+            .class_replacements(input.class_hash, offset, input.chunk_size + 1)
+            .context("Querying class replacements")?;
+
+        let continuation_token = if replacements.len() > input.chunk_size {
+            replacements.truncate(input.chunk_size);
+            Some((offset + input.chunk_size).to_string())
+        } else {
+            None
+        };
+
+        Ok(Output {
+            replacements,
+            continuation_token,
+        })
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        struct Replacement<'a>(&'a (ContractAddress, BlockNumber));
+
+        impl SerializeForVersion for Replacement<'_> {
+            fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+                let (contract_address, block_number) = self.0;
+                let mut serializer = serializer.serialize_struct()?;
+                serializer.serialize_field("contract_address", &dto::Address(contract_address))?;
+                serializer.serialize_field("replaced_in_block", &block_number.get())?;
+                serializer.end()
+            }
+        }
+
+        let mut serializer = serializer.serialize_struct()?;
+        serializer.serialize_iter(
+            "contracts",
+            self.replacements.len(),
+            &mut self.replacements.iter().map(Replacement),
+        )?;
+        serializer.serialize_optional("continuation_token", self.continuation_token.as_ref())?;
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::StateUpdate;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    const CLASS_HASH: ClassHash = class_hash_bytes!(b"upgraded class");
+
+    /// Declares [CLASS_HASH] in block 3 and deploys a contract with it, then
+    /// replaces the class of `contract 0`, `contract 1` and `contract 2
+    /// (sierra)` with it in blocks 4, 5 and 6 respectively.
+    fn setup() -> RpcContext {
+        let context = RpcContext::for_tests();
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        tx.insert_cairo_class(CLASS_HASH, b"upgraded class definition")
+            .unwrap();
+
+        let mut parent = tx
+            .block_header(pathfinder_storage::BlockId::Latest)
+            .unwrap()
+            .unwrap();
+        let state_updates = [
+            StateUpdate::default()
+                .with_declared_cairo_class(CLASS_HASH)
+                .with_deployed_contract(contract_address_bytes!(b"deployed"), CLASS_HASH),
+            StateUpdate::default()
+                .with_replaced_class(contract_address_bytes!(b"contract 0"), CLASS_HASH),
+            StateUpdate::default()
+                .with_replaced_class(contract_address_bytes!(b"contract 1"), CLASS_HASH),
+            StateUpdate::default()
+                .with_replaced_class(contract_address_bytes!(b"contract 2 (sierra)"), CLASS_HASH),
+        ];
+        for state_update in state_updates {
+            let hash = block_hash_bytes!(format!("usage block {}", parent.number + 1).as_bytes());
+            let header = parent.child_builder().finalize_with_hash(hash);
+            tx.insert_block_header(&header).unwrap();
+            tx.insert_state_update(header.number, &state_update)
+                .unwrap();
+            parent = header;
+        }
+        tx.commit().unwrap();
+
+        context
+    }
+
+    fn input(chunk_size: usize, continuation_token: Option<&str>) -> Input {
+        Input {
+            class_hash: CLASS_HASH,
+            chunk_size,
+            continuation_token: continuation_token.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        let json = json!({
+            "class_hash": "0x123",
+            "chunk_size": 10,
+            "continuation_token": "20",
+        });
+
+        let parsed =
+            Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            parsed,
+            Input {
+                class_hash: class_hash!("0x123"),
+                chunk_size: 10,
+                continuation_token: Some("20".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn zero_chunk_size_is_rejected() {
+        let json = json!({
+            "class_hash": "0x123",
+            "chunk_size": 0,
+        });
+
+        Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn replacements_exclude_deployments() {
+        let context = setup();
+
+        let output = get_class_usage(context, input(10, None))
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let address = |address: ContractAddress| {
+            dto::Address(&address)
+                .serialize(Serializer::new(RpcVersion::PathfinderV01))
+                .unwrap()
+        };
+        assert_eq!(
+            output,
+            json!({
+                "contracts": [
+                    {
+                        "contract_address": address(contract_address_bytes!(b"contract 0")),
+                        "replaced_in_block": 4,
+                    },
+                    {
+                        "contract_address": address(contract_address_bytes!(b"contract 1")),
+                        "replaced_in_block": 5,
+                    },
+                    {
+                        "contract_address": address(contract_address_bytes!(b"contract 2 (sierra)")),
+                        "replaced_in_block": 6,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn paging() {
+        let context = setup();
+
+        let first = get_class_usage(context.clone(), input(2, None))
+            .await
+            .unwrap();
+        assert_eq!(
+            first,
+            Output {
+                replacements: vec![
+                    (
+                        contract_address_bytes!(b"contract 0"),
+                        BlockNumber::new_or_panic(4)
+                    ),
+                    (
+                        contract_address_bytes!(b"contract 1"),
+                        BlockNumber::new_or_panic(5)
+                    ),
+                ],
+                continuation_token: Some("2".to_owned()),
+            }
+        );
+
+        let second = get_class_usage(context, input(2, first.continuation_token.as_deref()))
+            .await
+            .unwrap();
+        assert_eq!(
+            second,
+            Output {
+                replacements: vec![(
+                    contract_address_bytes!(b"contract 2 (sierra)"),
+                    BlockNumber::new_or_panic(6)
+                )],
+                continuation_token: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn class_not_found() {
+        let context = setup();
+        let input = Input {
+            class_hash: class_hash_bytes!(b"unknown"),
+            ..input(10, None)
+        };
+
+        let error = get_class_usage(context, input).await.unwrap_err();
+
+        assert_matches!(error, Error::ClassHashNotFound);
+    }
+
+    #[tokio::test]
+    async fn page_size_too_big() {
+        let context = setup();
+
+        let error = get_class_usage(context, input(MAX_CHUNK_SIZE + 1, None))
+            .await
+            .unwrap_err();
+
+        assert_matches!(error, Error::PageSizeTooBig);
+    }
+
+    #[tokio::test]
+    async fn invalid_continuation_token() {
+        let context = setup();
+
+        let error = get_class_usage(context, input(10, Some("invalid")))
+            .await
+            .unwrap_err();
+
+        assert_matches!(error, Error::InvalidContinuationToken);
+    }
+}
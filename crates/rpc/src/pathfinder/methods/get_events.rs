@@ -0,0 +1,1143 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context;
+use pathfinder_common::transaction::TransactionKind;
+use pathfinder_common::{
+    BlockHeader,
+    BlockNumber,
+    BlockTimestamp,
+    ContractAddress,
+    SequencerAddress,
+    StarknetVersion,
+};
+use serde::de::Error;
+
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+use crate::method::get_events::{
+    CorruptBlockWarning,
+    EmittedEvent,
+    EventFilter,
+    GetEventsError,
+    GetEventsInput,
+    GetEventsResult,
+};
+
+/// Maximum number of contracts in the `from_addresses` filter.
+pub const FROM_ADDRESSES_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    filter: EventFilter,
+    /// Event fields to include in the response, all fields if [`None`].
+    fields: Option<Vec<EventField>>,
+    /// Include the timestamp of the emitting block in each event.
+    include_timestamps: bool,
+    /// Include the number of events emitted by each block spanned by the
+    /// returned events.
+    include_block_stats: bool,
+    /// Include the timestamp, sequencer address and Starknet version of the
+    /// emitting block in each event.
+    include_block_context: bool,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                filter: value.deserialize_map("filter", |value| {
+                    let mut filter = EventFilter::deserialize_fields(value)?;
+                    filter.transaction_types = value
+                        .deserialize_optional_array("transaction_type", |value| {
+                            value.deserialize::<TransactionType>().map(|t| t.0)
+                        })?;
+                    filter.from_addresses = value
+                        .deserialize_optional_array("from_addresses", |value| {
+                            value.deserialize().map(ContractAddress)
+                        })?;
+                    if let Some(addresses) = &filter.from_addresses {
+                        if filter.address.is_some() {
+                            return Err(serde_json::Error::custom(
+                                "address and from_addresses are mutually exclusive",
+                            ));
+                        }
+                        if addresses.is_empty() || addresses.len() > FROM_ADDRESSES_LIMIT {
+                            return Err(serde_json::Error::custom(format!(
+                                "from_addresses must contain between 1 and {FROM_ADDRESSES_LIMIT} \
+                                 addresses"
+                            )));
+                        }
+                    }
+                    Ok(filter)
+                })?,
+                fields: value.deserialize_optional_array("fields", |value| value.deserialize())?,
+                include_timestamps: value
+                    .deserialize_optional_serde("include_timestamps")?
+                    .unwrap_or_default(),
+                include_block_stats: value
+                    .deserialize_optional_serde("include_block_stats")?
+                    .unwrap_or_default(),
+                include_block_context: value
+                    .deserialize_optional_serde("include_block_context")?
+                    .unwrap_or_default(),
+            })
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventField {
+    Keys,
+    Data,
+    FromAddress,
+    BlockHash,
+    BlockNumber,
+    TransactionHash,
+}
+
+impl crate::dto::DeserializeForVersion for EventField {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        let value: String = value.deserialize_serde()?;
+        match value.as_str() {
+            "keys" => Ok(Self::Keys),
+            "data" => Ok(Self::Data),
+            "from_address" => Ok(Self::FromAddress),
+            "block_hash" => Ok(Self::BlockHash),
+            "block_number" => Ok(Self::BlockNumber),
+            "transaction_hash" => Ok(Self::TransactionHash),
+            _ => Err(serde_json::Error::custom("Invalid event field")),
+        }
+    }
+}
+
+struct TransactionType(TransactionKind);
+
+impl crate::dto::DeserializeForVersion for TransactionType {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        let value: String = value.deserialize_serde()?;
+        let kind = match value.as_str() {
+            "INVOKE" => TransactionKind::Invoke,
+            "DECLARE" => TransactionKind::Declare,
+            "DEPLOY" => TransactionKind::Deploy,
+            "DEPLOY_ACCOUNT" => TransactionKind::DeployAccount,
+            "L1_HANDLER" => TransactionKind::L1Handler,
+            _ => return Err(serde_json::Error::custom("Invalid transaction type")),
+        };
+        Ok(Self(kind))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    result: GetEventsResult,
+    fields: Option<Vec<EventField>>,
+    /// Timestamps of the blocks in `result`, if requested.
+    timestamps: Option<HashMap<BlockNumber, BlockTimestamp>>,
+    /// Number of events emitted by each block spanned by `result`, if
+    /// requested.
+    block_event_counts: Option<BTreeMap<BlockNumber, usize>>,
+    /// Context of the blocks in `result`, if requested.
+    block_contexts: Option<HashMap<BlockNumber, BlockContext>>,
+}
+
+/// Properties of the block which emitted an event.
+#[derive(Debug, PartialEq, Eq)]
+struct BlockContext {
+    timestamp: BlockTimestamp,
+    sequencer_address: SequencerAddress,
+    starknet_version: StarknetVersion,
+}
+
+impl From<BlockHeader> for BlockContext {
+    fn from(header: BlockHeader) -> Self {
+        Self {
+            timestamp: header.timestamp,
+            sequencer_address: header.sequencer_address,
+            starknet_version: header.starknet_version,
+        }
+    }
+}
+
+/// Same as `starknet_getEvents`, with additional filters on the type of the
+/// transaction that emitted the events and on multiple emitting contracts,
+/// optional projection of the returned event fields, optional block timestamps
+/// or block context, and optional per block event counts.
+pub async fn get_events(context: RpcContext, input: Input) -> Result<Output, GetEventsError> {
+    let result = crate::method::get_events(
+        context.clone(),
+        GetEventsInput {
+            filter: input.filter,
+        },
+    )
+    .await?;
+
+    let timestamps = if input.include_timestamps {
+        Some(block_timestamps(context.clone(), &result).await?)
+    } else {
+        None
+    };
+
+    let block_event_counts = if input.include_block_stats {
+        Some(block_event_counts(context.clone(), &result).await?)
+    } else {
+        None
+    };
+
+    let block_contexts = if input.include_block_context {
+        Some(block_contexts(context, &result).await?)
+    } else {
+        None
+    };
+
+    Ok(Output {
+        result,
+        fields: input.fields,
+        timestamps,
+        block_event_counts,
+        block_contexts,
+    })
+}
+
+/// Reads the timestamps of the blocks of the events. Pending events have no
+/// block number and are skipped.
+async fn block_timestamps(
+    context: RpcContext,
+    result: &GetEventsResult,
+) -> anyhow::Result<HashMap<BlockNumber, BlockTimestamp>> {
+    read_per_block(context, result, |tx, block| {
+        tx.block_timestamp(block)
+            .context("Querying block timestamp")
+    })
+    .await
+}
+
+/// Reads the context of the blocks of the events. Pending events have no block
+/// number and are skipped.
+async fn block_contexts(
+    context: RpcContext,
+    result: &GetEventsResult,
+) -> anyhow::Result<HashMap<BlockNumber, BlockContext>> {
+    read_per_block(context, result, |tx, block| {
+        let header = tx
+            .block_header(block.into())
+            .context("Querying block header")?;
+        Ok(header.map(BlockContext::from))
+    })
+    .await
+}
+
+/// Reads a value for each block of the events from the database and
+/// [event shards](RpcContext::event_shards), using the first storage which has
+/// the block. Pending events have no block number and are skipped.
+async fn read_per_block<T: Send + 'static>(
+    context: RpcContext,
+    result: &GetEventsResult,
+    read: fn(&pathfinder_storage::Transaction<'_>, BlockNumber) -> anyhow::Result<Option<T>>,
+) -> anyhow::Result<HashMap<BlockNumber, T>> {
+    let mut blocks = result
+        .events
+        .iter()
+        .filter_map(|event| event.block_number)
+        .collect::<Vec<_>>();
+    blocks.dedup();
+
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || {
+        let _g = span.enter();
+
+        let mut values = HashMap::with_capacity(blocks.len());
+        for storage in std::iter::once(&context.storage).chain(&context.event_shards) {
+            if blocks.is_empty() {
+                break;
+            }
+
+            let mut db = storage
+                .connection()
+                .context("Opening database connection")?;
+            let tx = db.transaction().context("Creating database transaction")?;
+
+            let mut missing = Vec::new();
+            for block in blocks {
+                match read(&tx, block)? {
+                    Some(value) => {
+                        values.insert(block, value);
+                    }
+                    None => missing.push(block),
+                }
+            }
+            blocks = missing;
+        }
+
+        anyhow::ensure!(blocks.is_empty(), "Block headers missing for {blocks:?}");
+
+        Ok(values)
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+/// Reads the total number of events emitted by each block from the first to
+/// the last block of the events, regardless of the filter, from the database
+/// and [event shards](RpcContext::event_shards). Blocks without matching
+/// events in between are included, pending events are skipped.
+async fn block_event_counts(
+    context: RpcContext,
+    result: &GetEventsResult,
+) -> anyhow::Result<BTreeMap<BlockNumber, usize>> {
+    let mut blocks = result.events.iter().filter_map(|event| event.block_number);
+    let Some(first) = blocks.next() else {
+        return Ok(BTreeMap::new());
+    };
+    let last = blocks.last().unwrap_or(first);
+
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || {
+        let _g = span.enter();
+
+        let mut counts = BTreeMap::new();
+        for storage in std::iter::once(&context.storage).chain(&context.event_shards) {
+            let mut db = storage
+                .connection()
+                .context("Opening database connection")?;
+            let tx = db.transaction().context("Creating database transaction")?;
+
+            counts.extend(
+                tx.block_event_counts(first, last)
+                    .context("Querying block event counts")?,
+            );
+        }
+
+        Ok(counts)
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        if self.fields.is_none()
+            && self.timestamps.is_none()
+            && self.block_event_counts.is_none()
+            && self.block_contexts.is_none()
+        {
+            return self.result.serialize(serializer);
+        }
+
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_iter(
+            "events",
+            self.result.events.len(),
+            &mut self.result.events.iter().map(|event| ProjectedEvent {
+                event,
+                fields: self.fields.as_deref(),
+                timestamps: self.timestamps.as_ref(),
+                block_contexts: self.block_contexts.as_ref(),
+            }),
+        )?;
+        serializer.serialize_optional(
+            "continuation_token",
+            self.result.continuation_token.as_ref(),
+        )?;
+        if !self.result.corrupt_blocks.is_empty() {
+            serializer.serialize_iter(
+                "warnings",
+                self.result.corrupt_blocks.len(),
+                &mut self
+                    .result
+                    .corrupt_blocks
+                    .iter()
+                    .copied()
+                    .map(CorruptBlockWarning),
+            )?;
+        }
+        serializer.serialize_optional(
+            "block_event_counts",
+            self.block_event_counts.as_ref().map(BlockEventCounts),
+        )?;
+
+        serializer.end()
+    }
+}
+
+/// Serialized as an object keyed by block number.
+struct BlockEventCounts<'a>(&'a BTreeMap<BlockNumber, usize>);
+
+impl SerializeForVersion for BlockEventCounts<'_> {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        // Block numbers are not known up front, so the object is built directly.
+        let mut counts = serde_json::Map::new();
+        for (block, count) in self.0 {
+            counts.insert(
+                block.get().to_string(),
+                serializer.serialize_u64(*count as u64)?,
+            );
+        }
+        Ok(serde_json::Value::Object(counts))
+    }
+}
+
+struct ProjectedEvent<'a> {
+    event: &'a EmittedEvent,
+    /// All fields if [`None`].
+    fields: Option<&'a [EventField]>,
+    /// Adds `block_timestamp` to the event if present.
+    timestamps: Option<&'a HashMap<BlockNumber, BlockTimestamp>>,
+    /// Adds `block_timestamp`, `sequencer_address` and `starknet_version` to
+    /// the event if present.
+    block_contexts: Option<&'a HashMap<BlockNumber, BlockContext>>,
+}
+
+impl SerializeForVersion for ProjectedEvent<'_> {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        let event = self.event;
+        match self.fields {
+            Some(fields) => serialize_fields(&mut serializer, event, fields)?,
+            None => serializer.flatten(event)?,
+        }
+
+        if let Some(block_contexts) = self.block_contexts {
+            if let Some(block_context) = event
+                .block_number
+                .and_then(|block| block_contexts.get(&block))
+            {
+                serializer.serialize_field("block_timestamp", &block_context.timestamp.get())?;
+                serializer.serialize_field(
+                    "sequencer_address",
+                    &dto::Felt(&block_context.sequencer_address.0),
+                )?;
+                serializer.serialize_field(
+                    "starknet_version",
+                    &block_context.starknet_version.to_string(),
+                )?;
+            }
+        } else if let Some(timestamps) = self.timestamps {
+            let timestamp = event
+                .block_number
+                .and_then(|block| timestamps.get(&block))
+                .map(|timestamp| timestamp.get());
+            serializer.serialize_optional("block_timestamp", timestamp)?;
+        }
+
+        serializer.end()
+    }
+}
+
+fn serialize_fields(
+    serializer: &mut serialize::SerializeStruct,
+    event: &EmittedEvent,
+    fields: &[EventField],
+) -> Result<(), serialize::Error> {
+    for field in fields {
+        match field {
+            EventField::Keys => serializer.serialize_iter(
+                "keys",
+                event.keys.len(),
+                &mut event.keys.iter().map(|k| k.0),
+            )?,
+            EventField::Data => serializer.serialize_iter(
+                "data",
+                event.data.len(),
+                &mut event.data.iter().map(|d| d.0),
+            )?,
+            EventField::FromAddress => {
+                serializer.serialize_field("from_address", &dto::Address(&event.from_address))?
+            }
+            EventField::BlockHash => serializer
+                .serialize_optional("block_hash", event.block_hash.as_ref().map(dto::BlockHash))?,
+            EventField::BlockNumber => serializer
+                .serialize_optional("block_number", event.block_number.map(dto::BlockNumber))?,
+            EventField::TransactionHash => serializer
+                .serialize_field("transaction_hash", &dto::TxnHash(&event.transaction_hash))?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_storage::test_utils;
+    use pretty_assertions_sorted::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    #[rstest::rstest]
+    #[case::invoke("INVOKE", TransactionKind::Invoke)]
+    #[case::declare("DECLARE", TransactionKind::Declare)]
+    #[case::deploy("DEPLOY", TransactionKind::Deploy)]
+    #[case::deploy_account("DEPLOY_ACCOUNT", TransactionKind::DeployAccount)]
+    #[case::l1_handler("L1_HANDLER", TransactionKind::L1Handler)]
+    fn parsing(#[case] transaction_type: &str, #[case] kind: TransactionKind) {
+        let input = json!({
+            "filter": {
+                "chunk_size": 5,
+                "transaction_type": [transaction_type],
+            }
+        });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        let expected = Input {
+            filter: EventFilter {
+                chunk_size: 5,
+                transaction_types: Some(vec![kind]),
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    fn parsing_invalid_transaction_type() {
+        let input = json!({
+            "filter": {
+                "chunk_size": 5,
+                "transaction_type": ["INVALID"],
+            }
+        });
+
+        Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap_err();
+    }
+
+    #[rstest::rstest]
+    #[case::invoke(vec![TransactionKind::Invoke])]
+    #[case::declare(vec![TransactionKind::Declare])]
+    #[case::deploy(vec![TransactionKind::Deploy])]
+    #[case::deploy_account(vec![TransactionKind::DeployAccount])]
+    #[case::l1_handler(vec![TransactionKind::L1Handler])]
+    #[case::invoke_and_deploy(vec![TransactionKind::Invoke, TransactionKind::Deploy])]
+    #[tokio::test]
+    async fn filter_by_transaction_type(#[case] transaction_types: Vec<TransactionKind>) {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let transaction_kinds: HashMap<_, _> = test_data
+            .transactions
+            .iter()
+            .map(|tx| (tx.hash, tx.variant.kind()))
+            .collect();
+        let expected = test_data
+            .events
+            .into_iter()
+            .filter(|event| transaction_types.contains(&transaction_kinds[&event.transaction_hash]))
+            .map(EmittedEvent::from)
+            .collect::<Vec<_>>();
+
+        let input = Input {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                transaction_types: Some(transaction_types),
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        let result = get_events(context, input).await.unwrap();
+
+        assert_eq!(
+            result.result,
+            GetEventsResult {
+                events: expected,
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parsing_from_addresses() {
+        let input = json!({
+            "filter": {
+                "chunk_size": 5,
+                "from_addresses": ["0x1", "0x2"],
+            }
+        });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input.filter.from_addresses,
+            Some(vec![contract_address!("0x1"), contract_address!("0x2")])
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::empty(json!([]), None)]
+    #[case::too_many(json!(vec!["0x1"; FROM_ADDRESSES_LIMIT + 1]), None)]
+    #[case::with_address(json!(["0x1"]), Some(json!("0x2")))]
+    fn parsing_invalid_from_addresses(
+        #[case] from_addresses: serde_json::Value,
+        #[case] address: Option<serde_json::Value>,
+    ) {
+        let mut input = json!({
+            "filter": {
+                "chunk_size": 5,
+                "from_addresses": from_addresses,
+            }
+        });
+        if let Some(address) = address {
+            input["filter"]["address"] = address;
+        }
+
+        Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap_err();
+    }
+
+    #[test]
+    fn parsing_from_addresses_at_limit() {
+        let input = json!({
+            "filter": {
+                "chunk_size": 5,
+                "from_addresses": vec!["0x1"; FROM_ADDRESSES_LIMIT],
+            }
+        });
+
+        Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn filter_by_multiple_addresses() {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let addresses = vec![
+            test_data.events[0].from_address,
+            test_data.events[test_utils::NUM_EVENTS - 1].from_address,
+        ];
+        let expected = test_data
+            .events
+            .into_iter()
+            .filter(|event| addresses.contains(&event.from_address))
+            .map(EmittedEvent::from)
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len(), 2);
+
+        let input = Input {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                from_addresses: Some(addresses),
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        let result = get_events(context, input).await.unwrap();
+
+        assert_eq!(
+            result.result,
+            GetEventsResult {
+                events: expected,
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parsing_fields() {
+        let input = json!({
+            "filter": {
+                "chunk_size": 5,
+            },
+            "fields": ["keys", "transaction_hash"],
+        });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        let expected = Input {
+            filter: EventFilter {
+                chunk_size: 5,
+                ..Default::default()
+            },
+            fields: Some(vec![EventField::Keys, EventField::TransactionHash]),
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    fn parsing_invalid_field() {
+        let input = json!({
+            "filter": {
+                "chunk_size": 5,
+            },
+            "fields": ["class_hash"],
+        });
+
+        Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap_err();
+    }
+
+    #[rstest::rstest]
+    #[case::keys(vec![EventField::Keys], &["keys"])]
+    #[case::data(vec![EventField::Data], &["data"])]
+    #[case::from_address(vec![EventField::FromAddress], &["from_address"])]
+    #[case::block_hash(vec![EventField::BlockHash], &["block_hash"])]
+    #[case::block_number(vec![EventField::BlockNumber], &["block_number"])]
+    #[case::transaction_hash(vec![EventField::TransactionHash], &["transaction_hash"])]
+    #[case::keys_and_data(vec![EventField::Keys, EventField::Data], &["data", "keys"])]
+    #[case::keys_and_transaction_hash(
+        vec![EventField::Keys, EventField::TransactionHash],
+        &["keys", "transaction_hash"]
+    )]
+    #[case::all(
+        vec![
+            EventField::Keys,
+            EventField::Data,
+            EventField::FromAddress,
+            EventField::BlockHash,
+            EventField::BlockNumber,
+            EventField::TransactionHash,
+        ],
+        &["block_hash", "block_number", "data", "from_address", "keys", "transaction_hash"]
+    )]
+    #[case::none(vec![], &[])]
+    #[tokio::test]
+    async fn field_projection(#[case] fields: Vec<EventField>, #[case] expected: &[&str]) {
+        let (storage, _) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let input = Input {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                ..Default::default()
+            },
+            fields: Some(fields),
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        let full = get_events(
+            context.clone(),
+            Input {
+                fields: None,
+                ..input.clone()
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(Serializer::new(RpcVersion::PathfinderV01))
+        .unwrap();
+        let projected = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let full_events = full["events"].as_array().unwrap();
+        let projected_events = projected["events"].as_array().unwrap();
+        assert_eq!(projected_events.len(), test_utils::NUM_EVENTS);
+
+        for (full, projected) in full_events.iter().zip(projected_events) {
+            let projected = projected.as_object().unwrap();
+            let mut keys = projected.keys().map(String::as_str).collect::<Vec<_>>();
+            keys.sort();
+            assert_eq!(keys, expected);
+
+            for (key, value) in projected {
+                assert_eq!(value, &full[key]);
+            }
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::enabled(json!(true), true)]
+    #[case::disabled(json!(false), false)]
+    #[case::default(serde_json::Value::Null, false)]
+    fn parsing_include_timestamps(#[case] flag: serde_json::Value, #[case] expected: bool) {
+        let mut input = json!({ "filter": { "chunk_size": 5 } });
+        if !flag.is_null() {
+            input["include_timestamps"] = flag;
+        }
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(input.include_timestamps, expected);
+    }
+
+    #[rstest::rstest]
+    #[case::all_fields(None)]
+    #[case::projected(Some(vec![EventField::BlockNumber]))]
+    #[tokio::test]
+    async fn timestamps_match_block_headers(#[case] fields: Option<Vec<EventField>>) {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let input = Input {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                ..Default::default()
+            },
+            fields: fields.clone(),
+            include_timestamps: true,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let events = output["events"].as_array().unwrap();
+        assert_eq!(events.len(), test_utils::NUM_EVENTS);
+        for (event, expected) in events.iter().zip(&test_data.events) {
+            let header = test_data
+                .headers
+                .iter()
+                .find(|header| header.number == expected.block_number)
+                .unwrap();
+            assert_eq!(event["block_timestamp"], json!(header.timestamp.get()));
+            assert_eq!(event["block_number"], json!(header.number.get()));
+        }
+
+        if fields.is_some() {
+            let mut keys = events[0]
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            keys.sort();
+            assert_eq!(keys, ["block_number", "block_timestamp"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn timestamps_are_not_included_by_default() {
+        let (storage, _) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let input = Input {
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        for event in output["events"].as_array().unwrap() {
+            assert!(event.get("block_timestamp").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_events_have_no_timestamp() {
+        let context = RpcContext::for_tests_with_pending().await;
+
+        let input = Input {
+            filter: EventFilter {
+                to_block: Some(pathfinder_common::BlockId::Pending),
+                chunk_size: 1024,
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: true,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let events = output["events"].as_array().unwrap();
+        // One finalized event from the genesis block, followed by the pending events.
+        assert!(events.len() > 1);
+        assert_eq!(events[0]["block_timestamp"], json!(0));
+        for event in &events[1..] {
+            assert!(event.get("block_number").is_none());
+            assert!(event.get("block_timestamp").is_none());
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::enabled(json!(true), true)]
+    #[case::disabled(json!(false), false)]
+    #[case::default(serde_json::Value::Null, false)]
+    fn parsing_include_block_context(#[case] flag: serde_json::Value, #[case] expected: bool) {
+        let mut input = json!({ "filter": { "chunk_size": 5 } });
+        if !flag.is_null() {
+            input["include_block_context"] = flag;
+        }
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(input.include_block_context, expected);
+    }
+
+    #[rstest::rstest]
+    #[case::all_fields(None)]
+    #[case::projected(Some(vec![EventField::BlockNumber]))]
+    #[tokio::test]
+    async fn block_context(#[case] fields: Option<Vec<EventField>>) {
+        let context = RpcContext::for_tests().with_storage(setup_block_stats_storage());
+
+        let input = Input {
+            filter: EventFilter {
+                chunk_size: 1024,
+                ..Default::default()
+            },
+            fields: fields.clone(),
+            include_timestamps: true,
+            include_block_stats: false,
+            include_block_context: true,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let events = output["events"].as_array().unwrap();
+        let expected_blocks = [0, 0, 1, 2, 2, 2];
+        assert_eq!(events.len(), expected_blocks.len());
+        for (event, block) in events.iter().zip(expected_blocks) {
+            let expected = json!({
+                "block_number": block,
+                "block_timestamp": 1000 + block,
+                "sequencer_address": format!("{:#x}", 0x300 + block),
+                "starknet_version": format!("0.13.{block}"),
+            });
+            for (key, value) in expected.as_object().unwrap() {
+                assert_eq!(&event[key], value, "{key} of event in block {block}");
+            }
+        }
+
+        if fields.is_some() {
+            let mut keys = events[0]
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            keys.sort();
+            assert_eq!(
+                keys,
+                [
+                    "block_number",
+                    "block_timestamp",
+                    "sequencer_address",
+                    "starknet_version"
+                ]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_events_have_no_block_context() {
+        let context = RpcContext::for_tests_with_pending().await;
+
+        let input = Input {
+            filter: EventFilter {
+                to_block: Some(pathfinder_common::BlockId::Pending),
+                chunk_size: 1024,
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: true,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let events = output["events"].as_array().unwrap();
+        // One finalized event from the genesis block, followed by the pending events.
+        assert!(events.len() > 1);
+        assert!(events[0].get("starknet_version").is_some());
+        for event in &events[1..] {
+            assert!(event.get("block_timestamp").is_none());
+            assert!(event.get("sequencer_address").is_none());
+            assert!(event.get("starknet_version").is_none());
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::enabled(json!(true), true)]
+    #[case::disabled(json!(false), false)]
+    #[case::default(serde_json::Value::Null, false)]
+    fn parsing_include_block_stats(#[case] flag: serde_json::Value, #[case] expected: bool) {
+        let mut input = json!({ "filter": { "chunk_size": 5 } });
+        if !flag.is_null() {
+            input["include_block_stats"] = flag;
+        }
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(input.include_block_stats, expected);
+    }
+
+    /// Blocks 0 to 3, where blocks 0 and 2 emit events with key `0xa`, and all
+    /// blocks except block 3 emit events with key `0xb`. Each block's header
+    /// holds its actual event count, and a timestamp, sequencer address and
+    /// Starknet version which differ between blocks.
+    fn setup_block_stats_storage() -> pathfinder_storage::Storage {
+        use pathfinder_common::event::Event;
+        use pathfinder_common::macro_prelude::*;
+        use pathfinder_common::receipt::Receipt;
+        use pathfinder_common::transaction::{Transaction, TransactionVariant};
+        use pathfinder_common::{BlockHash, BlockHeader, EventKey, TransactionHash};
+        use pathfinder_crypto::Felt;
+
+        let storage = pathfinder_storage::StorageBuilder::in_memory().unwrap();
+        let mut db = storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let block_keys = [vec![0xa, 0xb], vec![0xb], vec![0xa, 0xb, 0xb], vec![]];
+        let mut header =
+            BlockHeader::builder().finalize_with_hash(BlockHash(Felt::from_u64(0x100)));
+        for (number, keys) in block_keys.iter().enumerate() {
+            if number > 0 {
+                header = header
+                    .child_builder()
+                    .finalize_with_hash(BlockHash(Felt::from_u64(0x100 + number as u64)));
+            }
+            header.event_count = keys.len();
+            header.timestamp = BlockTimestamp::new_or_panic(1000 + number as u64);
+            header.sequencer_address = SequencerAddress(Felt::from_u64(0x300 + number as u64));
+            header.starknet_version = StarknetVersion::new(0, 13, number as u8, 0);
+            tx.insert_block_header(&header).unwrap();
+
+            let transaction = Transaction {
+                hash: TransactionHash(Felt::from_u64(0x200 + number as u64)),
+                variant: TransactionVariant::InvokeV1(Default::default()),
+            };
+            let receipt = Receipt {
+                transaction_hash: transaction.hash,
+                ..Default::default()
+            };
+            let events = keys
+                .iter()
+                .map(|key| Event {
+                    data: vec![],
+                    from_address: contract_address!("0x1"),
+                    keys: vec![EventKey(Felt::from_u64(*key))],
+                })
+                .collect();
+            tx.insert_transaction_data(header.number, &[(transaction, receipt)], Some(&[events]))
+                .unwrap();
+        }
+        tx.commit().unwrap();
+
+        storage
+    }
+
+    #[tokio::test]
+    async fn block_event_counts() {
+        let context = RpcContext::for_tests().with_storage(setup_block_stats_storage());
+
+        let input = Input {
+            filter: EventFilter {
+                keys: vec![vec![pathfinder_common::EventKey(
+                    pathfinder_crypto::Felt::from_u64(0xa),
+                )]],
+                chunk_size: 1024,
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: true,
+            include_block_context: false,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(output["events"].as_array().unwrap().len(), 2);
+        // Counts include events which do not match the filter, as well as blocks
+        // in between without any matching events.
+        assert_eq!(
+            output["block_event_counts"],
+            json!({ "0": 2, "1": 1, "2": 3 })
+        );
+    }
+
+    #[tokio::test]
+    async fn block_event_counts_without_events() {
+        let context = RpcContext::for_tests().with_storage(setup_block_stats_storage());
+
+        let input = Input {
+            filter: EventFilter {
+                keys: vec![vec![pathfinder_common::EventKey(
+                    pathfinder_crypto::Felt::from_u64(0xc),
+                )]],
+                chunk_size: 1024,
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: true,
+            include_block_context: false,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(output["events"], json!([]));
+        assert_eq!(output["block_event_counts"], json!({}));
+    }
+
+    #[tokio::test]
+    async fn block_event_counts_are_not_included_by_default() {
+        let context = RpcContext::for_tests().with_storage(setup_block_stats_storage());
+
+        let input = Input {
+            filter: EventFilter {
+                chunk_size: 1024,
+                ..Default::default()
+            },
+            fields: None,
+            include_timestamps: false,
+            include_block_stats: false,
+            include_block_context: false,
+        };
+        let output = get_events(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert!(output.get("block_event_counts").is_none());
+    }
+}
@@ -0,0 +1,232 @@
+use anyhow::Context;
+use pathfinder_common::{BlockId, BlockTimestamp};
+use serde::de::Error;
+
+use crate::context::RpcContext;
+use crate::method::get_events::{EventFilter, GetEventsError, GetEventsInput, GetEventsResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    from_timestamp: BlockTimestamp,
+    to_timestamp: BlockTimestamp,
+    /// The block range is replaced by the blocks within the timestamp range.
+    filter: EventFilter,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            let timestamp = |value: u64| {
+                BlockTimestamp::new(value)
+                    .ok_or_else(|| serde_json::Error::custom("Invalid timestamp"))
+            };
+
+            value.deserialize_map("filter", |value| {
+                let from_timestamp = timestamp(value.deserialize_serde("from_timestamp")?)?;
+                let to_timestamp = timestamp(value.deserialize_serde("to_timestamp")?)?;
+
+                let filter = EventFilter::deserialize_fields(value)?;
+                if filter.from_block.is_some() || filter.to_block.is_some() {
+                    return Err(serde_json::Error::custom(
+                        "Block range cannot be combined with a timestamp range",
+                    ));
+                }
+
+                Ok(Self {
+                    from_timestamp,
+                    to_timestamp,
+                    filter,
+                })
+            })
+        })
+    }
+}
+
+/// Same as `starknet_getEvents`, except that the events are filtered by the
+/// timestamp of the emitting block instead of by block number.
+///
+/// Both ends of the timestamp range are inclusive. Pending events are not
+/// included.
+pub async fn get_events_in_time_range(
+    context: RpcContext,
+    input: Input,
+) -> Result<GetEventsResult, GetEventsError> {
+    let span = tracing::Span::current();
+    let storage = context.storage.clone();
+    let range = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+        let _g = span.enter();
+        let mut db = storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        tx.block_range_for_timestamps(input.from_timestamp, input.to_timestamp)
+    })
+    .await
+    .context("Joining blocking task")??;
+
+    let Some((first, last)) = range else {
+        return Ok(GetEventsResult {
+            events: vec![],
+            continuation_token: None,
+            corrupt_blocks: vec![],
+        });
+    };
+
+    crate::method::get_events(
+        context,
+        GetEventsInput {
+            filter: EventFilter {
+                from_block: Some(BlockId::Number(first)),
+                to_block: Some(BlockId::Number(last)),
+                ..input.filter
+            },
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use pathfinder_common::BlockNumber;
+    use pathfinder_storage::test_utils;
+    use pretty_assertions_sorted::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::method::get_events::EmittedEvent;
+    use crate::RpcVersion;
+
+    /// The test storage has blocks 0 to 3 with timestamps 500 to 503.
+    fn input(from_timestamp: u64, to_timestamp: u64) -> Input {
+        Input {
+            from_timestamp: BlockTimestamp::new_or_panic(from_timestamp),
+            to_timestamp: BlockTimestamp::new_or_panic(to_timestamp),
+            filter: EventFilter {
+                chunk_size: test_utils::NUM_EVENTS,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        let input = json!({
+            "filter": {
+                "from_timestamp": 500,
+                "to_timestamp": 600,
+                "chunk_size": 5,
+            }
+        });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        let expected = Input {
+            from_timestamp: BlockTimestamp::new_or_panic(500),
+            to_timestamp: BlockTimestamp::new_or_panic(600),
+            filter: EventFilter {
+                chunk_size: 5,
+                ..Default::default()
+            },
+        };
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    fn parsing_with_block_range() {
+        let input = json!({
+            "filter": {
+                "from_timestamp": 500,
+                "to_timestamp": 600,
+                "from_block": "latest",
+                "chunk_size": 5,
+            }
+        });
+
+        Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap_err();
+    }
+
+    #[rstest::rstest]
+    #[case::all(0, 1000, 0..=3)]
+    #[case::middle(501, 502, 1..=2)]
+    #[case::single(503, 503, 3..=3)]
+    #[tokio::test]
+    async fn events_in_range(
+        #[case] from_timestamp: u64,
+        #[case] to_timestamp: u64,
+        #[case] blocks: std::ops::RangeInclusive<u64>,
+    ) {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let expected = test_data
+            .events
+            .into_iter()
+            .filter(|event| blocks.contains(&event.block_number.get()))
+            .map(EmittedEvent::from)
+            .collect::<Vec<_>>();
+
+        let result = get_events_in_time_range(context, input(from_timestamp, to_timestamp))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            GetEventsResult {
+                events: expected,
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::before_first_block(0, 499)]
+    #[case::after_last_block(504, 1000)]
+    #[case::reversed(503, 500)]
+    #[tokio::test]
+    async fn no_blocks_in_range(#[case] from_timestamp: u64, #[case] to_timestamp: u64) {
+        let (storage, _) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let result = get_events_in_time_range(context, input(from_timestamp, to_timestamp))
+            .await
+            .unwrap();
+
+        assert_eq!(result.events, vec![]);
+        assert_eq!(result.continuation_token, None);
+    }
+
+    #[tokio::test]
+    async fn paging() {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let expected = test_data
+            .events
+            .into_iter()
+            .filter(|event| event.block_number >= BlockNumber::new_or_panic(1))
+            .map(EmittedEvent::from)
+            .collect::<Vec<_>>();
+
+        let mut input = input(501, 1000);
+        input.filter.chunk_size = 3;
+
+        let mut events = vec![];
+        loop {
+            let result = get_events_in_time_range(context.clone(), input.clone())
+                .await
+                .unwrap();
+            events.extend(result.events);
+
+            match result.continuation_token {
+                Some(token) => input.filter.continuation_token = Some(token),
+                None => break,
+            }
+        }
+
+        assert_eq!(events, expected);
+    }
+}
@@ -247,8 +247,9 @@ pub async fn get_proof(
         // Use internal error to indicate that the process of querying for a particular
         // block failed, which is not the same as being sure that the block is
         // not in the db.
-        let header = tx
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&tx, block_id)
             .context("Fetching block header")?
             .ok_or(GetProofError::BlockNotFound)?;
 
@@ -397,8 +398,9 @@ pub async fn get_class_proof(
         // Use internal error to indicate that the process of querying for a particular
         // block failed, which is not the same as being sure that the block is
         // not in the db.
-        let header = tx
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&tx, block_id)
             .context("Fetching block header")?
             .ok_or(GetProofError::BlockNotFound)?;
 
@@ -0,0 +1,185 @@
+use anyhow::Context;
+use pathfinder_common::TransactionHash;
+
+use crate::context::RpcContext;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+use crate::method::get_transaction_receipt::{self, read_receipt};
+use crate::method::trace_transaction::{
+    self,
+    complete_trace,
+    trace_locally,
+    TraceTransactionError,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    pub(super) transaction_hash: TransactionHash,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                transaction_hash: value.deserialize("transaction_hash").map(TransactionHash)?,
+            })
+        })
+    }
+}
+
+pub struct Output {
+    pub(super) receipt: get_transaction_receipt::TransactionReceipt,
+    pub(super) trace: trace_transaction::Output,
+}
+
+/// Returns the receipt of a transaction together with its execution trace, as
+/// `starknet_getTransactionReceipt` and `starknet_traceTransaction` would.
+///
+/// Both are read using the same database transaction so that they cannot
+/// disagree about the state of the chain.
+pub async fn get_transaction_receipt_with_trace(
+    context: RpcContext,
+    input: Input,
+) -> Result<Output, TraceTransactionError> {
+    let span = tracing::Span::current();
+    let (receipt, local) = {
+        let context = context.clone();
+        tokio::task::spawn_blocking(move || -> Result<_, TraceTransactionError> {
+            let _g = span.enter();
+
+            let mut db = context
+                .execution_storage
+                .connection()
+                .context("Creating database connection")?;
+            let db = db.transaction().context("Creating database transaction")?;
+
+            let pending = context
+                .pending_data
+                .get(&db)
+                .context("Querying pending data")?;
+
+            let receipt = read_receipt(&db, &pending, input.transaction_hash, false)?
+                .ok_or(TraceTransactionError::TxnHashNotFound)?;
+            let local = trace_locally(&context, &db, &pending, input.transaction_hash)?;
+
+            Ok((receipt, local))
+        })
+        .await
+        .context("Joining blocking task")??
+    };
+
+    // Transactions of old blocks are traced by the gateway, which has to happen
+    // outside of the blocking task.
+    let trace = complete_trace(&context, input.transaction_hash, local).await?;
+
+    Ok(Output { receipt, trace })
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.flatten(&self.receipt)?;
+        serializer.serialize_field("execution_trace", &self.trace)?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::method::trace_block_transactions::tests::{
+        setup_multi_tx_trace_pending_test,
+        setup_multi_tx_trace_test,
+    };
+    use crate::RpcVersion;
+
+    /// Checks that the output matches the separate receipt and trace methods.
+    async fn assert_matches_separate_calls(context: RpcContext, transaction_hash: TransactionHash) {
+        let serializer = Serializer::new(RpcVersion::PathfinderV01);
+
+        let output =
+            get_transaction_receipt_with_trace(context.clone(), Input { transaction_hash })
+                .await
+                .unwrap()
+                .serialize(serializer)
+                .unwrap();
+
+        let mut expected = get_transaction_receipt::get_transaction_receipt(
+            context.clone(),
+            get_transaction_receipt::Input {
+                transaction_hash,
+                include_inner_calls: false,
+                include_fee_trace: false,
+                include_data_gas: false,
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(serializer)
+        .unwrap();
+        let trace = trace_transaction::trace_transaction(
+            context,
+            crate::v06::method::trace_transaction::TraceTransactionInput { transaction_hash },
+        )
+        .await
+        .unwrap()
+        .serialize(serializer)
+        .unwrap();
+        expected["execution_trace"] = trace;
+
+        pretty_assertions_sorted::assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn parsing() {
+        let input = json!({ "transaction_hash": "0x123" });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input,
+            Input {
+                transaction_hash: transaction_hash!("0x123")
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_receipt_and_trace() {
+        let (context, _, traces) = setup_multi_tx_trace_test().await.unwrap();
+
+        for trace in traces {
+            assert_matches_separate_calls(context.clone(), trace.transaction_hash).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_transactions() {
+        let (context, traces) = setup_multi_tx_trace_pending_test().await.unwrap();
+
+        for trace in traces {
+            assert_matches_separate_calls(context.clone(), trace.transaction_hash).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn transaction_not_found() {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            transaction_hash: transaction_hash_bytes!(b"non-existent"),
+        };
+
+        let Err(error) = get_transaction_receipt_with_trace(context, input).await else {
+            panic!("Expected an error");
+        };
+
+        assert_matches!(error, TraceTransactionError::TxnHashNotFound);
+    }
+}
@@ -0,0 +1,143 @@
+use pathfinder_executor::types::{FunctionInvocation, TransactionTrace};
+
+use super::get_transaction_receipt_with_trace::{self, get_transaction_receipt_with_trace, Input};
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+use crate::method::get_transaction_receipt;
+use crate::method::trace_transaction::TraceTransactionError;
+
+pub struct Output {
+    receipt: get_transaction_receipt::TransactionReceipt,
+    /// Unset for L1 handler transactions, which are not validated by an
+    /// account.
+    validate_invocation: Option<FunctionInvocation>,
+}
+
+/// Returns the receipt of a transaction together with the `__validate__` call
+/// of its account, as found in the `validate_invocation` of
+/// `starknet_traceTransaction`.
+///
+/// This lets auditors inspect how an account validated a transaction without
+/// having to fetch and pick apart the whole execution trace.
+pub async fn get_transaction_receipt_with_validation(
+    context: RpcContext,
+    input: Input,
+) -> Result<Output, TraceTransactionError> {
+    let get_transaction_receipt_with_trace::Output { receipt, trace } =
+        get_transaction_receipt_with_trace(context, input).await?;
+
+    let validate_invocation = match trace.trace {
+        TransactionTrace::Declare(trace) => trace.validate_invocation,
+        TransactionTrace::DeployAccount(trace) => trace.validate_invocation,
+        TransactionTrace::Invoke(trace) => trace.validate_invocation,
+        TransactionTrace::L1Handler(_) => None,
+    };
+
+    Ok(Output {
+        receipt,
+        validate_invocation,
+    })
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.flatten(&self.receipt)?;
+        serializer.serialize_optional(
+            "validate_invocation",
+            self.validate_invocation
+                .as_ref()
+                .map(dto::FunctionInvocation),
+        )?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::TransactionHash;
+
+    use super::*;
+    use crate::method::trace_block_transactions::tests::{
+        setup_multi_tx_trace_pending_test,
+        setup_multi_tx_trace_test,
+    };
+    use crate::method::trace_transaction;
+    use crate::RpcVersion;
+
+    /// Checks that the output is the receipt extended by the validation call of
+    /// the trace, and that the account's validation is actually included.
+    async fn assert_matches_separate_calls(context: RpcContext, transaction_hash: TransactionHash) {
+        let serializer = Serializer::new(RpcVersion::PathfinderV01);
+
+        let output =
+            get_transaction_receipt_with_validation(context.clone(), Input { transaction_hash })
+                .await
+                .unwrap()
+                .serialize(serializer)
+                .unwrap();
+
+        let mut expected = get_transaction_receipt::get_transaction_receipt(
+            context.clone(),
+            get_transaction_receipt::Input {
+                transaction_hash,
+                include_inner_calls: false,
+                include_fee_trace: false,
+                include_data_gas: false,
+            },
+        )
+        .await
+        .unwrap()
+        .serialize(serializer)
+        .unwrap();
+        let trace = trace_transaction::trace_transaction(
+            context,
+            crate::v06::method::trace_transaction::TraceTransactionInput { transaction_hash },
+        )
+        .await
+        .unwrap()
+        .serialize(serializer)
+        .unwrap();
+        assert!(trace["validate_invocation"].is_object());
+        expected["validate_invocation"] = trace["validate_invocation"].clone();
+
+        pretty_assertions_sorted::assert_eq!(output, expected);
+    }
+
+    #[tokio::test]
+    async fn account_transactions() {
+        let (context, _, traces) = setup_multi_tx_trace_test().await.unwrap();
+
+        for trace in traces {
+            assert_matches_separate_calls(context.clone(), trace.transaction_hash).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_account_transactions() {
+        let (context, traces) = setup_multi_tx_trace_pending_test().await.unwrap();
+
+        for trace in traces {
+            assert_matches_separate_calls(context.clone(), trace.transaction_hash).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn transaction_not_found() {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            transaction_hash: transaction_hash_bytes!(b"non-existent"),
+        };
+
+        let Err(error) = get_transaction_receipt_with_validation(context, input).await else {
+            panic!("Expected an error");
+        };
+
+        assert_matches!(error, TraceTransactionError::TxnHashNotFound);
+    }
+}
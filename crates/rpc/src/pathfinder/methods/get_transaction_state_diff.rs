@@ -0,0 +1,153 @@
+use pathfinder_common::TransactionHash;
+
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+use crate::method::trace_transaction::{trace_transaction, TraceTransactionError};
+use crate::v06::method::trace_transaction::TraceTransactionInput;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    transaction_hash: TransactionHash,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                transaction_hash: value.deserialize("transaction_hash").map(TransactionHash)?,
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Output(pathfinder_executor::types::TransactionTrace);
+
+/// Returns the part of a block's state diff made by a single transaction.
+///
+/// The state diff is computed by re-executing the transactions of the block,
+/// the same way as for `starknet_traceTransaction`. Transactions of blocks
+/// which cannot be re-executed locally have no state diff available.
+pub async fn get_transaction_state_diff(
+    context: RpcContext,
+    input: Input,
+) -> Result<Output, TraceTransactionError> {
+    let output = trace_transaction(
+        context,
+        TraceTransactionInput {
+            transaction_hash: input.transaction_hash,
+        },
+    )
+    .await?;
+
+    if !output.include_state_diff {
+        return Err(TraceTransactionError::Custom(anyhow::anyhow!(
+            "State diff is not available for transaction {}",
+            input.transaction_hash
+        )));
+    }
+
+    Ok(Output(output.trace))
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        dto::TransactionStateDiff(self.0.state_diff()).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::method::trace_block_transactions::tests::setup_multi_tx_trace_test;
+    use crate::RpcVersion;
+
+    #[test]
+    fn parsing() {
+        let input = json!({ "transaction_hash": "0x123" });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input,
+            Input {
+                transaction_hash: transaction_hash!("0x123")
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_trace_state_diff() {
+        let (context, _, traces) = setup_multi_tx_trace_test().await.unwrap();
+
+        for trace in traces {
+            let input = Input {
+                transaction_hash: trace.transaction_hash,
+            };
+            let output = get_transaction_state_diff(context.clone(), input)
+                .await
+                .unwrap()
+                .serialize(Serializer::new(RpcVersion::PathfinderV01))
+                .unwrap();
+
+            let expected = serde_json::to_value(trace.trace_root).unwrap();
+            pretty_assertions_sorted::assert_eq!(output, expected["state_diff"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn invoke_writes_fee_token_balance() {
+        let (context, _, traces) = setup_multi_tx_trace_test().await.unwrap();
+        // The last transaction of the block is an invoke, which pays its fee to
+        // the sequencer.
+        let input = Input {
+            transaction_hash: traces.last().unwrap().transaction_hash,
+        };
+
+        let output = get_transaction_state_diff(context, input)
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let fee_token = output["storage_diffs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|diff| {
+                diff["address"] == json!(pathfinder_executor::ETH_FEE_TOKEN_ADDRESS.0.to_hex_str())
+            })
+            .unwrap();
+        let keys = fee_token["storage_entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["key"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert!(
+            keys.contains(&"0x5496768776e3db30053404f18067d81a6e06f5a2b0de326e21298fd9d569a9a"),
+            "Sequencer balance missing from {keys:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn transaction_not_found() {
+        let context = RpcContext::for_tests();
+        let input = Input {
+            transaction_hash: transaction_hash_bytes!(b"non-existent"),
+        };
+
+        let error = get_transaction_state_diff(context, input)
+            .await
+            .unwrap_err();
+
+        assert_matches!(error, TraceTransactionError::TxnHashNotFound);
+    }
+}
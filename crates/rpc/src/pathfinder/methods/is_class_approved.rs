@@ -0,0 +1,176 @@
+use anyhow::Context;
+use pathfinder_common::{BlockNumber, ClassHash};
+
+use crate::context::RpcContext;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+
+crate::error::generate_rpc_error_subset!(Error);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    class_hash: ClassHash,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            Ok(Self {
+                class_hash: ClassHash(value.deserialize("class_hash")?),
+            })
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    approved: bool,
+    /// The block in which the approved class was declared, if it has been
+    /// declared yet.
+    added_in_block: Option<BlockNumber>,
+}
+
+/// Returns whether the class hash is in the node's configured list of approved
+/// classes.
+///
+/// This is a check against the node operator's configuration, not against
+/// chain state. For approved classes the block in which the class was declared
+/// is included as well.
+pub async fn is_class_approved(context: RpcContext, input: Input) -> Result<Output, Error> {
+    if !context
+        .config
+        .approved_class_hashes
+        .contains(&input.class_hash)
+    {
+        return Ok(Output {
+            approved: false,
+            added_in_block: None,
+        });
+    }
+
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let added_in_block = tx
+            .class_declaration_block(input.class_hash)
+            .context("Querying class declaration block")?;
+
+        Ok(Output {
+            approved: true,
+            added_in_block,
+        })
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+        serializer.serialize_field("approved", &self.approved)?;
+        serializer.serialize_optional(
+            "added_in_block",
+            self.added_in_block.map(|block| block.get()),
+        )?;
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::StateUpdate;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    const DECLARED_CLASS: ClassHash = class_hash_bytes!(b"approved class");
+    /// Inserted by [RpcContext::for_tests] without being declared in a block.
+    const UNDECLARED_CLASS: ClassHash = class_hash_bytes!(b"class pending hash");
+
+    /// Declares [DECLARED_CLASS] in block 3 and approves it together with
+    /// [UNDECLARED_CLASS].
+    fn setup() -> RpcContext {
+        let mut context = RpcContext::for_tests();
+        context.config.approved_class_hashes = vec![DECLARED_CLASS, UNDECLARED_CLASS];
+
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        tx.insert_cairo_class(DECLARED_CLASS, b"approved class definition")
+            .unwrap();
+        let header = tx
+            .block_header(pathfinder_storage::BlockId::Latest)
+            .unwrap()
+            .unwrap()
+            .child_builder()
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+        tx.insert_state_update(
+            header.number,
+            &StateUpdate::default().with_declared_cairo_class(DECLARED_CLASS),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        context
+    }
+
+    async fn check(context: RpcContext, class_hash: ClassHash) -> serde_json::Value {
+        is_class_approved(context, Input { class_hash })
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let json = json!({"class_hash": "0x123"});
+
+        let parsed =
+            Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            parsed,
+            Input {
+                class_hash: class_hash!("0x123"),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn approved_and_declared() {
+        let output = check(setup(), DECLARED_CLASS).await;
+
+        assert_eq!(output, json!({"approved": true, "added_in_block": 3}));
+    }
+
+    #[tokio::test]
+    async fn approved_but_not_declared() {
+        let output = check(setup(), UNDECLARED_CLASS).await;
+
+        assert_eq!(output, json!({"approved": true}));
+    }
+
+    #[tokio::test]
+    async fn not_approved() {
+        // Known to the node, but not in the configured list.
+        let output = check(setup(), class_hash_bytes!(b"class 0 hash")).await;
+
+        assert_eq!(output, json!({"approved": false}));
+    }
+
+    #[tokio::test]
+    async fn nothing_approved_by_default() {
+        let output = check(RpcContext::for_tests(), DECLARED_CLASS).await;
+
+        assert_eq!(output, json!({"approved": false}));
+    }
+}
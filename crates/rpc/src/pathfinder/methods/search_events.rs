@@ -0,0 +1,223 @@
+use anyhow::Context;
+use pathfinder_common::BlockNumber;
+use pathfinder_crypto::Felt;
+use serde::de::Error as _;
+
+use crate::context::RpcContext;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+use crate::method::get_events::EmittedEvent;
+
+crate::error::generate_rpc_error_subset!(Error);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    /// Lowercase hex string, including the `0x`.
+    prefix: String,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            let block_number = |value: u64| {
+                BlockNumber::new(value)
+                    .ok_or_else(|| serde_json::Error::custom("Invalid block number"))
+            };
+
+            let prefix: String = value.deserialize_serde("prefix")?;
+            let is_hex = prefix
+                .strip_prefix("0x")
+                .is_some_and(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()));
+            if !is_hex {
+                return Err(serde_json::Error::custom(
+                    "Prefix must be a hex string starting with 0x",
+                ));
+            }
+
+            Ok(Self {
+                prefix: prefix.to_ascii_lowercase(),
+                from_block: block_number(value.deserialize_serde("from_block")?)?,
+                to_block: block_number(value.deserialize_serde("to_block")?)?,
+            })
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output {
+    events: Vec<EmittedEvent>,
+    /// The scan stops early if the range is larger than the maximum number of
+    /// blocks `starknet_getEvents` is allowed to scan, in which case the search
+    /// can be continued from the next block.
+    last_scanned_block: Option<BlockNumber>,
+}
+
+/// Returns the events in the block range, inclusive on both ends, with a key
+/// or data value whose hex representation starts with the given prefix.
+///
+/// Values are matched without leading zeros, the same way they are formatted in
+/// responses. Events are stored compressed per block, so each block in the
+/// range is decompressed and searched. Pending events are not included.
+pub async fn search_events(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let max_blocks = context.config.get_events_max_blocks_to_scan.get() as u64;
+        let to_block = input.to_block.min(input.from_block + (max_blocks - 1));
+
+        let (events, last_scanned_block) = tx
+            .events_in_range(input.from_block, to_block, None, vec![])
+            .context("Querying events")?;
+
+        let matches = |felt: &Felt| felt.to_hex_str().starts_with(&input.prefix);
+        let events = events
+            .into_iter()
+            .filter(|event| {
+                event.keys.iter().any(|key| matches(&key.0))
+                    || event.data.iter().any(|data| matches(&data.0))
+            })
+            .map(EmittedEvent::from)
+            .collect();
+
+        Ok(Output {
+            events,
+            last_scanned_block,
+        })
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_iter("events", self.events.len(), &mut self.events.iter())?;
+        serializer.serialize_optional("last_scanned_block", self.last_scanned_block)?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use pathfinder_storage::test_utils;
+    use pretty_assertions_sorted::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    fn input(prefix: &str, from_block: u64, to_block: u64) -> Input {
+        Input {
+            prefix: prefix.to_owned(),
+            from_block: BlockNumber::new_or_panic(from_block),
+            to_block: BlockNumber::new_or_panic(to_block),
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        let json = json!({ "prefix": "0xDEAD", "from_block": 1, "to_block": 10 });
+
+        let parsed =
+            Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(parsed, input("0xdead", 1, 10));
+    }
+
+    #[rstest::rstest]
+    #[case::missing_0x("dead")]
+    #[case::not_hex("0xhello")]
+    #[test]
+    fn parsing_invalid_prefix(#[case] prefix: &str) {
+        let json = json!({ "prefix": prefix, "from_block": 1, "to_block": 10 });
+
+        Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap_err();
+    }
+
+    /// The data of the n-th test event of the first block is `0x` followed by
+    /// n + 3 `c`s, and its keys are `0x` followed by n + 3 `d`s and
+    /// `0xdeadbeef`. Later blocks have longer values.
+    #[rstest::rstest]
+    #[case::data("0xcccccc", 3..test_utils::NUM_EVENTS)]
+    #[case::first_key("0xdddd", 1..test_utils::NUM_EVENTS)]
+    #[case::second_key("0xdeadb", 0..test_utils::NUM_EVENTS)]
+    #[case::no_match("0xcd", 0..0)]
+    #[tokio::test]
+    async fn matching_events(#[case] prefix: &str, #[case] expected: std::ops::Range<usize>) {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let output = search_events(context, input(prefix, 0, 3)).await.unwrap();
+
+        let expected = test_data.events[expected]
+            .iter()
+            .cloned()
+            .map(EmittedEvent::from)
+            .collect();
+        assert_eq!(
+            output,
+            Output {
+                events: expected,
+                last_scanned_block: Some(BlockNumber::new_or_panic(3)),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn scan_is_limited() {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let mut context = RpcContext::for_tests().with_storage(storage);
+        context.config.get_events_max_blocks_to_scan = NonZeroUsize::new(2).unwrap();
+
+        let output = search_events(context, input("0x", 1, 3)).await.unwrap();
+
+        let expected = test_data
+            .events
+            .into_iter()
+            .filter(|event| (1..=2).contains(&event.block_number.get()))
+            .map(EmittedEvent::from)
+            .collect();
+        assert_eq!(
+            output,
+            Output {
+                events: expected,
+                last_scanned_block: Some(BlockNumber::new_or_panic(2)),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn serialization() {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let context = RpcContext::for_tests().with_storage(storage);
+
+        let output = search_events(context, input("0xcccccccccccc", 0, 0))
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        let event = &test_data.events[test_utils::EVENTS_PER_BLOCK - 1];
+        assert_eq!(
+            output,
+            json!({
+                "events": [EmittedEvent::from(event.clone())
+                    .serialize(Serializer::new(RpcVersion::PathfinderV01))
+                    .unwrap()],
+                "last_scanned_block": 0,
+            })
+        );
+    }
+}
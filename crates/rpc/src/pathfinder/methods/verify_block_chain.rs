@@ -0,0 +1,231 @@
+use anyhow::Context;
+use pathfinder_common::{BlockHash, BlockNumber};
+use serde::de::Error as _;
+
+use crate::context::RpcContext;
+use crate::dto;
+use crate::dto::serialize::{self, SerializeForVersion, Serializer};
+
+crate::error::generate_rpc_error_subset!(Error);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            let block_number = |value: u64| {
+                BlockNumber::new(value)
+                    .ok_or_else(|| serde_json::Error::custom("Invalid block number"))
+            };
+
+            Ok(Self {
+                from_block: block_number(value.deserialize_serde("from_block")?)?,
+                to_block: block_number(value.deserialize_serde("to_block")?)?,
+            })
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Output(Vec<BrokenLink>);
+
+/// A block whose parent hash is not the hash of the preceding block.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BrokenLink {
+    block_number: BlockNumber,
+    parent_hash: BlockHash,
+    preceding_block_hash: BlockHash,
+}
+
+/// Checks that the parent hash of each block in the range, inclusive on both
+/// ends, is the hash of the preceding block. Returns the blocks for which this
+/// is not the case, which indicates a corrupted database.
+///
+/// Ranges of more than
+/// [get_events_max_blocks_to_scan](crate::context::RpcConfig::get_events_max_blocks_to_scan)
+/// blocks are rejected.
+pub async fn verify_block_chain(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let max_blocks = context.config.get_events_max_blocks_to_scan.get() as u64;
+    let requested = (input.to_block.get() + 1).saturating_sub(input.from_block.get());
+    if requested > max_blocks {
+        return Err(Error::Custom(anyhow::anyhow!(
+            "Block range too large: requested {requested} blocks, at most {max_blocks} can be \
+             verified at once"
+        )));
+    }
+
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || -> Result<Output, Error> {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let links = tx
+            .broken_parent_hash_links(input.from_block, input.to_block)
+            .context("Querying block hashes")?
+            .into_iter()
+            .map(
+                |(block_number, parent_hash, preceding_block_hash)| BrokenLink {
+                    block_number,
+                    parent_hash,
+                    preceding_block_hash,
+                },
+            )
+            .collect();
+
+        Ok(Output(links))
+    })
+    .await
+    .context("Joining blocking task")?
+}
+
+impl SerializeForVersion for Output {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        serializer.serialize_iter(self.0.len(), &mut self.0.iter())
+    }
+}
+
+impl SerializeForVersion for &'_ BrokenLink {
+    fn serialize(&self, serializer: Serializer) -> Result<serialize::Ok, serialize::Error> {
+        let mut serializer = serializer.serialize_struct()?;
+
+        serializer.serialize_field("block_number", &self.block_number.get())?;
+        serializer.serialize_field("parent_hash", &dto::Felt(&self.parent_hash.0))?;
+        serializer.serialize_field(
+            "preceding_block_hash",
+            &dto::Felt(&self.preceding_block_hash.0),
+        )?;
+
+        serializer.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::BlockHeader;
+    use pathfinder_storage::StorageBuilder;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    fn hash(block_number: u64) -> BlockHash {
+        BlockHash(pathfinder_crypto::Felt::from_u64(0x100 + block_number))
+    }
+
+    /// Blocks 0 to 4, where the parent hashes of blocks 2 and 4 are wrong.
+    fn setup() -> RpcContext {
+        let storage = StorageBuilder::in_memory().unwrap();
+        let mut db = storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+
+        let mut header = BlockHeader::builder().finalize_with_hash(hash(0));
+        tx.insert_block_header(&header).unwrap();
+        for number in 1..5 {
+            let mut builder = header.child_builder();
+            if number % 2 == 0 {
+                builder = builder.parent_hash(block_hash!("0xbad"));
+            }
+            header = builder.finalize_with_hash(hash(number));
+            tx.insert_block_header(&header).unwrap();
+        }
+        tx.commit().unwrap();
+
+        RpcContext::for_tests().with_storage(storage)
+    }
+
+    fn input(from_block: u64, to_block: u64) -> Input {
+        Input {
+            from_block: BlockNumber::new_or_panic(from_block),
+            to_block: BlockNumber::new_or_panic(to_block),
+        }
+    }
+
+    fn broken_link(block_number: u64) -> BrokenLink {
+        BrokenLink {
+            block_number: BlockNumber::new_or_panic(block_number),
+            parent_hash: block_hash!("0xbad"),
+            preceding_block_hash: hash(block_number - 1),
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        let json = json!({ "from_block": 1, "to_block": 10 });
+
+        let parsed =
+            Input::deserialize(crate::dto::Value::new(json, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(parsed, input(1, 10));
+    }
+
+    #[tokio::test]
+    async fn intact_chain() {
+        let context = RpcContext::for_tests();
+
+        let output = verify_block_chain(context, input(0, 2)).await.unwrap();
+
+        assert_eq!(output, Output(vec![]));
+    }
+
+    #[rstest::rstest]
+    #[case::all(0, 4, vec![2, 4])]
+    #[case::preceding_block_outside_range(2, 3, vec![2])]
+    #[case::intact_range(0, 1, vec![])]
+    #[case::past_last_block(3, 100, vec![4])]
+    #[case::reversed(4, 0, vec![])]
+    #[tokio::test]
+    async fn broken_chain(#[case] from: u64, #[case] to: u64, #[case] expected: Vec<u64>) {
+        let context = setup();
+
+        let output = verify_block_chain(context, input(from, to)).await.unwrap();
+
+        let expected = expected.into_iter().map(broken_link).collect();
+        assert_eq!(output, Output(expected));
+    }
+
+    #[tokio::test]
+    async fn range_is_limited() {
+        let mut context = setup();
+        context.config.get_events_max_blocks_to_scan = NonZeroUsize::new(3).unwrap();
+
+        let output = verify_block_chain(context.clone(), input(2, 4))
+            .await
+            .unwrap();
+        assert_eq!(output, Output(vec![broken_link(2), broken_link(4)]));
+
+        let error = verify_block_chain(context, input(1, 4)).await.unwrap_err();
+        assert_matches::assert_matches!(error, Error::Custom(_));
+    }
+
+    #[tokio::test]
+    async fn serialization() {
+        let context = setup();
+
+        let output = verify_block_chain(context, input(0, 2))
+            .await
+            .unwrap()
+            .serialize(Serializer::new(RpcVersion::PathfinderV01))
+            .unwrap();
+
+        assert_eq!(
+            output,
+            json!([{
+                "block_number": 2,
+                "parent_hash": "0xbad",
+                "preceding_block_hash": "0x101",
+            }])
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use pathfinder_common::{BlockId, BlockNumber};
+use serde::de::Error as _;
+
+use crate::context::RpcContext;
+use crate::method::get_block_with_tx_hashes::{
+    get_block_with_tx_hashes,
+    Error,
+    Input as BlockInput,
+    Output,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    block_number: BlockNumber,
+}
+
+impl crate::dto::DeserializeForVersion for Input {
+    fn deserialize(value: crate::dto::Value) -> Result<Self, serde_json::Error> {
+        value.deserialize_map(|value| {
+            let block_number: u64 = value.deserialize_serde("block_number")?;
+            Ok(Self {
+                block_number: BlockNumber::new(block_number)
+                    .ok_or_else(|| serde_json::Error::custom("Invalid block number"))?,
+            })
+        })
+    }
+}
+
+/// Same as `starknet_getBlockWithTxHashes` for the given block number, except
+/// that the request is held open until the block is produced if it does not
+/// exist yet.
+///
+/// Returns [Error::BlockNotFound] if the block is not produced within
+/// [wait_for_block_timeout](crate::context::RpcConfig::wait_for_block_timeout).
+pub async fn wait_for_block(context: RpcContext, input: Input) -> Result<Output, Error> {
+    let block_input = || BlockInput {
+        block_id: BlockId::Number(input.block_number),
+        include_full_transactions: false,
+        response_format: Default::default(),
+        include_block_body_commitment: false,
+        include_transaction_commitment: false,
+        include_event_commitment: false,
+    };
+
+    // Subscribe before querying storage so that a block produced in between is
+    // not missed.
+    let mut latest_block = context.notifications.latest_block.subscribe();
+
+    match get_block_with_tx_hashes(context.clone(), block_input()).await {
+        Err(Error::BlockNotFound) => {}
+        result => return result,
+    }
+
+    let produced = tokio::time::timeout(
+        context.config.wait_for_block_timeout,
+        latest_block.wait_for(|latest| latest.is_some_and(|latest| latest >= input.block_number)),
+    )
+    .await
+    // An error means that sync has shut down.
+    .is_ok_and(|result| result.is_ok());
+
+    if !produced {
+        return Err(Error::BlockNotFound);
+    }
+
+    get_block_with_tx_hashes(context, block_input()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use assert_matches::assert_matches;
+    use pathfinder_common::macro_prelude::*;
+    use pathfinder_common::BlockHeader;
+    use serde_json::json;
+
+    use super::*;
+    use crate::dto::DeserializeForVersion;
+    use crate::RpcVersion;
+
+    fn context_with_timeout(timeout: Duration) -> RpcContext {
+        let mut context = RpcContext::for_tests();
+        context.config.wait_for_block_timeout = timeout;
+        context
+    }
+
+    #[test]
+    fn parsing() {
+        let input = json!({ "block_number": 3 });
+
+        let input =
+            Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap();
+
+        assert_eq!(
+            input,
+            Input {
+                block_number: BlockNumber::new_or_panic(3)
+            }
+        );
+    }
+
+    #[test]
+    fn parsing_out_of_range() {
+        let input = json!({ "block_number": u64::MAX });
+
+        Input::deserialize(crate::dto::Value::new(input, RpcVersion::PathfinderV01)).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn existing_block_is_returned_immediately() {
+        // A timeout this long would fail the test if it was waited for.
+        let context = context_with_timeout(Duration::from_secs(3600));
+        let input = Input {
+            block_number: BlockNumber::GENESIS,
+        };
+
+        let output = wait_for_block(context, input).await.unwrap();
+
+        assert_matches!(
+            output,
+            Output::Full { header, .. } if header.number == BlockNumber::GENESIS
+        );
+    }
+
+    #[tokio::test]
+    async fn waits_for_block_to_be_produced() {
+        let context = context_with_timeout(Duration::from_secs(60));
+        let block_number = BlockNumber::new_or_panic(3);
+        let input = Input { block_number };
+
+        let request = tokio::spawn(wait_for_block(context.clone(), input));
+        // Give the request time to start waiting.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!request.is_finished());
+
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        let header = BlockHeader::builder()
+            .number(block_number)
+            .parent_hash(block_hash_bytes!(b"latest"))
+            .finalize_with_hash(block_hash_bytes!(b"block 3"));
+        tx.insert_block_header(&header).unwrap();
+        tx.commit().unwrap();
+        context
+            .notifications
+            .latest_block
+            .send_replace(Some(block_number));
+
+        let output = request.await.unwrap().unwrap();
+
+        assert_matches!(output, Output::Full { header, .. } if header.number == block_number);
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let context = context_with_timeout(Duration::from_millis(10));
+        let input = Input {
+            block_number: BlockNumber::new_or_panic(3),
+        };
+
+        let error = wait_for_block(context, input).await.unwrap_err();
+
+        assert_matches!(error, Error::BlockNotFound);
+    }
+}
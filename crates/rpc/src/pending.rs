@@ -47,6 +47,7 @@ impl PendingData {
             receipt_commitment: Default::default(),
             state_diff_commitment: Default::default(),
             state_diff_length: Default::default(),
+            block_body_commitment: Default::default(),
         }
     }
 }
@@ -3,6 +3,10 @@ use crate::jsonrpc::{RpcRouter, RpcRouterBuilder};
 pub(crate) mod method;
 pub(crate) mod types;
 
+/// The version of the specification implemented by these routes, returned by
+/// `starknet_specVersion`.
+pub const SPEC_VERSION: &str = "0.6.0";
+
 #[rustfmt::skip]
 pub fn register_routes() -> RpcRouterBuilder {
     RpcRouter::builder(crate::RpcVersion::V06)
@@ -33,7 +37,7 @@ pub fn register_routes() -> RpcRouterBuilder {
         .register("starknet_getTransactionByHash"            , crate::method::get_transaction_by_hash)
         .register("starknet_getTransactionReceipt"           , method::get_transaction_receipt)
         .register("starknet_simulateTransactions"            , method::simulate_transactions)
-        .register("starknet_specVersion"                     , || "0.6.0")
+        .register("starknet_specVersion"                     , || SPEC_VERSION)
         .register("starknet_traceBlockTransactions"          , method::trace_block_transactions)
         .register("starknet_traceTransaction"                , method::trace_transaction)
 
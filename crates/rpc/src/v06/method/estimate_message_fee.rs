@@ -146,8 +146,9 @@ pub(crate) async fn estimate_message_fee_impl(
             }
             other => {
                 let block_id = other.try_into().expect("Only pending cast should fail");
-                let header = db
-                    .block_header(block_id)
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_id)
                     .context("Querying block header")?
                     .ok_or(EstimateMessageFeeError::BlockNotFound)?;
 
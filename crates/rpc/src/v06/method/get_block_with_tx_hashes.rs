@@ -52,8 +52,9 @@ pub async fn get_block_with_tx_hashes(
             other => other.try_into().expect("Only pending cast should fail"),
         };
 
-        let header = transaction
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&transaction, block_id)
             .context("Reading block from database")?
             .ok_or(GetBlockError::BlockNotFound)?;
 
@@ -53,8 +53,9 @@ pub async fn get_block_with_txs(
             other => other.try_into().expect("Only pending cast should fail"),
         };
 
-        let header = transaction
-            .block_header(block_id)
+        let header = context
+            .header_cache
+            .block_header(&transaction, block_id)
             .context("Reading block from database")?
             .ok_or(GetBlockError::BlockNotFound)?;
 
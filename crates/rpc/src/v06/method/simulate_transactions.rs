@@ -140,8 +140,9 @@ pub async fn simulate_transactions_impl(
             other => {
                 let block_id = other.try_into().expect("Only pending should fail");
 
-                let header = db
-                    .block_header(block_id)
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_id)
                     .context("Fetching block header")?
                     .ok_or(SimulateTransactionError::BlockNotFound)?;
 
@@ -199,7 +199,7 @@ pub async fn trace_block_transactions_impl(
 ) -> Result<TraceBlockTransactionsOutput, TraceBlockTransactionsError> {
     enum LocalExecution {
         Success(Vec<Trace>),
-        Unsupported(Vec<Transaction>),
+        Unsupported(BlockId, Vec<Transaction>),
     }
 
     let span = tracing::Span::current();
@@ -230,8 +230,9 @@ pub async fn trace_block_transactions_impl(
             }
             other => {
                 let block_id = other.try_into().expect("Only pending should fail");
-                let header = db
-                    .block_header(block_id)?
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_id)?
                     .ok_or(TraceBlockTransactionsError::BlockNotFound)?;
 
                 let transactions = db
@@ -245,6 +246,13 @@ pub async fn trace_block_transactions_impl(
             }
         };
 
+        // The feeder gateway does not accept all block ids, such as `l1_accepted`, so
+        // the resolved block is forwarded by its hash.
+        let gateway_block_id = match input.block_id {
+            BlockId::Pending => BlockId::Pending,
+            _ => BlockId::Hash(header.hash),
+        };
+
         if header.starknet_version
             < VERSIONS_LOWER_THAN_THIS_SHOULD_FALL_BACK_TO_FETCHING_TRACE_FROM_GATEWAY
         {
@@ -256,6 +264,7 @@ pub async fn trace_block_transactions_impl(
                 }
                 _ => {
                     return Ok::<_, TraceBlockTransactionsError>(LocalExecution::Unsupported(
+                        gateway_block_id,
                         transactions,
                     ))
                 }
@@ -278,7 +287,7 @@ pub async fn trace_block_transactions_impl(
         let traces = match pathfinder_executor::trace(state, cache, hash, executor_transactions) {
             Ok(traces) => traces,
             Err(TransactionExecutionError::ExecutionError { .. }) => {
-                return Ok(LocalExecution::Unsupported(transactions))
+                return Ok(LocalExecution::Unsupported(gateway_block_id, transactions))
             }
             Err(e) => return Err(e.into()),
         };
@@ -298,14 +307,14 @@ pub async fn trace_block_transactions_impl(
     .await
     .context("trace_block_transactions: fetch block & transactions")??;
 
-    let transactions = match traces {
+    let (gateway_block_id, transactions) = match traces {
         LocalExecution::Success(traces) => return Ok(TraceBlockTransactionsOutput(traces)),
-        LocalExecution::Unsupported(transactions) => transactions,
+        LocalExecution::Unsupported(block_id, transactions) => (block_id, transactions),
     };
 
     context
         .sequencer
-        .block_traces(input.block_id)
+        .block_traces(gateway_block_id)
         .await
         .context("Forwarding to feeder gateway")
         .map_err(TraceBlockTransactionsError::from)
@@ -685,6 +694,7 @@ pub(crate) mod tests {
             receipt_commitment: Default::default(),
             state_diff_commitment: Default::default(),
             state_diff_length: 0,
+            block_body_commitment: Default::default(),
         };
         transaction
             .insert_block_header(&BlockHeader {
@@ -723,4 +733,46 @@ pub(crate) mod tests {
         .await
         .unwrap();
     }
+
+    /// Blocks which cannot be traced locally are fetched from the feeder
+    /// gateway, which does not accept every block id.
+    #[tokio::test]
+    async fn gateway_fallback_forwards_block_hash() {
+        use httpmock::prelude::*;
+
+        let storage = pathfinder_storage::StorageBuilder::in_memory().unwrap();
+        let header = BlockHeader::builder()
+            .starknet_version(StarknetVersion::new(0, 13, 1, 0))
+            .finalize_with_hash(block_hash!("0xabcd"));
+        let mut connection = storage.connection().unwrap();
+        let transaction = connection.transaction().unwrap();
+        transaction.insert_block_header(&header).unwrap();
+        transaction
+            .update_l1_l2_pointer(Some(header.number))
+            .unwrap();
+        transaction.commit().unwrap();
+        drop(connection);
+
+        let server = MockServer::start_async().await;
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/feeder_gateway/get_block_traces")
+                .query_param("blockHash", "0xabcd");
+            then.status(200).body(r#"{"traces":[]}"#);
+        });
+        let mut context = RpcContext::for_tests().with_storage(storage);
+        context.sequencer = starknet_gateway_client::Client::with_base_url(
+            server.base_url().parse().unwrap(),
+            std::time::Duration::from_secs(10),
+        )
+        .unwrap()
+        .disable_retry_for_tests();
+
+        let input = TraceBlockTransactionsInput {
+            block_id: BlockId::L1Accepted,
+        };
+        trace_block_transactions(context, input).await.unwrap();
+
+        mock.assert();
+    }
 }
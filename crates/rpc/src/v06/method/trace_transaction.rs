@@ -164,8 +164,9 @@ pub async fn trace_transaction_impl(
                     .transaction_block_hash(input.transaction_hash)?
                     .ok_or(TraceTransactionError::TxnHashNotFound)?;
 
-                let header = db
-                    .block_header(block_hash.into())
+                let header = context
+                    .header_cache
+                    .block_header(&db, block_hash.into())
                     .context("Fetching block header")?
                     .context("Block header is missing")?;
 
@@ -357,6 +358,7 @@ pub mod tests {
             receipt_commitment: Default::default(),
             state_diff_commitment: Default::default(),
             state_diff_length: 0,
+            block_body_commitment: Default::default(),
         };
         transaction
             .insert_block_header(&BlockHeader {
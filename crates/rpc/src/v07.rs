@@ -2,6 +2,10 @@ pub mod dto;
 
 use crate::jsonrpc::{RpcRouter, RpcRouterBuilder};
 
+/// The version of the specification implemented by these routes, returned by
+/// `starknet_specVersion`.
+pub const SPEC_VERSION: &str = "0.7.1";
+
 #[rustfmt::skip]
 pub fn register_routes() -> RpcRouterBuilder {
     RpcRouter::builder(crate::RpcVersion::V07)
@@ -34,5 +38,5 @@ pub fn register_routes() -> RpcRouterBuilder {
         .register("starknet_traceTransaction",                    crate::method::trace_transaction)
         .register("starknet_getBlockWithReceipts",                crate::method::get_block_with_receipts)
         .register("pathfinder_getProof",                          crate::pathfinder::methods::get_proof)
-        .register("starknet_specVersion",                         || "0.7.1")
+        .register("starknet_specVersion",                         || SPEC_VERSION)
 }
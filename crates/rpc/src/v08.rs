@@ -4,6 +4,10 @@ use crate::method::subscribe_new_heads::SubscribeNewHeads;
 use crate::method::subscribe_pending_transactions::SubscribePendingTransactions;
 use crate::method::subscribe_transaction_status::SubscribeTransactionStatus;
 
+/// The version of the specification implemented by these routes, returned by
+/// `starknet_specVersion`.
+pub const SPEC_VERSION: &str = "0.8.0-rc1";
+
 #[rustfmt::skip]
 pub fn register_routes() -> RpcRouterBuilder {
     RpcRouter::builder(crate::RpcVersion::V08)
@@ -15,7 +19,7 @@ pub fn register_routes() -> RpcRouterBuilder {
         .register("starknet_call",                                crate::method::call)
         .register("starknet_chainId",                             crate::method::chain_id)
         .register("starknet_estimateFee",                         crate::method::estimate_fee)
-        .register("starknet_estimateMessageFee",                  crate::method::estimate_fee)
+        .register("starknet_estimateMessageFee",                  crate::method::estimate_message_fee)
         .register("starknet_getBlockTransactionCount",            crate::method::get_block_transaction_count)
         .register("starknet_getBlockWithTxHashes",                crate::method::get_block_with_tx_hashes)
         .register("starknet_getBlockWithTxs",                     crate::method::get_block_with_txs)
@@ -38,7 +42,7 @@ pub fn register_routes() -> RpcRouterBuilder {
         .register("starknet_subscribePendingTransactions",        SubscribePendingTransactions)
         .register("starknet_subscribeEvents",                     SubscribeEvents)
         .register("starknet_subscribeTransactionStatus",          SubscribeTransactionStatus)
-        .register("starknet_specVersion",                         || "0.8.0-rc1")
+        .register("starknet_specVersion",                         || SPEC_VERSION)
         .register("starknet_syncing",                             crate::method::syncing)
         .register("starknet_traceBlockTransactions",              crate::method::trace_block_transactions)
         .register("starknet_traceTransaction",                    crate::method::trace_transaction)
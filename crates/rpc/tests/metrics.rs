@@ -0,0 +1,113 @@
+//! This test was separated because the `metrics` crate uses a singleton
+//! recorder, so keeping a test that relies on metric values in a separate
+//! binary makes more sense than using an inter-test locking mechanism.
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use pathfinder_common::test_utils::metrics::{FakeRecorder, ScopedRecorderGuard};
+use pathfinder_common::ChainId;
+use pathfinder_ethereum::EthereumClient;
+use pathfinder_rpc::context::{RpcConfig, RpcContext};
+use pathfinder_rpc::{Notifications, RpcServer, RpcVersion, SyncState};
+use pathfinder_storage::StorageBuilder;
+use primitive_types::H160;
+use starknet_gateway_client::Client;
+
+fn context() -> RpcContext {
+    let storage = StorageBuilder::in_memory().unwrap();
+    let (_, pending_data) = tokio::sync::watch::channel(Default::default());
+    let config = RpcConfig {
+        batch_concurrency_limit: NonZeroUsize::new(1).unwrap(),
+        get_events_max_blocks_to_scan: NonZeroUsize::new(1).unwrap(),
+        get_events_max_event_filters_to_load: NonZeroUsize::new(1).unwrap(),
+        custom_versioned_constants: None,
+        large_block_threshold: 10_000,
+        class_fallback_url: None,
+        wait_for_block_timeout: Duration::from_secs(30),
+        validate_class_hash: false,
+        prefetch_next_event_page: false,
+        subscription_send_timeout: Duration::from_secs(30),
+        websocket_keepalive_interval: None,
+        subscription_catchup_concurrency: NonZeroUsize::new(1).unwrap(),
+        max_subscriptions_per_connection: NonZeroUsize::new(100).unwrap(),
+        block_header_cache_size: NonZeroUsize::new(1024).unwrap(),
+        websocket_max_message_size_bytes: 10 * 1024 * 1024,
+        approved_class_hashes: Vec::new(),
+    };
+    RpcContext::new(
+        storage.clone(),
+        storage,
+        Arc::new(SyncState::default()),
+        ChainId::SEPOLIA_TESTNET,
+        H160::from(pathfinder_ethereum::core_addr::SEPOLIA_TESTNET),
+        Client::sepolia_testnet(Duration::from_secs(10)),
+        pending_data,
+        Notifications::default(),
+        EthereumClient::new("wss://eth-sepolia.g.alchemy.com/v2/just-for-tests").unwrap(),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn method_calls() {
+    let recorder = FakeRecorder::new_for(&["starknet_chainId", "starknet_blockNumber"]);
+    let handle = recorder.handle();
+
+    // Automatically deregister the recorder
+    let _guard = ScopedRecorderGuard::new(recorder);
+
+    let (_jh, addr) = RpcServer::new("127.0.0.1:0".parse().unwrap(), context(), RpcVersion::V07)
+        .spawn()
+        .await
+        .unwrap();
+    let url = format!("http://{addr}/rpc/v0_7");
+    let client = reqwest::Client::new();
+
+    // The database is empty, so `starknet_blockNumber` fails.
+    for method in [
+        "starknet_chainId",
+        "starknet_chainId",
+        "starknet_blockNumber",
+    ] {
+        client
+            .post(&url)
+            .json(&serde_json::json!({"jsonrpc": "2.0", "method": method, "id": 0}))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    // IMPORTANT
+    //
+    // The metric names are public API, so they are spelled out here to catch
+    // if/when they change.
+    [
+        ("rpc_method_calls_total", "starknet_chainId", 2),
+        ("rpc_method_calls_total", "starknet_blockNumber", 1),
+        ("rpc_method_calls_failed_total", "starknet_blockNumber", 1),
+    ]
+    .into_iter()
+    .for_each(|(counter_name, method, expected_count)| {
+        assert_eq!(
+            handle.get_counter_value_by_label(
+                counter_name,
+                [("method", method), ("version", "v0.7")]
+            ),
+            expected_count,
+            "counter: {counter_name}, method: {method}"
+        )
+    });
+
+    for (method, expected_count) in [("starknet_chainId", 2), ("starknet_blockNumber", 1)] {
+        assert_eq!(
+            handle.get_histogram_count_by_label(
+                "rpc_method_call_duration_seconds",
+                [("method", method), ("version", "v0.7")]
+            ),
+            expected_count,
+            "histogram: rpc_method_call_duration_seconds, method: {method}"
+        );
+    }
+}
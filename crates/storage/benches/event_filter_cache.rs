@@ -0,0 +1,69 @@
+//! Compares repeated event queries over the same block range, with and without
+//! the in-memory cache of aggregate event filters.
+
+use std::num::{NonZeroU32, NonZeroUsize};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pathfinder_common::macro_prelude::*;
+use pathfinder_storage::{test_utils, EventConstraints, JournalMode, Storage, StorageBuilder};
+
+/// Number of stored event filters covered by the queries.
+const EVENT_FILTERS: u64 = 4;
+
+fn reopen(storage: &Storage, bloom_filter_cache_size: usize) -> Storage {
+    StorageBuilder::file(storage.path().to_path_buf())
+        .journal_mode(JournalMode::Rollback)
+        .bloom_filter_cache_size(bloom_filter_cache_size)
+        .migrate()
+        .unwrap()
+        .create_pool(NonZeroU32::new(1).unwrap())
+        .unwrap()
+}
+
+fn repeated_range_queries(c: &mut Criterion) {
+    let range_len = pathfinder_storage::BLOCK_RANGE_LEN;
+    // Only the last block of each range is required for its filter to be
+    // stored.
+    let blocks = (1..=EVENT_FILTERS)
+        .map(|i| (i * range_len - 1) as usize)
+        .collect::<Vec<_>>();
+    let (storage, _) = test_utils::setup_custom_test_storage(&blocks, 2);
+
+    let constraints = EventConstraints {
+        // Not present in any event, so the run time is dominated by loading
+        // the filters rather than by scanning blocks.
+        keys: vec![vec![event_key!("0x1234")]],
+        page_size: 1024,
+        ..Default::default()
+    };
+    let max_blocks_to_scan = NonZeroUsize::new(100).unwrap();
+    let max_event_filters_to_load = NonZeroUsize::new(EVENT_FILTERS as usize).unwrap();
+
+    let mut group = c.benchmark_group("events");
+    group.sample_size(10);
+
+    for (name, cache_size) in [
+        ("uncached", 0),
+        ("cached", (EVENT_FILTERS * range_len) as usize),
+    ] {
+        let storage = reopen(&storage, cache_size);
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                tx.events(
+                    black_box(&constraints),
+                    max_blocks_to_scan,
+                    max_event_filters_to_load,
+                )
+                .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, repeated_range_queries);
+criterion_main!(benches);
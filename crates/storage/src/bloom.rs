@@ -61,8 +61,10 @@
 //! filter.
 
 use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use bloomfilter::Bloom;
+use cached::{Cached, SizedCache};
 use pathfinder_common::BlockNumber;
 use pathfinder_crypto::Felt;
 
@@ -115,6 +117,45 @@ impl AggregateBloom {
         Self::from_parts(from_block, to_block, bitmap)
     }
 
+    /// Same as [Self::from_existing_compressed] but returns an error instead of
+    /// panicking if the stored filter is corrupted.
+    pub fn try_from_existing_compressed(
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        compressed_bitmap: Vec<u8>,
+    ) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
+        anyhow::ensure!(
+            from_block + Self::BLOCK_RANGE_LEN - 1 == to_block,
+            "Invalid aggregate Bloom filter range {from_block}..={to_block}"
+        );
+
+        let bitmap = zstd::bulk::decompress(
+            &compressed_bitmap,
+            AggregateBloom::BLOCK_RANGE_BYTES as usize * BloomFilter::BITVEC_LEN as usize,
+        )
+        .context("Decompressing aggregate Bloom filter")?;
+
+        anyhow::ensure!(
+            bitmap.len() as u64 == Self::BLOCK_RANGE_BYTES * BloomFilter::BITVEC_LEN,
+            "Invalid aggregate Bloom filter bitmap length {}",
+            bitmap.len()
+        );
+
+        Ok(Self::from_parts(from_block, to_block, bitmap))
+    }
+
+    /// Create an `AggregateBloom` for the (`from_block`, `from_block` +
+    /// [`block_range_length`](Self::BLOCK_RANGE_LEN) - 1) range which matches
+    /// every block, for any set of keys.
+    pub fn match_all(from_block: BlockNumber) -> Self {
+        let to_block = from_block + Self::BLOCK_RANGE_LEN - 1;
+        let bitmap =
+            vec![u8::MAX; Self::BLOCK_RANGE_BYTES as usize * BloomFilter::BITVEC_LEN as usize];
+        Self::from_parts(from_block, to_block, bitmap)
+    }
+
     fn from_parts(from_block: BlockNumber, to_block: BlockNumber, bitmap: Vec<u8>) -> Self {
         assert_eq!(from_block + Self::BLOCK_RANGE_LEN - 1, to_block);
         assert_eq!(
@@ -218,6 +259,56 @@ impl AggregateBloom {
     }
 }
 
+/// An LRU cache of [aggregate Bloom filters](AggregateBloom) loaded from the
+/// database, keyed by the first block of their range.
+///
+/// A cache with no capacity stores nothing.
+pub(crate) struct AggregateBloomCache(Option<Mutex<SizedCache<BlockNumber, Arc<AggregateBloom>>>>);
+
+impl AggregateBloomCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        // `SizedCache` panics if created with a size of zero.
+        Self((capacity > 0).then(|| Mutex::new(SizedCache::with_size(capacity))))
+    }
+
+    fn locked_cache(&self) -> Option<MutexGuard<'_, SizedCache<BlockNumber, Arc<AggregateBloom>>>> {
+        self.0.as_ref().map(|cache| cache.lock().unwrap())
+    }
+
+    pub fn get(&self, from_block: BlockNumber) -> Option<Arc<AggregateBloom>> {
+        let mut cache = self.locked_cache()?;
+
+        match cache.cache_get(&from_block) {
+            Some(filter) => {
+                metrics::increment_counter!(METRIC_EVENT_FILTER_CACHE_HITS);
+                Some(filter.clone())
+            }
+            None => {
+                metrics::increment_counter!(METRIC_EVENT_FILTER_CACHE_MISSES);
+                None
+            }
+        }
+    }
+
+    pub fn set(&self, filter: Arc<AggregateBloom>) {
+        if let Some(mut cache) = self.locked_cache() {
+            cache.cache_set(filter.from_block, filter);
+        }
+    }
+
+    /// Removes the filter covering the given block, if it is cached.
+    pub fn invalidate(&self, block: BlockNumber) {
+        if let Some(mut cache) = self.locked_cache() {
+            let from_block =
+                block.get() / AggregateBloom::BLOCK_RANGE_LEN * AggregateBloom::BLOCK_RANGE_LEN;
+            cache.cache_remove(&BlockNumber::new_or_panic(from_block));
+        }
+    }
+}
+
+const METRIC_EVENT_FILTER_CACHE_HITS: &str = "pathfinder_storage_event_filter_cache_hits_total";
+const METRIC_EVENT_FILTER_CACHE_MISSES: &str = "pathfinder_storage_event_filter_cache_misses_total";
+
 #[derive(Clone)]
 pub(crate) struct BloomFilter(Bloom<Felt>);
 
@@ -404,4 +495,30 @@ mod tests {
         let invalid_insert_pos = from_block + AggregateBloom::BLOCK_RANGE_LEN;
         aggregate_bloom_filter.add_bloom(&bloom, invalid_insert_pos);
     }
+
+    #[test]
+    fn cache_invalidation_by_any_block_in_range() {
+        let cache = AggregateBloomCache::with_capacity(2);
+        let first = Arc::new(AggregateBloom::new(BlockNumber::GENESIS));
+        let second = Arc::new(AggregateBloom::new(
+            BlockNumber::GENESIS + AggregateBloom::BLOCK_RANGE_LEN,
+        ));
+
+        cache.set(first.clone());
+        cache.set(second.clone());
+        assert!(Arc::ptr_eq(&cache.get(first.from_block).unwrap(), &first));
+        assert!(Arc::ptr_eq(&cache.get(second.from_block).unwrap(), &second));
+
+        cache.invalidate(second.to_block - 1);
+        assert!(cache.get(first.from_block).is_some());
+        assert!(cache.get(second.from_block).is_none());
+    }
+
+    #[test]
+    fn cache_without_capacity_stores_nothing() {
+        let cache = AggregateBloomCache::with_capacity(0);
+
+        cache.set(Arc::new(AggregateBloom::new(BlockNumber::GENESIS)));
+        assert!(cache.get(BlockNumber::GENESIS).is_none());
+    }
 }
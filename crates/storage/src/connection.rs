@@ -12,6 +12,7 @@ pub mod storage_index;
 pub(crate) mod transaction;
 mod trie;
 
+pub use class::ClassMetadata;
 use event::RunningEventFilter;
 pub use event::{
     EmittedEvent,
@@ -29,11 +30,14 @@ pub(crate) use reorg_counter::ReorgCounter;
 pub use rusqlite::TransactionBehavior;
 pub use trie::{Node, NodeRef, RootIndexUpdate, StoredNode, TrieUpdate};
 
+use crate::bloom::AggregateBloomCache;
+
 type PooledConnection = r2d2::PooledConnection<r2d2_sqlite::SqliteConnectionManager>;
 
 pub struct Connection {
     connection: PooledConnection,
     running_event_filter: Arc<Mutex<RunningEventFilter>>,
+    event_filter_cache: Arc<AggregateBloomCache>,
     trie_prune_mode: TriePruneMode,
 }
 
@@ -41,11 +45,13 @@ impl Connection {
     pub(crate) fn new(
         connection: PooledConnection,
         running_event_filter: Arc<Mutex<RunningEventFilter>>,
+        event_filter_cache: Arc<AggregateBloomCache>,
         trie_prune_mode: TriePruneMode,
     ) -> Self {
         Self {
             connection,
             running_event_filter,
+            event_filter_cache,
             trie_prune_mode,
         }
     }
@@ -55,6 +61,7 @@ impl Connection {
         Ok(Transaction {
             transaction: tx,
             running_event_filter: self.running_event_filter.clone(),
+            event_filter_cache: self.event_filter_cache.clone(),
             trie_prune_mode: self.trie_prune_mode,
         })
     }
@@ -67,6 +74,7 @@ impl Connection {
         Ok(Transaction {
             transaction: tx,
             running_event_filter: self.running_event_filter.clone(),
+            event_filter_cache: self.event_filter_cache.clone(),
             trie_prune_mode: self.trie_prune_mode,
         })
     }
@@ -75,6 +83,7 @@ impl Connection {
 pub struct Transaction<'inner> {
     transaction: rusqlite::Transaction<'inner>,
     running_event_filter: Arc<Mutex<RunningEventFilter>>,
+    event_filter_cache: Arc<AggregateBloomCache>,
     trie_prune_mode: TriePruneMode,
 }
 
@@ -98,7 +107,7 @@ impl Transaction<'_> {
     // the real implementations be kept in separate files with more reasonable
     // LOC counts and easier test oversight.
 
-    fn inner(&self) -> &rusqlite::Transaction<'_> {
+    pub(crate) fn inner(&self) -> &rusqlite::Transaction<'_> {
         &self.transaction
     }
 
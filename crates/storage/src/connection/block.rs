@@ -3,9 +3,11 @@ use std::num::NonZeroUsize;
 
 use anyhow::Context;
 use pathfinder_common::{
+    BlockBodyCommitment,
     BlockHash,
     BlockHeader,
     BlockNumber,
+    BlockTimestamp,
     ClassCommitment,
     GasPrice,
     StarknetVersion,
@@ -23,8 +25,8 @@ impl Transaction<'_> {
         // Insert the header
         self.inner().execute(
         r"INSERT INTO block_headers 
-                   ( number,  hash,  parent_hash,  storage_commitment,  timestamp,  eth_l1_gas_price,  strk_l1_gas_price,  eth_l1_data_gas_price,  strk_l1_data_gas_price,  eth_l2_gas_price,  strk_l2_gas_price,  sequencer_address,  version,  transaction_commitment,  event_commitment,  state_commitment,  class_commitment,  transaction_count,  event_count,  l1_da_mode,  receipt_commitment,  state_diff_commitment,  state_diff_length)
-            VALUES (:number, :hash, :parent_hash, :storage_commitment, :timestamp, :eth_l1_gas_price, :strk_l1_gas_price, :eth_l1_data_gas_price, :strk_l1_data_gas_price, :eth_l2_gas_price, :strk_l2_gas_price, :sequencer_address, :version, :transaction_commitment, :event_commitment, :state_commitment, :class_commitment, :transaction_count, :event_count, :l1_da_mode, :receipt_commitment, :state_diff_commitment, :state_diff_length)",
+                   ( number,  hash,  parent_hash,  storage_commitment,  timestamp,  eth_l1_gas_price,  strk_l1_gas_price,  eth_l1_data_gas_price,  strk_l1_data_gas_price,  eth_l2_gas_price,  strk_l2_gas_price,  sequencer_address,  version,  transaction_commitment,  event_commitment,  state_commitment,  class_commitment,  transaction_count,  event_count,  l1_da_mode,  receipt_commitment,  state_diff_commitment,  state_diff_length,  block_body_commitment)
+            VALUES (:number, :hash, :parent_hash, :storage_commitment, :timestamp, :eth_l1_gas_price, :strk_l1_gas_price, :eth_l1_data_gas_price, :strk_l1_data_gas_price, :eth_l2_gas_price, :strk_l2_gas_price, :sequencer_address, :version, :transaction_commitment, :event_commitment, :state_commitment, :class_commitment, :transaction_count, :event_count, :l1_da_mode, :receipt_commitment, :state_diff_commitment, :state_diff_length, :block_body_commitment)",
         named_params! {
             ":number": &header.number,
             ":hash": &header.hash,
@@ -49,6 +51,7 @@ impl Transaction<'_> {
             ":receipt_commitment": &header.receipt_commitment,
             ":state_diff_commitment": &header.state_diff_commitment,
             ":state_diff_length": &header.state_diff_length,
+            ":block_body_commitment": &header.block_body_commitment,
         },
     ).context("Inserting block header")?;
 
@@ -125,6 +128,7 @@ impl Transaction<'_> {
                 named_params![":block": &block],
             )
             .context("Deleting event bloom filter")?;
+        self.event_filter_cache.invalidate(block);
 
         self.inner()
             .execute(
@@ -234,6 +238,17 @@ impl Transaction<'_> {
                     Ok((number, hash))
                 },
             ),
+            BlockId::L1Accepted => self.inner().query_row(
+                "SELECT number, hash FROM canonical_blocks WHERE number = (SELECT l1_l2_head FROM \
+                 refs WHERE idx = 1)",
+                [],
+                |row| {
+                    let number = row.get_block_number(0)?;
+                    let hash = row.get_block_hash(1)?;
+
+                    Ok((number, hash))
+                },
+            ),
         }
         .optional()
         .map_err(|e| e.into())
@@ -270,6 +285,16 @@ impl Transaction<'_> {
                     .optional()
                     .map_err(|e| e.into())
             }
+            BlockId::L1Accepted => self
+                .inner()
+                .query_row(
+                    "SELECT hash FROM canonical_blocks WHERE number = (SELECT l1_l2_head FROM \
+                     refs WHERE idx = 1)",
+                    [],
+                    |row| row.get_block_hash(0),
+                )
+                .optional()
+                .map_err(|e| e.into()),
         }
     }
 
@@ -304,6 +329,16 @@ impl Transaction<'_> {
                 )
                 .optional()
                 .map_err(|e| e.into()),
+            BlockId::L1Accepted => self
+                .inner()
+                .query_row(
+                    "SELECT number FROM canonical_blocks WHERE number = (SELECT l1_l2_head FROM \
+                     refs WHERE idx = 1)",
+                    [],
+                    |row| row.get_block_number(0),
+                )
+                .optional()
+                .map_err(|e| e.into()),
         }
     }
 
@@ -327,6 +362,13 @@ impl Transaction<'_> {
                 )?;
                 stmt.query_row(params![&hash], |row| row.get(0))
             }
+            BlockId::L1Accepted => {
+                let mut stmt = self.inner().prepare_cached(
+                    "SELECT EXISTS(SELECT 1 FROM canonical_blocks WHERE number = (SELECT \
+                     l1_l2_head FROM refs WHERE idx = 1))",
+                )?;
+                stmt.query_row([], |row| row.get(0))
+            }
         }
         .map_err(|e| e.into())
     }
@@ -345,6 +387,10 @@ impl Transaction<'_> {
             BlockId::Latest => "SELECT * FROM block_headers ORDER BY number DESC LIMIT 1",
             BlockId::Number(_) => "SELECT * FROM block_headers WHERE number = ?",
             BlockId::Hash(_) => "SELECT * FROM block_headers WHERE hash = ?",
+            BlockId::L1Accepted => {
+                "SELECT * FROM block_headers WHERE number = (SELECT l1_l2_head FROM refs WHERE idx \
+                 = 1)"
+            }
         };
 
         let mut stmt = self
@@ -356,6 +402,7 @@ impl Transaction<'_> {
             BlockId::Latest => stmt.query_row([], parse_row_as_header),
             BlockId::Number(number) => stmt.query_row(params![&number], parse_row_as_header),
             BlockId::Hash(hash) => stmt.query_row(params![&hash], parse_row_as_header),
+            BlockId::L1Accepted => stmt.query_row([], parse_row_as_header),
         }
         .optional()
         .context("Querying for block header")?;
@@ -391,6 +438,10 @@ impl Transaction<'_> {
             }
             BlockId::Number(_) => "SELECT state_commitment FROM block_headers WHERE number = ?",
             BlockId::Hash(_) => "SELECT state_commitment FROM block_headers WHERE hash = ?",
+            BlockId::L1Accepted => {
+                "SELECT state_commitment FROM block_headers WHERE number = (SELECT l1_l2_head FROM \
+                 refs WHERE idx = 1)"
+            }
         };
 
         let mut stmt = self
@@ -408,6 +459,9 @@ impl Transaction<'_> {
             BlockId::Hash(hash) => stmt.query_row(params![&hash], |row| {
                 row.get_state_commitment("state_commitment")
             }),
+            BlockId::L1Accepted => {
+                stmt.query_row([], |row| row.get_state_commitment("state_commitment"))
+            }
         }
         .optional()
         .context("Querying for state commitment")?;
@@ -427,6 +481,119 @@ impl Transaction<'_> {
         Ok(block_number <= l1_l2)
     }
 
+    /// The first and last block stored in the database, [None] if there are
+    /// no blocks.
+    pub fn stored_block_range(&self) -> anyhow::Result<Option<(BlockNumber, BlockNumber)>> {
+        let mut stmt = self
+            .inner()
+            .prepare_cached("SELECT MIN(number), MAX(number) FROM block_headers")
+            .context("Preparing block range query")?;
+
+        let range = stmt
+            .query_row([], |row| {
+                let first = row.get_optional_block_number(0)?;
+                let last = row.get_optional_block_number(1)?;
+
+                Ok(first.zip(last))
+            })
+            .context("Querying block range")?;
+
+        Ok(range)
+    }
+
+    pub fn block_timestamp(&self, block: BlockNumber) -> anyhow::Result<Option<BlockTimestamp>> {
+        self.inner()
+            .query_row(
+                "SELECT timestamp FROM block_headers WHERE number = ?",
+                params![&block],
+                |row| row.get_timestamp(0),
+            )
+            .optional()
+            .context("Querying block timestamp")
+    }
+
+    /// The first and last block with a timestamp within the given range,
+    /// inclusive on both ends. [None] if there are no such blocks.
+    pub fn block_range_for_timestamps(
+        &self,
+        from: BlockTimestamp,
+        to: BlockTimestamp,
+    ) -> anyhow::Result<Option<(BlockNumber, BlockNumber)>> {
+        // Block timestamps do not decrease, so the first and last block are found
+        // at either end of the timestamp index instead of aggregating over the
+        // whole range. Consecutive blocks can share a timestamp, hence the
+        // ordering by number.
+        let mut first_stmt =
+            self.inner()
+                .prepare_cached(
+                    "SELECT number FROM block_headers WHERE timestamp >= ? AND timestamp <= ? \
+                     ORDER                  BY timestamp ASC, number ASC LIMIT 1",
+                )
+                .context("Preparing first block query")?;
+        let mut last_stmt =
+            self.inner()
+                .prepare_cached(
+                    "SELECT number FROM block_headers WHERE timestamp >= ? AND timestamp <= ? \
+                     ORDER                  BY timestamp DESC, number DESC LIMIT 1",
+                )
+                .context("Preparing last block query")?;
+
+        let first = first_stmt
+            .query_row(params![&from, &to], |row| row.get_block_number(0))
+            .optional()
+            .context("Querying first block for timestamps")?;
+        let last = last_stmt
+            .query_row(params![&from, &to], |row| row.get_block_number(0))
+            .optional()
+            .context("Querying last block for timestamps")?;
+
+        Ok(first.zip(last))
+    }
+
+    /// Blocks within the range, inclusive on both ends, whose parent hash does
+    /// not match the hash of the preceding block. Each entry holds the block
+    /// number, its parent hash and the hash of the preceding block.
+    ///
+    /// The headers are streamed in order, so memory use does not depend on the
+    /// size of the range. Blocks whose preceding block is not stored are not
+    /// checked.
+    pub fn broken_parent_hash_links(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<(BlockNumber, BlockHash, BlockHash)>> {
+        let mut stmt = self
+            .inner()
+            .prepare_cached(
+                "SELECT number, hash, parent_hash FROM block_headers WHERE number >= ? AND number \
+                 <= ? ORDER BY number ASC",
+            )
+            .context("Preparing block hash query")?;
+
+        // Start from the preceding block so that the link of `from` is checked too.
+        let first = from.parent().unwrap_or(from);
+        let mut rows = stmt
+            .query(params![&first, &to])
+            .context("Querying block hashes")?;
+
+        let mut broken = Vec::new();
+        let mut preceding: Option<(BlockNumber, BlockHash)> = None;
+        while let Some(row) = rows.next().context("Fetching next block hash")? {
+            let number = row.get_block_number(0)?;
+            let hash = row.get_block_hash(1)?;
+            let parent_hash = row.get_block_hash(2)?;
+
+            if let Some((preceding_number, preceding_hash)) = preceding {
+                if preceding_number + 1 == number && preceding_hash != parent_hash {
+                    broken.push((number, parent_hash, preceding_hash));
+                }
+            }
+            preceding = Some((number, hash));
+        }
+
+        Ok(broken)
+    }
+
     pub fn first_block_without_transactions(&self) -> anyhow::Result<Option<BlockNumber>> {
         let mut stmt = self
             .inner()
@@ -532,6 +699,32 @@ impl Transaction<'_> {
         Ok(ret)
     }
 
+    /// The number of events emitted by each block within the range, inclusive
+    /// on both ends, in block order. Blocks which are not stored are skipped.
+    pub fn block_event_counts(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<(BlockNumber, usize)>> {
+        let mut stmt = self
+            .inner()
+            .prepare_cached(
+                "SELECT number, event_count FROM block_headers WHERE number >= ? AND number <= ? \
+                 ORDER BY number ASC",
+            )
+            .context("Preparing block event counts query")?;
+
+        let counts = stmt
+            .query_map(params![&from, &to], |row| {
+                Ok((row.get_block_number(0)?, row.get(1)?))
+            })
+            .context("Querying block event counts")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Iterating over block event counts")?;
+
+        Ok(counts)
+    }
+
     pub fn transaction_counts(
         &self,
         block_number: BlockNumber,
@@ -638,6 +831,10 @@ fn parse_row_as_header(row: &rusqlite::Row<'_>) -> rusqlite::Result<BlockHeader>
         .get_optional_felt("state_diff_commitment")?
         .unwrap_or_default();
     let state_diff_length: u64 = row.get("state_diff_length")?;
+    let block_body_commitment = row
+        .get_optional_felt("block_body_commitment")?
+        .map(BlockBodyCommitment)
+        .unwrap_or_default();
 
     let header = BlockHeader {
         hash,
@@ -663,6 +860,7 @@ fn parse_row_as_header(row: &rusqlite::Row<'_>) -> rusqlite::Result<BlockHeader>
         receipt_commitment,
         state_diff_commitment: StateDiffCommitment(state_diff_commitment),
         state_diff_length,
+        block_body_commitment,
     };
 
     Ok(header)
@@ -716,6 +914,7 @@ mod tests {
             receipt_commitment: receipt_commitment_bytes!(b"receipt commitment genesis"),
             state_diff_commitment: state_diff_commitment!("12"),
             state_diff_length: 12,
+            block_body_commitment: block_body_commitment_bytes!(b"body commitment genesis"),
         };
         let header1 = genesis
             .child_builder()
@@ -732,6 +931,7 @@ mod tests {
             .transaction_commitment(transaction_commitment_bytes!(b"tx commitment 1"))
             .l1_da_mode(L1DataAvailabilityMode::Calldata)
             .receipt_commitment(receipt_commitment_bytes!(b"block 1 receipt commitment"))
+            .block_body_commitment(block_body_commitment_bytes!(b"block 1 body commitment"))
             .finalize_with_hash(block_hash_bytes!(b"block 1 hash"));
 
         let header2 = header1
@@ -786,6 +986,19 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn missing_block_body_commitment_is_zero() {
+        let (mut connection, headers) = setup();
+        let tx = connection.transaction().unwrap();
+        // Blocks stored before the column was added have no commitment.
+        tx.inner()
+            .execute("UPDATE block_headers SET block_body_commitment = NULL", [])
+            .unwrap();
+
+        let result = tx.block_header(headers[1].number.into()).unwrap().unwrap();
+        assert_eq!(result.block_body_commitment, BlockBodyCommitment::ZERO);
+    }
+
     #[test]
     fn get_by_hash() {
         let (mut connection, headers) = setup();
@@ -842,6 +1055,110 @@ mod tests {
         assert_eq!(by_hash, expected);
     }
 
+    #[test]
+    fn stored_block_range() {
+        let (mut connection, headers) = setup();
+        let tx = connection.transaction().unwrap();
+
+        let range = tx.stored_block_range().unwrap();
+        assert_eq!(
+            range,
+            Some((headers[0].number, headers.last().unwrap().number))
+        );
+
+        for header in headers.iter().rev() {
+            tx.purge_block(header.number).unwrap();
+        }
+        assert_eq!(tx.stored_block_range().unwrap(), None);
+    }
+
+    #[test]
+    fn block_timestamp() {
+        let (mut connection, headers) = setup();
+        let tx = connection.transaction().unwrap();
+
+        for header in &headers {
+            let timestamp = tx.block_timestamp(header.number).unwrap();
+            assert_eq!(timestamp, Some(header.timestamp));
+        }
+
+        let missing = tx
+            .block_timestamp(headers.last().unwrap().number + 1)
+            .unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[rstest]
+    #[case::all(0, 100, Some((0, 2)))]
+    #[case::exact(12, 15, Some((1, 2)))]
+    #[case::between_blocks(11, 14, Some((1, 1)))]
+    #[case::single(15, 15, Some((2, 2)))]
+    #[case::before_first(0, 9, None)]
+    #[case::after_last(16, 100, None)]
+    #[case::empty(13, 14, None)]
+    fn block_range_for_timestamps(
+        #[case] from: u64,
+        #[case] to: u64,
+        #[case] expected: Option<(u64, u64)>,
+    ) {
+        // The test headers have timestamps 10, 12 and 15.
+        let (mut connection, _) = setup();
+        let tx = connection.transaction().unwrap();
+
+        let range = tx
+            .block_range_for_timestamps(
+                BlockTimestamp::new_or_panic(from),
+                BlockTimestamp::new_or_panic(to),
+            )
+            .unwrap();
+
+        let expected = expected.map(|(first, last)| {
+            (
+                BlockNumber::new_or_panic(first),
+                BlockNumber::new_or_panic(last),
+            )
+        });
+        assert_eq!(range, expected);
+    }
+
+    #[test]
+    fn broken_parent_hash_links() {
+        let (mut connection, headers) = setup();
+        let tx = connection.transaction().unwrap();
+
+        let all = tx
+            .broken_parent_hash_links(BlockNumber::GENESIS, BlockNumber::MAX)
+            .unwrap();
+        assert_eq!(all, vec![]);
+
+        tx.inner()
+            .execute(
+                "UPDATE block_headers SET parent_hash = ? WHERE number = 2",
+                params![&block_hash_bytes!(b"wrong parent")],
+            )
+            .unwrap();
+
+        let expected = vec![(
+            headers[2].number,
+            block_hash_bytes!(b"wrong parent"),
+            headers[1].hash,
+        )];
+        let all = tx
+            .broken_parent_hash_links(BlockNumber::GENESIS, BlockNumber::MAX)
+            .unwrap();
+        assert_eq!(all, expected);
+        // The link to the preceding block is checked even if that block is outside
+        // the range.
+        let last = tx
+            .broken_parent_hash_links(headers[2].number, headers[2].number)
+            .unwrap();
+        assert_eq!(last, expected);
+        let first = tx
+            .broken_parent_hash_links(BlockNumber::GENESIS, headers[1].number)
+            .unwrap();
+        assert_eq!(first, vec![]);
+    }
+
     #[test]
     fn block_is_l1_accepted() {
         let (mut connection, headers) = setup();
@@ -862,6 +1179,24 @@ mod tests {
         assert!(!l2_by_number);
     }
 
+    #[test]
+    fn get_l1_accepted() {
+        let (mut connection, headers) = setup();
+        let tx = connection.transaction().unwrap();
+
+        // Nothing has been accepted on L1 yet.
+        let result = tx.block_header(BlockId::L1Accepted).unwrap();
+        assert_eq!(result, None);
+
+        tx.update_l1_l2_pointer(Some(headers[1].number)).unwrap();
+
+        let result = tx.block_header(BlockId::L1Accepted).unwrap().unwrap();
+        assert_eq!(result, headers[1]);
+
+        let result = tx.block_id(BlockId::L1Accepted).unwrap();
+        assert_eq!(result, Some((headers[1].number, headers[1].hash)));
+    }
+
     mod next_ancestor {
         use pretty_assertions_sorted::assert_eq;
 
@@ -1029,6 +1364,33 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case::all(0, 100, 0..3)]
+    #[case::single(1, 1, 1..2)]
+    #[case::after_last(3, 100, 0..0)]
+    #[case::reversed(2, 0, 0..0)]
+    fn block_event_counts(
+        #[case] from: u64,
+        #[case] to: u64,
+        #[case] expected: std::ops::Range<usize>,
+    ) {
+        let (mut connection, headers) = setup();
+        let tx = connection.transaction().unwrap();
+
+        let counts = tx
+            .block_event_counts(
+                BlockNumber::new_or_panic(from),
+                BlockNumber::new_or_panic(to),
+            )
+            .unwrap();
+
+        let expected = headers[expected]
+            .iter()
+            .map(|header| (header.number, header.event_count))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, expected);
+    }
+
     #[rstest]
     #[case::all_missing("UPDATE block_headers SET event_count = 0", 10)]
     #[case::partially_present("UPDATE block_headers SET event_count = 0 WHERE number > 4", 5)]
@@ -12,6 +12,14 @@ impl Transaction<'_> {
         casm_hash: &CasmHash,
         casm_definition: &[u8],
     ) -> anyhow::Result<()> {
+        // Definitions which cannot be parsed are stored without metadata.
+        let metadata = ClassMetadata::from_sierra(sierra_definition, casm_definition).ok();
+        let sierra_program_length = metadata.as_ref().and_then(|m| m.sierra_program_length);
+        let abi_length = metadata.as_ref().and_then(|m| m.abi_length);
+        let compiler_version = metadata
+            .as_ref()
+            .and_then(|m| m.compiler_version.as_deref());
+
         let mut compressor = zstd::bulk::Compressor::new(10).context("Creating zstd compressor")?;
         let sierra_definition = compressor
             .compress(sierra_definition)
@@ -22,8 +30,17 @@ impl Transaction<'_> {
 
         self.inner()
             .execute(
-                r"INSERT OR IGNORE INTO class_definitions (hash,  definition) VALUES (?, ?)",
-                params![sierra_hash, &sierra_definition],
+                r"INSERT OR IGNORE INTO class_definitions
+                (hash, definition, sierra_program_length, abi_length, compiler_version)
+            VALUES
+                (:hash, :definition, :sierra_program_length, :abi_length, :compiler_version)",
+                named_params! {
+                    ":hash": sierra_hash,
+                    ":definition": &sierra_definition,
+                    ":sierra_program_length": &sierra_program_length,
+                    ":abi_length": &abi_length,
+                    ":compiler_version": &compiler_version,
+                },
             )
             .context("Inserting sierra definition")?;
 
@@ -51,6 +68,13 @@ impl Transaction<'_> {
         casm_hash: &CasmHash,
         casm_definition: &[u8],
     ) -> anyhow::Result<()> {
+        let metadata = ClassMetadata::from_sierra(sierra_definition, casm_definition).ok();
+        let sierra_program_length = metadata.as_ref().and_then(|m| m.sierra_program_length);
+        let abi_length = metadata.as_ref().and_then(|m| m.abi_length);
+        let compiler_version = metadata
+            .as_ref()
+            .and_then(|m| m.compiler_version.as_deref());
+
         let mut compressor = zstd::bulk::Compressor::new(10).context("Creating zstd compressor")?;
         let sierra_definition = compressor
             .compress(sierra_definition)
@@ -61,8 +85,19 @@ impl Transaction<'_> {
 
         self.inner()
             .execute(
-                r"UPDATE class_definitions SET definition=? WHERE hash=?",
-                params![&sierra_definition, sierra_hash],
+                r"UPDATE class_definitions SET
+                    definition=:definition,
+                    sierra_program_length=:sierra_program_length,
+                    abi_length=:abi_length,
+                    compiler_version=:compiler_version
+                WHERE hash=:hash",
+                named_params! {
+                    ":definition": &sierra_definition,
+                    ":sierra_program_length": &sierra_program_length,
+                    ":abi_length": &abi_length,
+                    ":compiler_version": &compiler_version,
+                    ":hash": sierra_hash,
+                },
             )
             .context("Updating sierra definition")?;
 
@@ -85,6 +120,10 @@ impl Transaction<'_> {
         cairo_hash: ClassHash,
         definition: &[u8],
     ) -> anyhow::Result<()> {
+        let abi_length = ClassMetadata::from_cairo(definition)
+            .ok()
+            .and_then(|m| m.abi_length);
+
         let mut compressor = zstd::bulk::Compressor::new(10).context("Creating zstd compressor")?;
         let definition = compressor
             .compress(definition)
@@ -92,8 +131,11 @@ impl Transaction<'_> {
 
         self.inner()
             .execute(
-                r"INSERT OR IGNORE INTO class_definitions (hash,  definition) VALUES (?, ?)",
-                params![&cairo_hash, &definition],
+                r"INSERT OR IGNORE INTO class_definitions
+                (hash, definition, abi_length)
+            VALUES
+                (?, ?, ?)",
+                params![&cairo_hash, &definition, &abi_length],
             )
             .context("Inserting cairo definition")?;
 
@@ -105,6 +147,10 @@ impl Transaction<'_> {
         cairo_hash: ClassHash,
         definition: &[u8],
     ) -> anyhow::Result<()> {
+        let abi_length = ClassMetadata::from_cairo(definition)
+            .ok()
+            .and_then(|m| m.abi_length);
+
         let mut compressor = zstd::bulk::Compressor::new(10).context("Creating zstd compressor")?;
         let definition = compressor
             .compress(definition)
@@ -112,8 +158,8 @@ impl Transaction<'_> {
 
         self.inner()
             .execute(
-                r"UPDATE class_definitions SET definition=? WHERE hash=?",
-                params![&definition, &cairo_hash],
+                r"UPDATE class_definitions SET definition=?, abi_length=? WHERE hash=?",
+                params![&definition, &abi_length, &cairo_hash],
             )
             .context("Updating cairo definition")?;
 
@@ -136,6 +182,23 @@ impl Transaction<'_> {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
+    /// Returns the number of the block in which the class was declared, or
+    /// [None] if the class is unknown or has not been declared in a block yet.
+    pub fn class_declaration_block(
+        &self,
+        class_hash: ClassHash,
+    ) -> anyhow::Result<Option<BlockNumber>> {
+        self.inner()
+            .query_row(
+                "SELECT block_number FROM class_definitions WHERE hash = ?",
+                params![&class_hash],
+                |row| row.get_optional_block_number(0),
+            )
+            .optional()
+            .map(Option::flatten)
+            .context("Querying class declaration block")
+    }
+
     /// Returns the uncompressed class definition.
     pub fn class_definition(&self, class_hash: ClassHash) -> anyhow::Result<Option<Vec<u8>>> {
         self.class_definition_with_block_number(class_hash)
@@ -167,7 +230,7 @@ impl Transaction<'_> {
             return Ok(None);
         };
         let definition =
-            zstd::decode_all(definition.as_slice()).context("Decompressing class definition")?;
+            decompress_definition(&definition).context("Decompressing class definition")?;
 
         Ok(Some((block_number, definition)))
     }
@@ -223,6 +286,16 @@ impl Transaction<'_> {
                 from_row,
             )
         }
+        BlockId::L1Accepted => {
+            let mut stmt = self.inner().prepare_cached(
+                r"SELECT definition, block_number FROM class_definitions
+                WHERE hash = ? AND block_number <= (SELECT l1_l2_head FROM refs WHERE idx = 1)",
+            )?;
+            stmt.query_row(
+                params![&class_hash],
+                from_row,
+            )
+        }
     }
     .optional()
     .context("Querying for class definition")
@@ -252,11 +325,85 @@ impl Transaction<'_> {
             return Ok(None);
         };
         let definition =
-            zstd::decode_all(definition.as_slice()).context("Decompressing class definition")?;
+            decompress_definition(&definition).context("Decompressing class definition")?;
 
         Ok(Some((block_number, definition)))
     }
 
+    /// Returns the [metadata](ClassMetadata) of a stored class definition.
+    pub fn class_metadata(&self, class_hash: ClassHash) -> anyhow::Result<Option<ClassMetadata>> {
+        let mut stmt = self.inner().prepare_cached(
+            r"SELECT sierra_program_length, abi_length, compiler_version,
+                casm_definitions.hash IS NOT NULL
+            FROM class_definitions
+            LEFT JOIN casm_definitions ON casm_definitions.hash = class_definitions.hash
+            WHERE class_definitions.hash = ? AND class_definitions.definition IS NOT NULL",
+        )?;
+        let metadata = stmt
+            .query_row(params![&class_hash], ClassMetadata::from_row)
+            .optional()
+            .context("Querying for class metadata")?;
+
+        self.complete_class_metadata(class_hash, metadata)
+    }
+
+    /// Returns the [metadata](ClassMetadata) of a class definition if it has
+    /// been declared at `block_id`.
+    pub fn class_metadata_at(
+        &self,
+        block_id: BlockId,
+        class_hash: ClassHash,
+    ) -> anyhow::Result<Option<ClassMetadata>> {
+        let Some(block_number) = self.block_number(block_id)? else {
+            return Ok(None);
+        };
+
+        let mut stmt = self.inner().prepare_cached(
+            r"SELECT sierra_program_length, abi_length, compiler_version,
+                casm_definitions.hash IS NOT NULL
+            FROM class_definitions
+            LEFT JOIN casm_definitions ON casm_definitions.hash = class_definitions.hash
+            WHERE class_definitions.hash = ? AND class_definitions.block_number <= ?
+                AND class_definitions.definition IS NOT NULL",
+        )?;
+        let metadata = stmt
+            .query_row(params![&class_hash, &block_number], ClassMetadata::from_row)
+            .optional()
+            .context("Querying for class metadata")?;
+
+        self.complete_class_metadata(class_hash, metadata)
+    }
+
+    /// Metadata is not stored for definitions inserted by versions without the
+    /// metadata columns, in which case it is computed from the definition.
+    /// Definitions which cannot be parsed are returned without metadata.
+    fn complete_class_metadata(
+        &self,
+        class_hash: ClassHash,
+        metadata: Option<ClassMetadata>,
+    ) -> anyhow::Result<Option<ClassMetadata>> {
+        let Some(metadata) = metadata else {
+            return Ok(None);
+        };
+        if metadata.abi_length.is_some() {
+            return Ok(Some(metadata));
+        }
+
+        let definition = self
+            .class_definition(class_hash)?
+            .context("Class definition missing")?;
+        let computed = if metadata.is_sierra {
+            let casm_definition = self
+                .casm_definition(class_hash)?
+                .context("Compiled class definition missing")?;
+            ClassMetadata::from_sierra(&definition, &casm_definition)
+        } else {
+            ClassMetadata::from_cairo(&definition)
+        };
+
+        Ok(Some(computed.unwrap_or(metadata)))
+    }
+
     /// Returns the uncompressed compiled class definition.
     pub fn casm_definition(&self, class_hash: ClassHash) -> anyhow::Result<Option<Vec<u8>>> {
         // Don't reuse the "_with_block_number" impl here since the suffixed one
@@ -274,7 +421,7 @@ impl Transaction<'_> {
         let Some(definition) = definition else {
             return Ok(None);
         };
-        let definition = zstd::decode_all(definition.as_slice())
+        let definition = decompress_definition(&definition)
             .context("Decompressing compiled class definition")?;
 
         Ok(Some(definition))
@@ -313,7 +460,7 @@ impl Transaction<'_> {
         let Some((block_number, definition)) = result else {
             return Ok(None);
         };
-        let definition = zstd::decode_all(definition.as_slice())
+        let definition = decompress_definition(&definition)
             .context("Decompressing compiled class definition")?;
 
         Ok(Some((block_number, definition)))
@@ -391,6 +538,21 @@ impl Transaction<'_> {
                 AND class_definitions.block_number <= (SELECT number FROM canonical_blocks WHERE hash = ?)")?;
             stmt.query_row(params![&class_hash, &hash], from_row)
         },
+        BlockId::L1Accepted => {
+            let mut stmt = self.inner().prepare_cached(
+            r"SELECT
+                casm_definitions.definition,
+                class_definitions.block_number
+            FROM
+                casm_definitions
+                INNER JOIN class_definitions ON (
+                    class_definitions.hash = casm_definitions.hash
+                )
+            WHERE
+                casm_definitions.hash = ?
+                AND class_definitions.block_number <= (SELECT l1_l2_head FROM refs WHERE idx = 1)")?;
+            stmt.query_row(params![&class_hash], from_row)
+        },
     }
     .optional()
     .context("Querying for compiled class definition")?;
@@ -398,7 +560,7 @@ impl Transaction<'_> {
         let Some((block_number, definition)) = definition else {
             return Ok(None);
         };
-        let definition = zstd::decode_all(definition.as_slice())
+        let definition = decompress_definition(&definition)
             .context("Decompressing compiled class definition")?;
 
         Ok(Some((block_number, definition)))
@@ -467,6 +629,20 @@ impl Transaction<'_> {
                 AND class_definitions.block_number <= (SELECT number FROM canonical_blocks WHERE hash = ?)"#)?;
             stmt.query_row(params![&class_hash, &hash], |row| row.get_casm_hash(0))
         }
+        BlockId::L1Accepted => {
+            let mut stmt = self.inner().prepare_cached(
+            r#"SELECT
+                casm_definitions.compiled_class_hash
+            FROM
+                casm_definitions
+                INNER JOIN class_definitions ON (
+                    class_definitions.hash = casm_definitions.hash
+                )
+            WHERE
+                casm_definitions.hash = ?
+                AND class_definitions.block_number <= (SELECT l1_l2_head FROM refs WHERE idx = 1)"#)?;
+            stmt.query_row(params![&class_hash], |row| row.get_casm_hash(0))
+        }
     }
     .optional()
     .context("Querying for class definition")?;
@@ -515,6 +691,182 @@ impl Transaction<'_> {
         .optional()
         .map_err(Into::into)
     }
+
+    /// Re-compresses class definitions which are not stored using zstd and
+    /// returns the number of definitions re-compressed.
+    ///
+    /// Older databases may contain gzip compressed or uncompressed definitions.
+    /// These can be read as they are, but storing a single format keeps the
+    /// stored size of all definitions consistent. This scans every stored
+    /// definition, which takes a long time on large databases.
+    pub fn recompress_class_definitions(&self) -> anyhow::Result<usize> {
+        let mut count = 0;
+        for table in ["class_definitions", "casm_definitions"] {
+            count += self
+                .recompress_definitions(table)
+                .with_context(|| format!("Re-compressing definitions in {table}"))?;
+        }
+
+        Ok(count)
+    }
+
+    fn recompress_definitions(&self, table: &str) -> anyhow::Result<usize> {
+        let hashes = self
+            .inner()
+            .prepare(&format!(
+                "SELECT hash FROM {table} WHERE definition IS NOT NULL AND substr(definition, 1, \
+                 4) != X'28B52FFD'"
+            ))?
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))
+            .context("Querying definitions to re-compress")?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if hashes.is_empty() {
+            return Ok(0);
+        }
+
+        tracing::info!(count=%hashes.len(), %table, "Re-compressing class definitions using zstd");
+
+        let mut select = self
+            .inner()
+            .prepare(&format!("SELECT definition FROM {table} WHERE hash = ?"))?;
+        let mut update = self
+            .inner()
+            .prepare(&format!("UPDATE {table} SET definition = ? WHERE hash = ?"))?;
+        let mut compressor = zstd::bulk::Compressor::new(10).context("Creating zstd compressor")?;
+
+        for hash in &hashes {
+            let definition = select
+                .query_row([hash], |row| row.get::<_, Vec<u8>>(0))
+                .context("Querying definition")?;
+            let definition =
+                decompress_definition(&definition).context("Decompressing definition")?;
+            let definition = compressor
+                .compress(&definition)
+                .context("Compressing definition")?;
+
+            update
+                .execute(params![&definition, hash])
+                .context("Updating definition")?;
+        }
+
+        Ok(hashes.len())
+    }
+}
+
+/// Size and compiler information of a class definition, which is stored
+/// alongside the definition so that it can be queried without reading it.
+///
+/// The lengths and the compiler version are `None` if the definition could not
+/// be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassMetadata {
+    /// Whether this is a Sierra class, as opposed to a Cairo 0 class.
+    pub is_sierra: bool,
+    /// The number of felts in the Sierra program, `None` for Cairo 0 classes.
+    pub sierra_program_length: Option<u64>,
+    /// The length of the ABI in bytes.
+    pub abi_length: Option<u64>,
+    /// The version of the compiler which produced the CASM definition, `None`
+    /// for Cairo 0 classes.
+    pub compiler_version: Option<String>,
+}
+
+impl ClassMetadata {
+    fn from_sierra(definition: &[u8], casm_definition: &[u8]) -> anyhow::Result<Self> {
+        #[derive(serde::Deserialize)]
+        struct Sierra<'a> {
+            #[serde(borrow)]
+            abi: Option<&'a serde_json::value::RawValue>,
+            sierra_program: Vec<serde::de::IgnoredAny>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct Casm {
+            compiler_version: String,
+        }
+
+        let sierra: Sierra<'_> =
+            serde_json::from_slice(definition).context("Parsing sierra definition")?;
+        let casm: Casm =
+            serde_json::from_slice(casm_definition).context("Parsing casm definition")?;
+
+        Ok(Self {
+            is_sierra: true,
+            sierra_program_length: Some(sierra.sierra_program.len() as u64),
+            abi_length: Some(abi_length(sierra.abi)?),
+            compiler_version: Some(casm.compiler_version),
+        })
+    }
+
+    fn from_cairo(definition: &[u8]) -> anyhow::Result<Self> {
+        #[derive(serde::Deserialize)]
+        struct Cairo<'a> {
+            #[serde(borrow)]
+            abi: Option<&'a serde_json::value::RawValue>,
+        }
+
+        let cairo: Cairo<'_> =
+            serde_json::from_slice(definition).context("Parsing cairo definition")?;
+
+        Ok(Self {
+            is_sierra: false,
+            sierra_program_length: None,
+            abi_length: Some(abi_length(cairo.abi)?),
+            compiler_version: None,
+        })
+    }
+
+    fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            is_sierra: row.get(3)?,
+            sierra_program_length: row.get(0)?,
+            abi_length: row.get(1)?,
+            compiler_version: row.get(2)?,
+        })
+    }
+}
+
+/// Sierra classes store the ABI as a JSON encoded string, in which case the
+/// length of the decoded string is used.
+fn abi_length(abi: Option<&serde_json::value::RawValue>) -> anyhow::Result<u64> {
+    let Some(abi) = abi else {
+        return Ok(0);
+    };
+
+    let length = if abi.get().starts_with('"') {
+        serde_json::from_str::<String>(abi.get())
+            .context("Parsing ABI string")?
+            .len()
+    } else {
+        abi.get().len()
+    };
+
+    Ok(length as u64)
+}
+
+/// Decompresses a stored class definition.
+///
+/// Definitions are written using zstd, but databases created by older
+/// versions may also contain gzip compressed or uncompressed definitions. The
+/// format is detected from the leading magic bytes.
+fn decompress_definition(definition: &[u8]) -> anyhow::Result<Vec<u8>> {
+    use std::io::Read;
+
+    const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+    const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+    if definition.starts_with(&ZSTD_MAGIC) {
+        zstd::decode_all(definition).context("Decoding zstd")
+    } else if definition.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(definition)
+            .read_to_end(&mut decompressed)
+            .context("Decoding gzip")?;
+        Ok(decompressed)
+    } else {
+        Ok(definition.to_vec())
+    }
 }
 
 #[cfg(test)]
@@ -556,6 +908,34 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn class_declaration_block() {
+        let mut connection = crate::StorageBuilder::in_memory()
+            .unwrap()
+            .connection()
+            .unwrap();
+        let transaction = connection.transaction().unwrap();
+
+        let (hash, _, _) = setup_class(&transaction);
+        // Definitions are stored before the block declaring them.
+        assert_eq!(transaction.class_declaration_block(hash).unwrap(), None);
+
+        transaction
+            .inner()
+            .execute("UPDATE class_definitions SET block_number = 5", [])
+            .unwrap();
+        assert_eq!(
+            transaction.class_declaration_block(hash).unwrap(),
+            Some(BlockNumber::new_or_panic(5))
+        );
+
+        let non_existent = class_hash!("0x456");
+        assert_eq!(
+            transaction.class_declaration_block(non_existent).unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn insert_cairo() {
         let mut connection = crate::StorageBuilder::in_memory()
@@ -618,6 +998,132 @@ mod tests {
         assert_eq!(definition, sierra_definition);
     }
 
+    fn zstd_compressed(definition: &[u8]) -> Vec<u8> {
+        zstd::bulk::compress(definition, 10).unwrap()
+    }
+
+    fn gzip_compressed(definition: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(definition).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn uncompressed(definition: &[u8]) -> Vec<u8> {
+        definition.to_vec()
+    }
+
+    #[rstest::rstest]
+    #[case::zstd(zstd_compressed)]
+    #[case::gzip(gzip_compressed)]
+    #[case::uncompressed(uncompressed)]
+    fn stored_compression_formats(#[case] compress: fn(&[u8]) -> Vec<u8>) {
+        let mut connection = crate::StorageBuilder::in_memory()
+            .unwrap()
+            .connection()
+            .unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let sierra_hash = sierra_hash_bytes!(b"sierra hash");
+        let casm_hash = casm_hash_bytes!(b"casm hash");
+        let sierra_definition = br#"{"sierra_program":[]}"#;
+        let casm_definition = br#"{"bytecode":[]}"#;
+
+        tx.insert_block_header(&pathfinder_common::BlockHeader::default())
+            .unwrap();
+        tx.insert_sierra_class(&sierra_hash, sierra_definition, &casm_hash, casm_definition)
+            .unwrap();
+
+        // Declare the class at genesis and overwrite the definitions in the
+        // format under test.
+        tx.inner()
+            .execute(
+                "UPDATE class_definitions SET definition = ?, block_number = 0 WHERE hash = ?",
+                params![&compress(sierra_definition), &sierra_hash],
+            )
+            .unwrap();
+        tx.inner()
+            .execute(
+                "UPDATE casm_definitions SET definition = ? WHERE hash = ?",
+                params![&compress(casm_definition), &sierra_hash],
+            )
+            .unwrap();
+
+        let class_hash = ClassHash(sierra_hash.0);
+        let block_id = BlockId::Number(BlockNumber::GENESIS);
+
+        let definition = tx.class_definition(class_hash).unwrap().unwrap();
+        assert_eq!(definition, sierra_definition);
+        let definition = tx
+            .class_definition_at(block_id, class_hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!(definition, sierra_definition);
+
+        let definition = tx.casm_definition(class_hash).unwrap().unwrap();
+        assert_eq!(definition, casm_definition);
+        let definition = tx
+            .casm_definition_at(block_id, class_hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!(definition, casm_definition);
+    }
+
+    #[test]
+    fn recompress_class_definitions() {
+        let mut connection = crate::StorageBuilder::in_memory()
+            .unwrap()
+            .connection()
+            .unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let sierra_hash = sierra_hash_bytes!(b"sierra hash");
+        let casm_hash = casm_hash_bytes!(b"casm hash");
+        let cairo_hash = class_hash_bytes!(b"cairo hash");
+        let sierra_definition = br#"{"sierra_program":[]}"#;
+        let casm_definition = br#"{"bytecode":[]}"#;
+        let cairo_definition = br#"{"abi":[]}"#;
+
+        tx.insert_sierra_class(&sierra_hash, sierra_definition, &casm_hash, casm_definition)
+            .unwrap();
+        tx.insert_cairo_class(cairo_hash, cairo_definition).unwrap();
+        tx.inner()
+            .execute(
+                "UPDATE class_definitions SET definition = ? WHERE hash = ?",
+                params![&gzip_compressed(sierra_definition), &sierra_hash],
+            )
+            .unwrap();
+        tx.inner()
+            .execute(
+                "UPDATE casm_definitions SET definition = ? WHERE hash = ?",
+                params![&uncompressed(casm_definition), &sierra_hash],
+            )
+            .unwrap();
+
+        // The Cairo class is already stored using zstd.
+        assert_eq!(tx.recompress_class_definitions().unwrap(), 2);
+        assert_eq!(tx.recompress_class_definitions().unwrap(), 0);
+
+        let stored: Vec<u8> = tx
+            .inner()
+            .query_row(
+                "SELECT definition FROM class_definitions WHERE hash = ?",
+                params![&sierra_hash],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(stored, zstd_compressed(sierra_definition));
+
+        let class_hash = ClassHash(sierra_hash.0);
+        let definition = tx.class_definition(class_hash).unwrap().unwrap();
+        assert_eq!(definition, sierra_definition);
+        let definition = tx.casm_definition(class_hash).unwrap().unwrap();
+        assert_eq!(definition, casm_definition);
+        let definition = tx.class_definition(cairo_hash).unwrap().unwrap();
+        assert_eq!(definition, cairo_definition);
+    }
+
     #[test]
     fn compiled_class_leaves() {
         let mut connection = crate::StorageBuilder::in_memory()
@@ -654,4 +1160,188 @@ mod tests {
             .unwrap();
         assert!(result.is_none());
     }
+
+    mod metadata {
+        use super::*;
+
+        const SIERRA_DEFINITION: &[u8] =
+            br#"{"abi":"[{\"type\":\"function\"}]","sierra_program":["0x1","0x2","0x3"]}"#;
+        const CASM_DEFINITION: &[u8] = br#"{"compiler_version":"2.6.0","bytecode":[]}"#;
+        const CAIRO_DEFINITION: &[u8] = br#"{"abi":[{"type":"event"}],"program":{}}"#;
+
+        fn sierra_metadata() -> ClassMetadata {
+            ClassMetadata {
+                is_sierra: true,
+                sierra_program_length: Some(3),
+                // The decoded `[{"type":"function"}]`.
+                abi_length: Some(21),
+                compiler_version: Some("2.6.0".to_owned()),
+            }
+        }
+
+        fn cairo_metadata() -> ClassMetadata {
+            ClassMetadata {
+                is_sierra: false,
+                sierra_program_length: None,
+                abi_length: Some(r#"[{"type":"event"}]"#.len() as u64),
+                compiler_version: None,
+            }
+        }
+
+        /// Inserts a Sierra and a Cairo 0 class, both declared at genesis.
+        fn setup(tx: &Transaction<'_>) -> (ClassHash, ClassHash) {
+            let sierra_hash = sierra_hash_bytes!(b"sierra hash");
+            let cairo_hash = class_hash_bytes!(b"cairo hash");
+
+            tx.insert_block_header(&pathfinder_common::BlockHeader::default())
+                .unwrap();
+            tx.insert_sierra_class(
+                &sierra_hash,
+                SIERRA_DEFINITION,
+                &casm_hash_bytes!(b"casm hash"),
+                CASM_DEFINITION,
+            )
+            .unwrap();
+            tx.insert_cairo_class(cairo_hash, CAIRO_DEFINITION).unwrap();
+            tx.inner()
+                .execute("UPDATE class_definitions SET block_number = 0", [])
+                .unwrap();
+
+            (ClassHash(sierra_hash.0), cairo_hash)
+        }
+
+        #[test]
+        fn stored_on_insert() {
+            let mut connection = crate::StorageBuilder::in_memory()
+                .unwrap()
+                .connection()
+                .unwrap();
+            let tx = connection.transaction().unwrap();
+            let (sierra_hash, cairo_hash) = setup(&tx);
+
+            let (sierra_program_length, abi_length, compiler_version): (
+                Option<u64>,
+                Option<u64>,
+                Option<String>,
+            ) = tx
+                .inner()
+                .query_row(
+                    "SELECT sierra_program_length, abi_length, compiler_version
+                    FROM class_definitions WHERE hash = ?",
+                    params![&sierra_hash],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .unwrap();
+            assert_eq!(sierra_program_length, Some(3));
+            assert_eq!(abi_length, Some(21));
+            assert_eq!(compiler_version.as_deref(), Some("2.6.0"));
+
+            assert_eq!(
+                tx.class_metadata(sierra_hash).unwrap(),
+                Some(sierra_metadata())
+            );
+            assert_eq!(
+                tx.class_metadata(cairo_hash).unwrap(),
+                Some(cairo_metadata())
+            );
+        }
+
+        #[test]
+        fn not_stored_if_unparseable() {
+            let mut connection = crate::StorageBuilder::in_memory()
+                .unwrap()
+                .connection()
+                .unwrap();
+            let tx = connection.transaction().unwrap();
+            let sierra_hash = sierra_hash_bytes!(b"sierra hash");
+            let cairo_hash = class_hash_bytes!(b"cairo hash");
+
+            tx.insert_sierra_class(
+                &sierra_hash,
+                b"not a sierra definition",
+                &casm_hash_bytes!(b"casm hash"),
+                CASM_DEFINITION,
+            )
+            .unwrap();
+            tx.insert_cairo_class(cairo_hash, b"not a cairo definition")
+                .unwrap();
+
+            // The class type is still known for definitions which could not be parsed.
+            assert_eq!(
+                tx.class_metadata(ClassHash(sierra_hash.0)).unwrap(),
+                Some(ClassMetadata {
+                    is_sierra: true,
+                    sierra_program_length: None,
+                    abi_length: None,
+                    compiler_version: None,
+                })
+            );
+            assert_eq!(
+                tx.class_metadata(cairo_hash).unwrap(),
+                Some(ClassMetadata {
+                    is_sierra: false,
+                    sierra_program_length: None,
+                    abi_length: None,
+                    compiler_version: None,
+                })
+            );
+        }
+
+        #[test]
+        fn computed_if_not_stored() {
+            let mut connection = crate::StorageBuilder::in_memory()
+                .unwrap()
+                .connection()
+                .unwrap();
+            let tx = connection.transaction().unwrap();
+            let (sierra_hash, cairo_hash) = setup(&tx);
+
+            // As if stored by a version without the metadata columns.
+            tx.inner()
+                .execute(
+                    "UPDATE class_definitions SET
+                        sierra_program_length = NULL, abi_length = NULL, compiler_version = NULL",
+                    [],
+                )
+                .unwrap();
+
+            assert_eq!(
+                tx.class_metadata(sierra_hash).unwrap(),
+                Some(sierra_metadata())
+            );
+            assert_eq!(
+                tx.class_metadata_at(BlockId::Latest, cairo_hash).unwrap(),
+                Some(cairo_metadata())
+            );
+        }
+
+        #[test]
+        fn at_block() {
+            let mut connection = crate::StorageBuilder::in_memory()
+                .unwrap()
+                .connection()
+                .unwrap();
+            let tx = connection.transaction().unwrap();
+            let (sierra_hash, _) = setup(&tx);
+
+            let genesis = BlockId::Number(BlockNumber::GENESIS);
+            assert_eq!(
+                tx.class_metadata_at(genesis, sierra_hash).unwrap(),
+                Some(sierra_metadata())
+            );
+            assert_eq!(
+                tx.class_metadata_at(BlockId::Latest, sierra_hash).unwrap(),
+                Some(sierra_metadata())
+            );
+
+            let missing_block = BlockId::Number(BlockNumber::GENESIS + 1);
+            assert_eq!(
+                tx.class_metadata_at(missing_block, sierra_hash).unwrap(),
+                None
+            );
+
+            let missing_class = class_hash_bytes!(b"missing");
+            assert_eq!(tx.class_metadata_at(genesis, missing_class).unwrap(), None);
+        }
+    }
 }
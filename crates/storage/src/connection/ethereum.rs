@@ -9,16 +9,22 @@ impl Transaction<'_> {
             r"INSERT OR REPLACE INTO l1_state (
                     starknet_block_number,
                     starknet_block_hash,
-                    starknet_state_root
+                    starknet_state_root,
+                    l1_block_number,
+                    l1_transaction_hash
                 ) VALUES (
                     :starknet_block_number,
                     :starknet_block_hash,
-                    :starknet_state_root
+                    :starknet_state_root,
+                    :l1_block_number,
+                    :l1_transaction_hash
                 )",
             named_params! {
                 ":starknet_block_number": &update.block_number,
                 ":starknet_block_hash": &update.block_hash,
                 ":starknet_state_root": &update.state_root,
+                ":l1_block_number": &update.l1_block_number,
+                ":l1_transaction_hash": &update.l1_transaction_hash,
             },
         )?;
 
@@ -31,53 +37,74 @@ impl Transaction<'_> {
     ) -> anyhow::Result<Option<EthereumStateUpdate>> {
         self.inner()
         .query_row(
-            r"SELECT starknet_block_number, starknet_block_hash, starknet_state_root FROM l1_state 
+            r"SELECT starknet_block_number, starknet_block_hash, starknet_state_root, l1_block_number, l1_transaction_hash FROM l1_state 
             WHERE starknet_block_number = ?",
             params![&block],
-            |row| {
-                let block_number = row.get_block_number(0)?;
-                let block_hash = row.get_block_hash(1)?;
-                let state_root = row.get_state_commitment(2)?;
-
-                Ok(EthereumStateUpdate {
-                    state_root,
-                    block_number,
-                    block_hash,
-                })
-            },
+            l1_state_from_row,
         )
         .optional()
         .map_err(|e| e.into())
     }
 
+    /// Returns the first L1 state update at or after `block`, i.e. the state
+    /// update which accepted `block` on L1.
+    ///
+    /// Returns [None] if that update has no L1 block number. Such updates are
+    /// snapshots of the L1 state, which may have been accepted along with
+    /// earlier blocks, or were stored before this information was tracked.
+    pub fn l1_state_accepting_number(
+        &self,
+        block: BlockNumber,
+    ) -> anyhow::Result<Option<EthereumStateUpdate>> {
+        let update = self
+            .inner()
+            .query_row(
+                r"SELECT starknet_block_number, starknet_block_hash, starknet_state_root, l1_block_number, l1_transaction_hash FROM l1_state
+                WHERE starknet_block_number >= ?
+                ORDER BY starknet_block_number ASC
+                LIMIT 1",
+                params![&block],
+                l1_state_from_row,
+            )
+            .optional()?;
+
+        Ok(update.filter(|update| update.l1_block_number.is_some()))
+    }
+
     pub fn latest_l1_state(&self) -> anyhow::Result<Option<EthereumStateUpdate>> {
         self.inner()
         .query_row(
-            r"SELECT starknet_block_number, starknet_block_hash, starknet_state_root FROM l1_state 
+            r"SELECT starknet_block_number, starknet_block_hash, starknet_state_root, l1_block_number, l1_transaction_hash FROM l1_state 
             ORDER BY starknet_block_number DESC
             LIMIT 1",
             [],
-            |row| {
-                let block_number = row.get_block_number(0)?;
-                let block_hash = row.get_block_hash(1)?;
-                let state_root = row.get_state_commitment(2)?;
-
-                Ok(EthereumStateUpdate {
-                    state_root,
-                    block_number,
-                    block_hash,
-                })
-            },
+            l1_state_from_row,
         )
         .optional()
         .map_err(|e| e.into())
     }
 }
 
+fn l1_state_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<EthereumStateUpdate> {
+    let block_number = row.get_block_number(0)?;
+    let block_hash = row.get_block_hash(1)?;
+    let state_root = row.get_state_commitment(2)?;
+    let l1_block_number = row.get_optional_l1_block_number(3)?;
+    let l1_transaction_hash = row.get_optional_l1_transaction_hash(4)?;
+
+    Ok(EthereumStateUpdate {
+        state_root,
+        block_number,
+        block_hash,
+        l1_block_number,
+        l1_transaction_hash,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use pathfinder_common::macro_prelude::*;
-    use pathfinder_common::{BlockHash, StateCommitment};
+    use pathfinder_common::{BlockHash, L1BlockNumber, L1TransactionHash, StateCommitment};
     use pathfinder_crypto::Felt;
     use pathfinder_ethereum::EthereumStateUpdate;
 
@@ -91,6 +118,8 @@ mod tests {
                 state_root: StateCommitment(Felt::from_hex_str(&"3".repeat(i + 1)).unwrap()),
                 block_number: BlockNumber::GENESIS + i as u64,
                 block_hash: BlockHash(Felt::from_hex_str(&"F".repeat(i + 1)).unwrap()),
+                l1_block_number: Some(L1BlockNumber::new_or_panic(100 + i as u64)),
+                l1_transaction_hash: Some(L1TransactionHash::from([i as u8 + 1; 32])),
             })
             .collect::<Vec<_>>()
             .try_into()
@@ -154,6 +183,8 @@ mod tests {
             state_root: state_commitment!("0x1234"),
             block_number: BlockNumber::new_or_panic(10),
             block_hash: block_hash!("0xabdd"),
+            l1_block_number: None,
+            l1_transaction_hash: None,
         };
         tx.upsert_l1_state(&original).unwrap();
 
@@ -161,6 +192,8 @@ mod tests {
             state_root: state_commitment!("0xabcdef"),
             block_number: original.block_number,
             block_hash: block_hash!("0xccdd22"),
+            l1_block_number: Some(L1BlockNumber::new_or_panic(12)),
+            l1_transaction_hash: Some(L1TransactionHash::from([0x12; 32])),
         };
         tx.upsert_l1_state(&new_value).unwrap();
 
@@ -170,4 +203,63 @@ mod tests {
             .unwrap();
         assert_eq!(result, new_value);
     }
+
+    #[test]
+    fn accepting_number() {
+        let storage = crate::StorageBuilder::in_memory().unwrap();
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        // L1 state updates usually cover a range of L2 blocks.
+        let updates = [3, 7].map(|number| EthereumStateUpdate {
+            block_number: BlockNumber::new_or_panic(number),
+            l1_block_number: Some(L1BlockNumber::new_or_panic(100 + number)),
+            ..Default::default()
+        });
+        for update in &updates {
+            tx.upsert_l1_state(update).unwrap();
+        }
+
+        for (number, expected) in [
+            (0, Some(updates[0])),
+            (3, Some(updates[0])),
+            (4, Some(updates[1])),
+            (7, Some(updates[1])),
+            (8, None),
+        ] {
+            let result = tx
+                .l1_state_accepting_number(BlockNumber::new_or_panic(number))
+                .unwrap();
+            assert_eq!(result, expected, "block {number}");
+        }
+    }
+
+    #[test]
+    fn accepting_number_is_unknown_for_snapshot() {
+        let storage = crate::StorageBuilder::in_memory().unwrap();
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        // The L1 state read on startup has no L1 block number, the update which
+        // accepted the earlier blocks is not known.
+        let snapshot = EthereumStateUpdate {
+            block_number: BlockNumber::new_or_panic(3),
+            l1_block_number: None,
+            ..Default::default()
+        };
+        let update = EthereumStateUpdate {
+            block_number: BlockNumber::new_or_panic(7),
+            l1_block_number: Some(L1BlockNumber::new_or_panic(107)),
+            ..Default::default()
+        };
+        tx.upsert_l1_state(&snapshot).unwrap();
+        tx.upsert_l1_state(&update).unwrap();
+
+        for (number, expected) in [(0, None), (3, None), (4, Some(update)), (7, Some(update))] {
+            let result = tx
+                .l1_state_accepting_number(BlockNumber::new_or_panic(number))
+                .unwrap();
+            assert_eq!(result, expected, "block {number}");
+        }
+    }
 }
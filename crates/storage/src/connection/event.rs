@@ -1,8 +1,10 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use pathfinder_common::event::Event;
+use pathfinder_common::transaction::TransactionKind;
 use pathfinder_common::{
     BlockHash,
     BlockNumber,
@@ -12,6 +14,7 @@ use pathfinder_common::{
     TransactionHash,
 };
 
+use super::transaction::CorruptEvents;
 use crate::bloom::{AggregateBloom, BloomFilter};
 use crate::prelude::*;
 
@@ -30,6 +33,10 @@ pub struct EventConstraints {
     pub keys: Vec<Vec<EventKey>>,
     pub page_size: usize,
     pub offset: usize,
+    /// Only return events emitted by transactions of these kinds.
+    pub transaction_types: Option<Vec<TransactionKind>>,
+    /// Only return events emitted by one of these contracts.
+    pub from_addresses: Option<Vec<ContractAddress>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -66,6 +73,8 @@ pub struct ContinuationToken {
 pub struct PageOfEvents {
     pub events: Vec<EmittedEvent>,
     pub continuation_token: Option<ContinuationToken>,
+    /// Blocks whose events could not be decoded and were skipped.
+    pub corrupt_blocks: Vec<BlockNumber>,
 }
 
 impl Transaction<'_> {
@@ -116,6 +125,8 @@ impl Transaction<'_> {
                 &running_event_filter.filter.to_block,
                 &running_event_filter.filter.compress_bitmap()
             ])?;
+            self.event_filter_cache
+                .invalidate(running_event_filter.filter.from_block);
 
             *running_event_filter = RunningEventFilter {
                 filter: AggregateBloom::new(block_number + 1),
@@ -256,6 +267,7 @@ impl Transaction<'_> {
         let mut offset = constraints.offset;
 
         let mut emitted_events = vec![];
+        let mut corrupt_blocks = vec![];
 
         for (blocks_scanned, block) in blocks_to_scan.enumerate() {
             if blocks_scanned >= max_blocks_to_scan.get() {
@@ -266,6 +278,7 @@ impl Transaction<'_> {
                         block_number: block,
                         offset: 0,
                     }),
+                    corrupt_blocks,
                 });
             }
 
@@ -276,15 +289,39 @@ impl Transaction<'_> {
                 break;
             };
 
-            let events = match self.events_for_block(block.into())? {
-                Some(events) => events,
+            let events = match self.events_for_block(block.into()) {
+                Ok(Some(events)) => events,
                 // Reached the end of P2P (checkpoint) synced events.
-                None => {
+                Ok(None) => {
                     return Ok(PageOfEvents {
                         events: emitted_events,
                         continuation_token: None,
+                        corrupt_blocks,
                     })
                 }
+                // A single unreadable block must not make the rest of the range
+                // inaccessible. Other errors, such as failing database queries, are not
+                // specific to the block.
+                Err(error) if error.is::<CorruptEvents>() => {
+                    tracing::error!(
+                        %block,
+                        error = %format!("{error:#}"),
+                        "Skipping corrupt events"
+                    );
+                    corrupt_blocks.push(block);
+                    continue;
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            let transaction_kinds: HashMap<_, _> = match constraints.transaction_types {
+                Some(_) => self
+                    .transactions_for_block(block.into())?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|tx| (tx.hash, tx.variant.kind()))
+                    .collect(),
+                None => HashMap::new(),
             };
 
             let events = events
@@ -296,6 +333,16 @@ impl Transaction<'_> {
                     Some(address) => event.from_address == address,
                     None => true,
                 })
+                .filter(|(event, _)| match &constraints.from_addresses {
+                    Some(addresses) => addresses.contains(&event.from_address),
+                    None => true,
+                })
+                .filter(|(_, tx_hash)| match &constraints.transaction_types {
+                    Some(types) => transaction_kinds
+                        .get(tx_hash)
+                        .is_some_and(|kind| types.contains(kind)),
+                    None => true,
+                })
                 .filter(|(event, _)| {
                     if key_filter_is_empty {
                         return true;
@@ -349,6 +396,7 @@ impl Transaction<'_> {
                         // Account for the extra event.
                         offset: continuation_token.offset - 1,
                     }),
+                    corrupt_blocks,
                 });
             }
         }
@@ -366,11 +414,13 @@ impl Transaction<'_> {
                     block_number: last_loaded_block + 1,
                     offset: 0,
                 }),
+                corrupt_blocks,
             })
         } else {
             Ok(PageOfEvents {
                 events: emitted_events,
                 continuation_token: None,
+                corrupt_blocks,
             })
         }
     }
@@ -378,10 +428,10 @@ impl Transaction<'_> {
         &self,
         start_block: BlockNumber,
         end_block: BlockNumber,
-    ) -> anyhow::Result<Vec<AggregateBloom>> {
+    ) -> anyhow::Result<Vec<Arc<AggregateBloom>>> {
         let mut stmt = self.inner().prepare_cached(
             r"
-            SELECT from_block, to_block, bitmap
+            SELECT from_block, to_block
             FROM event_filters
             WHERE from_block <= :end_block AND to_block >= :start_block
             ORDER BY from_block
@@ -397,17 +447,15 @@ impl Transaction<'_> {
                 |row| {
                     let from_block = row.get_block_number(0)?;
                     let to_block = row.get_block_number(1)?;
-                    let compressed_bitmap: Vec<u8> = row.get(2)?;
 
-                    Ok(AggregateBloom::from_existing_compressed(
-                        from_block,
-                        to_block,
-                        compressed_bitmap,
-                    ))
+                    Ok((from_block, to_block))
                 },
             )
             .context("Querying event filter range")?
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|(from_block, to_block)| self.load_event_filter(from_block, to_block))
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         // There are no event filters in the database yet or the loaded ones
         // don't cover the requested range.
@@ -417,7 +465,7 @@ impl Transaction<'_> {
 
         if should_include_running {
             let running_event_filter = self.running_event_filter.lock().unwrap();
-            event_filters.push(running_event_filter.filter.clone());
+            event_filters.push(Arc::new(running_event_filter.filter.clone()));
         }
 
         Ok(event_filters)
@@ -428,10 +476,10 @@ impl Transaction<'_> {
         start_block: BlockNumber,
         end_block: BlockNumber,
         max_event_filters_to_load: NonZeroUsize,
-    ) -> anyhow::Result<(Vec<AggregateBloom>, bool)> {
+    ) -> anyhow::Result<(Vec<Arc<AggregateBloom>>, bool)> {
         let mut event_filters_in_range_stmt = self.inner().prepare_cached(
             r"
-            SELECT from_block, to_block, bitmap
+            SELECT from_block, to_block
             FROM event_filters
             WHERE from_block <= :end_block AND to_block >= :start_block
             ORDER BY from_block
@@ -456,17 +504,15 @@ impl Transaction<'_> {
                 |row| {
                     let from_block = row.get_block_number(0)?;
                     let to_block = row.get_block_number(1)?;
-                    let compressed_bitmap: Vec<u8> = row.get(2)?;
 
-                    Ok(AggregateBloom::from_existing_compressed(
-                        from_block,
-                        to_block,
-                        compressed_bitmap,
-                    ))
+                    Ok((from_block, to_block))
                 },
             )
             .context("Querying event filter range")?
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|(from_block, to_block)| self.load_event_filter(from_block, to_block))
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         // There are no event filters in the database yet or the loaded ones
         // don't cover the requested range.
@@ -485,12 +531,38 @@ impl Transaction<'_> {
 
         if should_include_running && !load_limit_reached {
             let running_event_filter = self.running_event_filter.lock().unwrap();
-            event_filters.push(running_event_filter.filter.clone());
+            event_filters.push(Arc::new(running_event_filter.filter.clone()));
         }
 
         Ok((event_filters, load_limit_reached))
     }
 
+    /// Loads a stored aggregate Bloom filter, from the cache if possible.
+    fn load_event_filter(
+        &self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> anyhow::Result<Arc<AggregateBloom>> {
+        if let Some(filter) = self.event_filter_cache.get(from_block) {
+            return Ok(filter);
+        }
+
+        let compressed_bitmap: Vec<u8> = self
+            .inner()
+            .prepare_cached("SELECT bitmap FROM event_filters WHERE from_block = ?")?
+            .query_row(params![&from_block], |row| row.get(0))
+            .context("Querying event filter bitmap")?;
+
+        let filter = Arc::new(try_bloom_filter_check(
+            from_block,
+            to_block,
+            compressed_bitmap,
+        ));
+        self.event_filter_cache.set(filter.clone());
+
+        Ok(filter)
+    }
+
     pub fn next_block_without_events(&self) -> BlockNumber {
         self.running_event_filter.lock().unwrap().next_block
     }
@@ -500,9 +572,14 @@ impl AggregateBloom {
     /// Returns the block numbers that match the given constraints.
     pub fn check(&self, constraints: &EventConstraints) -> BTreeSet<BlockNumber> {
         let addr_blocks = self.check_address(constraints.contract_address);
+        let addresses_blocks = self.check_addresses(constraints.from_addresses.as_deref());
         let keys_blocks = self.check_keys(&constraints.keys);
 
-        addr_blocks.intersection(&keys_blocks).cloned().collect()
+        addr_blocks
+            .intersection(&addresses_blocks)
+            .filter(|block| keys_blocks.contains(block))
+            .cloned()
+            .collect()
     }
 
     fn check_address(&self, address: Option<ContractAddress>) -> BTreeSet<BlockNumber> {
@@ -512,6 +589,20 @@ impl AggregateBloom {
         }
     }
 
+    /// Blocks which may contain events from any of the addresses.
+    fn check_addresses(&self, addresses: Option<&[ContractAddress]>) -> BTreeSet<BlockNumber> {
+        match addresses {
+            Some(addresses) => {
+                let addresses = addresses
+                    .iter()
+                    .map(|address| address.0)
+                    .collect::<Vec<_>>();
+                self.blocks_for_keys(&addresses)
+            }
+            None => self.all_blocks(),
+        }
+    }
+
     fn check_keys(&self, keys: &[Vec<EventKey>]) -> BTreeSet<BlockNumber> {
         if keys.is_empty() {
             return self.all_blocks();
@@ -650,6 +741,28 @@ pub(crate) fn reconstruct_running_event_filter(
     })
 }
 
+/// Loads an aggregate Bloom filter stored in the database.
+///
+/// A corrupted filter should not make event queries fail, so it gets replaced
+/// with a filter that matches every block in its range. The affected blocks
+/// then get scanned in full.
+fn try_bloom_filter_check(
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    compressed_bitmap: Vec<u8>,
+) -> AggregateBloom {
+    match AggregateBloom::try_from_existing_compressed(from_block, to_block, compressed_bitmap) {
+        Ok(filter) => filter,
+        Err(error) => {
+            tracing::warn!(
+                %from_block, %to_block, ?error,
+                "Failed to load event filter, falling back to a full scan of its block range"
+            );
+            AggregateBloom::match_all(from_block)
+        }
+    }
+}
+
 fn continuation_token(
     events: &[EmittedEvent],
     previous_token: ContinuationToken,
@@ -725,6 +838,8 @@ mod tests {
                 keys: vec![vec![event_key!("0xdeadbeef")]],
                 page_size: 1024,
                 offset: 0,
+                transaction_types: None,
+                from_addresses: None,
             };
 
             assert_eq!(
@@ -750,6 +865,8 @@ mod tests {
                 keys: vec![vec![event_key!("0xdeadbeef")]],
                 page_size: 1024,
                 offset: 0,
+                transaction_types: None,
+                from_addresses: None,
             };
 
             assert_eq!(aggregate.check(&constraints), BTreeSet::new());
@@ -772,6 +889,8 @@ mod tests {
                 keys: vec![vec![event_key!("0xfeebdaed"), event_key!("0x4321")]],
                 page_size: 1024,
                 offset: 0,
+                transaction_types: None,
+                from_addresses: None,
             };
 
             assert_eq!(aggregate.check(&constraints), BTreeSet::new());
@@ -799,6 +918,8 @@ mod tests {
                 ],
                 page_size: 1024,
                 offset: 0,
+                transaction_types: None,
+                from_addresses: None,
             };
 
             assert_eq!(aggregate.check(&constraints), BTreeSet::new());
@@ -821,6 +942,8 @@ mod tests {
                 keys: vec![],
                 page_size: 1024,
                 offset: 0,
+                transaction_types: None,
+                from_addresses: None,
             };
 
             assert_eq!(aggregate.check(&constraints), aggregate.all_blocks());
@@ -843,6 +966,8 @@ mod tests {
             keys: vec![vec![], vec![event_key!("0xdeadbeef")]],
             page_size: test_utils::NUM_EVENTS,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -857,6 +982,7 @@ mod tests {
             PageOfEvents {
                 events: vec![expected_event.clone()],
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -952,6 +1078,8 @@ mod tests {
                     keys: vec![],
                     page_size: 1024,
                     offset: 0,
+                    transaction_types: None,
+                    from_addresses: None,
                 },
                 *MAX_BLOCKS_TO_SCAN,
                 *MAX_BLOOM_FILTERS_TO_LOAD,
@@ -985,6 +1113,8 @@ mod tests {
             keys: vec![],
             page_size: test_utils::NUM_EVENTS,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let expected_events = &emitted_events[test_utils::EVENTS_PER_BLOCK * BLOCK_NUMBER
@@ -1001,10 +1131,80 @@ mod tests {
             PageOfEvents {
                 events: expected_events.to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
 
+    #[test]
+    fn corrupt_events_are_skipped() {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let corrupt_block = BlockNumber::new_or_panic(2);
+        test_utils::corrupt_block_events(&storage, corrupt_block);
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let constraints = EventConstraints {
+            from_block: None,
+            to_block: None,
+            contract_address: None,
+            keys: vec![],
+            page_size: test_utils::NUM_EVENTS,
+            offset: 0,
+            transaction_types: None,
+            from_addresses: None,
+        };
+
+        let expected_events = test_data
+            .events
+            .into_iter()
+            .filter(|event| event.block_number != corrupt_block)
+            .collect::<Vec<_>>();
+        let events = tx
+            .events(
+                &constraints,
+                *MAX_BLOCKS_TO_SCAN,
+                *MAX_BLOOM_FILTERS_TO_LOAD,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            PageOfEvents {
+                events: expected_events,
+                continuation_token: None,
+                corrupt_blocks: vec![corrupt_block],
+            }
+        );
+    }
+
+    #[test]
+    fn storage_errors_are_not_skipped() {
+        let (storage, _) = test_utils::setup_test_storage();
+        test_utils::drop_transactions_table(&storage);
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let constraints = EventConstraints {
+            from_block: None,
+            to_block: None,
+            contract_address: None,
+            keys: vec![],
+            page_size: test_utils::NUM_EVENTS,
+            offset: 0,
+            transaction_types: None,
+            from_addresses: None,
+        };
+
+        let error = tx
+            .events(
+                &constraints,
+                *MAX_BLOCKS_TO_SCAN,
+                *MAX_BLOOM_FILTERS_TO_LOAD,
+            )
+            .unwrap_err();
+        assert_matches::assert_matches!(error, EventFilterError::Internal(_));
+    }
+
     #[test]
     fn get_events_up_to_block() {
         let (storage, test_data) = test_utils::setup_test_storage();
@@ -1020,6 +1220,8 @@ mod tests {
             keys: vec![],
             page_size: test_utils::NUM_EVENTS,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let expected_events =
@@ -1036,6 +1238,7 @@ mod tests {
             PageOfEvents {
                 events: expected_events.to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1054,6 +1257,8 @@ mod tests {
             keys: vec![],
             page_size: test_utils::EVENTS_PER_BLOCK + 1,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let expected_events = &emitted_events[..test_utils::EVENTS_PER_BLOCK + 1];
@@ -1072,6 +1277,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(1),
                     offset: 1
                 }),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1083,6 +1289,8 @@ mod tests {
             keys: vec![],
             page_size: test_utils::EVENTS_PER_BLOCK + 1,
             offset: events.continuation_token.unwrap().offset,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let expected_events =
@@ -1099,6 +1307,7 @@ mod tests {
             PageOfEvents {
                 events: expected_events.to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1118,6 +1327,8 @@ mod tests {
             keys: vec![],
             page_size: test_utils::NUM_EVENTS,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let expected_events = &emitted_events[test_utils::EVENTS_PER_BLOCK * FROM_BLOCK_NUMBER..];
@@ -1133,6 +1344,7 @@ mod tests {
             PageOfEvents {
                 events: expected_events.to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1153,6 +1365,8 @@ mod tests {
             keys: vec![],
             page_size: test_utils::NUM_EVENTS,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1167,6 +1381,7 @@ mod tests {
             PageOfEvents {
                 events: vec![expected_event.clone()],
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1186,6 +1401,8 @@ mod tests {
             keys: vec![vec![expected_event.keys[0]], vec![expected_event.keys[1]]],
             page_size: test_utils::NUM_EVENTS,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1200,6 +1417,7 @@ mod tests {
             PageOfEvents {
                 events: vec![expected_event.clone()],
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1221,6 +1439,7 @@ mod tests {
             PageOfEvents {
                 events: vec![],
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1239,6 +1458,8 @@ mod tests {
             keys: vec![],
             page_size: test_utils::NUM_EVENTS,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1253,6 +1474,7 @@ mod tests {
             PageOfEvents {
                 events: emitted_events,
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1271,6 +1493,8 @@ mod tests {
             keys: vec![],
             page_size: 10,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1288,6 +1512,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(1),
                     offset: 0
                 }),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1298,6 +1523,8 @@ mod tests {
             keys: vec![],
             page_size: 10,
             offset: 10,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1315,6 +1542,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(2),
                     offset: 0
                 }),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1325,6 +1553,8 @@ mod tests {
             keys: vec![],
             page_size: 10,
             offset: 30,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1338,7 +1568,8 @@ mod tests {
             events,
             PageOfEvents {
                 events: emitted_events[30..40].to_vec(),
-                continuation_token: None
+                continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1358,6 +1589,8 @@ mod tests {
             page_size: PAGE_SIZE,
             // _after_ the last one
             offset: test_utils::NUM_BLOCKS * test_utils::EVENTS_PER_BLOCK,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1372,6 +1605,7 @@ mod tests {
             PageOfEvents {
                 events: vec![],
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1396,6 +1630,8 @@ mod tests {
             keys: keys_for_expected_events.clone(),
             page_size: 2,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1413,6 +1649,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(0),
                     offset: 2
                 }),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1424,6 +1661,8 @@ mod tests {
             keys: keys_for_expected_events.clone(),
             page_size: 2,
             offset: 2,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1441,6 +1680,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(3),
                     offset: 1
                 }),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1452,6 +1692,8 @@ mod tests {
             keys: keys_for_expected_events.clone(),
             page_size: 2,
             offset: 2,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1469,6 +1711,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(3),
                     offset: 1
                 }),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1480,6 +1723,8 @@ mod tests {
             keys: keys_for_expected_events.clone(),
             page_size: 2,
             offset: 4,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1494,6 +1739,7 @@ mod tests {
             PageOfEvents {
                 events: expected_events[4..].to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1505,6 +1751,8 @@ mod tests {
             keys: keys_for_expected_events,
             page_size: 2,
             offset: 1,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1519,6 +1767,7 @@ mod tests {
             PageOfEvents {
                 events: expected_events[4..].to_vec(),
                 continuation_token: None,
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1537,6 +1786,8 @@ mod tests {
             keys: vec![],
             page_size: 20,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1554,6 +1805,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(1),
                     offset: 0
                 }),
+                corrupt_blocks: vec![],
             }
         );
 
@@ -1564,6 +1816,8 @@ mod tests {
             keys: vec![],
             page_size: 20,
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1581,6 +1835,7 @@ mod tests {
                     block_number: BlockNumber::new_or_panic(2),
                     offset: 0
                 }),
+                corrupt_blocks: vec![],
             }
         );
     }
@@ -1630,6 +1885,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn corrupted_event_filter_falls_back_to_full_scan() {
+        let blocks: Vec<usize> = [
+            BlockNumber::GENESIS,
+            BlockNumber::GENESIS + 1,
+            BlockNumber::GENESIS + 2,
+            BlockNumber::GENESIS + 3,
+            // Completes the first event filter range so that it gets stored.
+            BlockNumber::GENESIS + AggregateBloom::BLOCK_RANGE_LEN - 1,
+        ]
+        .iter()
+        .map(|&n| n.get() as usize)
+        .collect();
+
+        let (storage, test_data) = test_utils::setup_custom_test_storage(&blocks, 2);
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let to_block = BlockNumber::GENESIS + 3;
+        let expected_events = test_data
+            .events
+            .into_iter()
+            .filter(|event| event.block_number <= to_block)
+            .collect::<Vec<_>>();
+
+        let constraints = EventConstraints {
+            from_block: None,
+            to_block: Some(to_block),
+            contract_address: None,
+            // We're using a key which is present in _all_ events as the 2nd key.
+            keys: vec![vec![], vec![event_key!("0xdeadbeef")]],
+            page_size: expected_events.len(),
+            offset: 0,
+            transaction_types: None,
+            from_addresses: None,
+        };
+
+        let events = tx
+            .events(
+                &constraints,
+                *MAX_BLOCKS_TO_SCAN,
+                *MAX_BLOOM_FILTERS_TO_LOAD,
+            )
+            .unwrap();
+        assert_eq!(events.events, expected_events);
+
+        let corrupted = tx
+            .inner()
+            .execute(
+                "UPDATE event_filters SET bitmap = ?",
+                [b"corrupted".as_slice()],
+            )
+            .unwrap();
+        assert_eq!(corrupted, 1);
+
+        let events = tx
+            .events(
+                &constraints,
+                *MAX_BLOCKS_TO_SCAN,
+                *MAX_BLOOM_FILTERS_TO_LOAD,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            PageOfEvents {
+                events: expected_events,
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn loaded_event_filters_are_cached() {
+        let blocks: Vec<usize> = [
+            BlockNumber::GENESIS,
+            BlockNumber::GENESIS + 1,
+            // Completes the first event filter range so that it gets stored.
+            BlockNumber::GENESIS + AggregateBloom::BLOCK_RANGE_LEN - 1,
+        ]
+        .iter()
+        .map(|&n| n.get() as usize)
+        .collect();
+
+        let (storage, _) = test_utils::setup_custom_test_storage(&blocks, 2);
+        // Open the same database again, this time with room for a single
+        // cached filter.
+        let storage = crate::StorageBuilder::file(storage.0.database_path.to_path_buf())
+            .journal_mode(crate::JournalMode::Rollback)
+            .bloom_filter_cache_size(AggregateBloom::BLOCK_RANGE_LEN as usize)
+            .migrate()
+            .unwrap()
+            .create_pool(std::num::NonZeroU32::new(1).unwrap())
+            .unwrap();
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let from_block = BlockNumber::GENESIS;
+        let to_block = BlockNumber::GENESIS + AggregateBloom::BLOCK_RANGE_LEN - 1;
+        assert!(tx.event_filter_cache.get(from_block).is_none());
+
+        let loaded = tx.load_event_filter(from_block, to_block).unwrap();
+        let cached = tx.event_filter_cache.get(from_block).unwrap();
+        assert!(Arc::ptr_eq(&loaded, &cached));
+        let reloaded = tx.load_event_filter(from_block, to_block).unwrap();
+        assert!(Arc::ptr_eq(&loaded, &reloaded));
+
+        // Removing a block also removes the stored filter of its range.
+        tx.purge_block(BlockNumber::GENESIS + 1).unwrap();
+        assert!(tx.event_filter_cache.get(from_block).is_none());
+    }
+
     #[test]
     fn event_filter_filter_load_limit() {
         let blocks: Vec<usize> = [
@@ -1668,6 +2035,8 @@ mod tests {
             keys: vec![vec![], vec![event_key!("0xdeadbeef")]],
             page_size: emitted_events.len(),
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1695,6 +2064,8 @@ mod tests {
             keys: vec![vec![], vec![event_key!("0xdeadbeef")]],
             page_size: emitted_events.len(),
             offset: 0,
+            transaction_types: None,
+            from_addresses: None,
         };
 
         let events = tx
@@ -1719,4 +2090,207 @@ mod tests {
             );
         }
     }
+
+    #[rstest::rstest]
+    #[case::invoke(vec![TransactionKind::Invoke])]
+    #[case::declare(vec![TransactionKind::Declare])]
+    #[case::deploy(vec![TransactionKind::Deploy])]
+    #[case::deploy_account(vec![TransactionKind::DeployAccount])]
+    #[case::l1_handler(vec![TransactionKind::L1Handler])]
+    #[case::invoke_and_deploy(vec![TransactionKind::Invoke, TransactionKind::Deploy])]
+    fn get_events_by_transaction_type(#[case] transaction_types: Vec<TransactionKind>) {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let transaction_kinds: HashMap<_, _> = test_data
+            .transactions
+            .iter()
+            .map(|tx| (tx.hash, tx.variant.kind()))
+            .collect();
+        let expected_events = test_data
+            .events
+            .into_iter()
+            .filter(|event| transaction_types.contains(&transaction_kinds[&event.transaction_hash]))
+            .collect::<Vec<_>>();
+
+        let constraints = EventConstraints {
+            from_block: None,
+            to_block: None,
+            contract_address: None,
+            keys: vec![],
+            page_size: test_utils::NUM_EVENTS,
+            offset: 0,
+            transaction_types: Some(transaction_types),
+            from_addresses: None,
+        };
+
+        let events = tx
+            .events(
+                &constraints,
+                *MAX_BLOCKS_TO_SCAN,
+                *MAX_BLOOM_FILTERS_TO_LOAD,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            PageOfEvents {
+                events: expected_events,
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn get_events_from_multiple_addresses() {
+        let (storage, test_data) = test_utils::setup_test_storage();
+        let mut connection = storage.connection().unwrap();
+        let tx = connection.transaction().unwrap();
+
+        let addresses = vec![
+            test_data.events[0].from_address,
+            test_data.events[test_utils::NUM_EVENTS - 1].from_address,
+        ];
+        assert_ne!(addresses[0], addresses[1]);
+        let expected_events = test_data
+            .events
+            .into_iter()
+            .filter(|event| addresses.contains(&event.from_address))
+            .collect::<Vec<_>>();
+
+        let constraints = EventConstraints {
+            from_block: None,
+            to_block: None,
+            contract_address: None,
+            keys: vec![],
+            page_size: test_utils::NUM_EVENTS,
+            offset: 0,
+            transaction_types: None,
+            from_addresses: Some(addresses),
+        };
+
+        let events = tx
+            .events(
+                &constraints,
+                *MAX_BLOCKS_TO_SCAN,
+                *MAX_BLOOM_FILTERS_TO_LOAD,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            PageOfEvents {
+                events: expected_events,
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::all_wildcards(vec![vec![], vec![], vec![]], &[0, 1])]
+    #[case::first(vec![vec![event_key!("0x1")], vec![], vec![]], &[0])]
+    #[case::second(vec![vec![], vec![event_key!("0x2")], vec![]], &[0])]
+    #[case::third(vec![vec![], vec![], vec![event_key!("0x3")]], &[0])]
+    #[case::first_and_second(vec![vec![event_key!("0x1")], vec![event_key!("0x2")], vec![]], &[0])]
+    #[case::first_and_third(vec![vec![event_key!("0x1")], vec![], vec![event_key!("0x3")]], &[0])]
+    #[case::second_and_third(vec![vec![], vec![event_key!("0x2")], vec![event_key!("0x3")]], &[0])]
+    #[case::no_wildcards(
+        vec![vec![event_key!("0x1")], vec![event_key!("0x2")], vec![event_key!("0x3")]],
+        &[0]
+    )]
+    #[case::alternatives_with_wildcard(
+        vec![vec![event_key!("0x1"), event_key!("0x2")], vec![], vec![event_key!("0x3")]],
+        &[0]
+    )]
+    #[case::alternatives_matching_both(
+        vec![vec![event_key!("0x1"), event_key!("0x4")], vec![], vec![]],
+        &[0, 1]
+    )]
+    #[case::key_at_wrong_position(vec![vec![], vec![event_key!("0x1")], vec![]], &[])]
+    #[case::more_keys_than_event(vec![vec![], vec![], vec![], vec![event_key!("0x7")]], &[])]
+    fn get_events_with_wildcard_keys(#[case] keys: Vec<Vec<EventKey>>, #[case] expected: &[usize]) {
+        let events = vec![
+            Event {
+                data: vec![],
+                keys: vec![event_key!("0x1"), event_key!("0x2"), event_key!("0x3")],
+                from_address: contract_address!("0x100"),
+            },
+            Event {
+                data: vec![],
+                keys: vec![event_key!("0x4"), event_key!("0x5"), event_key!("0x6")],
+                from_address: contract_address!("0x100"),
+            },
+        ];
+
+        let header = BlockHeader::builder().finalize_with_hash(block_hash!("0x1234"));
+        let transaction = common::Transaction {
+            hash: transaction_hash!("0x1"),
+            variant: common::TransactionVariant::InvokeV0(common::InvokeTransactionV0 {
+                calldata: vec![],
+                sender_address: ContractAddress::new_or_panic(Felt::ZERO),
+                entry_point_type: Some(common::EntryPointType::External),
+                entry_point_selector: EntryPoint(Felt::ZERO),
+                max_fee: Fee::ZERO,
+                signature: vec![],
+            }),
+        };
+        let receipt = Receipt {
+            transaction_hash: transaction.hash,
+            ..Default::default()
+        };
+
+        let mut connection = crate::StorageBuilder::in_memory()
+            .unwrap()
+            .connection()
+            .unwrap();
+        let tx = connection.transaction().unwrap();
+
+        tx.insert_block_header(&header).unwrap();
+        tx.insert_transaction_data(
+            header.number,
+            &[(transaction.clone(), receipt)],
+            Some(&[events.clone()]),
+        )
+        .unwrap();
+
+        let constraints = EventConstraints {
+            from_block: None,
+            to_block: None,
+            contract_address: None,
+            keys,
+            page_size: 1024,
+            offset: 0,
+            transaction_types: None,
+            from_addresses: None,
+        };
+
+        let result = tx
+            .events(
+                &constraints,
+                *MAX_BLOCKS_TO_SCAN,
+                *MAX_BLOOM_FILTERS_TO_LOAD,
+            )
+            .unwrap();
+
+        let expected = expected
+            .iter()
+            .map(|&idx| EmittedEvent {
+                from_address: events[idx].from_address,
+                data: events[idx].data.clone(),
+                keys: events[idx].keys.clone(),
+                block_hash: header.hash,
+                block_number: header.number,
+                transaction_hash: transaction.hash,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            PageOfEvents {
+                events: expected,
+                continuation_token: None,
+                corrupt_blocks: vec![],
+            }
+        );
+    }
 }
@@ -272,6 +272,8 @@ impl Transaction<'_> {
         const LATEST: &str = formatcp!("{PREFIX} ORDER BY b1.number DESC LIMIT 1");
         const NUMBER: &str = formatcp!("{PREFIX} WHERE b1.number = ?");
         const HASH: &str = formatcp!("{PREFIX} WHERE b1.hash = ?");
+        const L1_ACCEPTED: &str =
+            formatcp!("{PREFIX} WHERE b1.number = (SELECT l1_l2_head FROM refs WHERE idx = 1)");
 
         let handle_row = |row: &rusqlite::Row<'_>| {
             let number = row.get_block_number(0)?;
@@ -299,6 +301,7 @@ impl Transaction<'_> {
             BlockId::Latest => tx.query_row(LATEST, [], handle_row),
             BlockId::Number(number) => tx.query_row(NUMBER, params![&number], handle_row),
             BlockId::Hash(hash) => tx.query_row(HASH, params![&hash], handle_row),
+            BlockId::L1Accepted => tx.query_row(L1_ACCEPTED, [], handle_row),
         }
         .optional()
         .map_err(Into::into)
@@ -644,6 +647,23 @@ impl Transaction<'_> {
                     row.get_storage_value(0)
                 })
             }
+            BlockId::L1Accepted => {
+                let mut stmt = self.inner().prepare_cached(
+                    r"
+                    SELECT storage_value
+                    FROM storage_updates
+                    JOIN contract_addresses ON contract_addresses.id = storage_updates.contract_address_id
+                    JOIN storage_addresses ON storage_addresses.id = storage_updates.storage_address_id
+                    WHERE contract_address = ? AND storage_address = ? AND block_number <= (
+                        SELECT l1_l2_head FROM refs WHERE idx = 1
+                    )
+                    ORDER BY block_number DESC LIMIT 1
+                    ",
+                )?;
+                stmt.query_row(params![&contract_address, &key], |row| {
+                    row.get_storage_value(0)
+                })
+            }
         }
         .optional()
         .map_err(|e| e.into())
@@ -686,6 +706,19 @@ impl Transaction<'_> {
                     |row| row.get(0),
                 )
             }
+            BlockId::L1Accepted => {
+                let mut stmt = self.inner().prepare_cached(
+                    r"SELECT EXISTS(
+                        SELECT 1 FROM contract_updates WHERE contract_address = ? AND block_number <= (
+                            SELECT l1_l2_head FROM refs WHERE idx = 1
+                        )
+                    )",
+                )?;
+                stmt.query_row(
+                    params![&contract_address],
+                    |row| row.get(0),
+                )
+            }
         }
         .context("Querying that contract exists")
     }
@@ -735,6 +768,19 @@ impl Transaction<'_> {
                     row.get_contract_nonce(0)
                 })
             }
+            BlockId::L1Accepted => {
+                let mut stmt = self.inner().prepare_cached(
+                    r"
+                    SELECT nonce FROM nonce_updates
+                    JOIN contract_addresses ON contract_addresses.id = nonce_updates.contract_address_id
+                    WHERE contract_address = ? AND block_number <= (
+                        SELECT l1_l2_head FROM refs WHERE idx = 1
+                    )
+                    ORDER BY block_number DESC LIMIT 1
+                    ",
+                )?;
+                stmt.query_row(params![&contract_address], |row| row.get_contract_nonce(0))
+            }
         }
         .optional()
         .map_err(|e| e.into())
@@ -776,11 +822,62 @@ impl Transaction<'_> {
                     row.get_class_hash(0)
                 })
             }
+            BlockId::L1Accepted => {
+                let mut stmt = self.inner().prepare_cached(
+                    r"SELECT class_hash FROM contract_updates
+                WHERE contract_address = ? AND block_number <= (
+                    SELECT l1_l2_head FROM refs WHERE idx = 1
+                )
+                ORDER BY block_number DESC LIMIT 1",
+                )?;
+                stmt.query_row(params![&contract_address], |row| row.get_class_hash(0))
+            }
         }
         .optional()
         .map_err(|e| e.into())
     }
 
+    /// Contracts which replaced their class with `class_hash`, together with
+    /// the number of the block containing the replacement. Deployments of
+    /// contracts with the class are not included.
+    ///
+    /// Replacements are ordered by block number and then contract address, and
+    /// the first `offset` of them are skipped.
+    pub fn class_replacements(
+        &self,
+        class_hash: ClassHash,
+        offset: usize,
+        limit: usize,
+    ) -> anyhow::Result<Vec<(ContractAddress, BlockNumber)>> {
+        let mut stmt = self
+            .inner()
+            .prepare_cached(
+                r"SELECT cu1.contract_address, cu1.block_number
+                FROM contract_updates cu1
+                WHERE cu1.class_hash = ? AND EXISTS (
+                    SELECT 1 FROM contract_updates cu2
+                    WHERE cu2.contract_address = cu1.contract_address
+                        AND cu2.block_number < cu1.block_number
+                )
+                ORDER BY cu1.block_number, cu1.contract_address
+                LIMIT ? OFFSET ?",
+            )
+            .context("Preparing class replacements query")?;
+
+        let replacements = stmt
+            .query_map(params![&class_hash, &limit, &offset], |row| {
+                let address = row.get_contract_address(0)?;
+                let block_number = row.get_block_number(1)?;
+
+                Ok((address, block_number))
+            })
+            .context("Querying class replacements")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Iterating over class replacements")?;
+
+        Ok(replacements)
+    }
+
     pub fn reverse_contract_updates(
         &self,
         from: BlockNumber,
@@ -1268,6 +1365,56 @@ mod tests {
             assert_eq!(non_existent, None);
         }
 
+        #[test]
+        fn class_replacements() {
+            let (mut db, _, header) = setup();
+            let tx = db.transaction().unwrap();
+
+            // Another contract replaces its class with the Sierra class in a later block.
+            let header = header
+                .child_builder()
+                .finalize_with_hash(block_hash!("0xabcdef01"));
+            let later_contract = contract_address_bytes!(b"contract addr 3");
+            tx.insert_block_header(&header).unwrap();
+            tx.insert_state_update(
+                header.number,
+                &StateUpdate::default()
+                    .with_deployed_contract(later_contract, CAIRO_HASH)
+                    .with_replaced_class(CONTRACT_ADDRESS, CAIRO_HASH2),
+            )
+            .unwrap();
+            let header = header
+                .child_builder()
+                .finalize_with_hash(block_hash!("0xabcdef02"));
+            tx.insert_block_header(&header).unwrap();
+            tx.insert_state_update(
+                header.number,
+                &StateUpdate::default()
+                    .with_replaced_class(later_contract, ClassHash(SIERRA_HASH.0)),
+            )
+            .unwrap();
+
+            let replacements = tx
+                .class_replacements(ClassHash(SIERRA_HASH.0), 0, 10)
+                .unwrap();
+            // The deployment of "contract addr 2" with the class is not a replacement.
+            assert_eq!(
+                replacements,
+                vec![
+                    (CONTRACT_ADDRESS, BlockNumber::new_or_panic(1)),
+                    (later_contract, BlockNumber::new_or_panic(3)),
+                ]
+            );
+
+            let page = tx
+                .class_replacements(ClassHash(SIERRA_HASH.0), 1, 1)
+                .unwrap();
+            assert_eq!(page, replacements[1..]);
+
+            let none = tx.class_replacements(CAIRO_HASH, 0, 10).unwrap();
+            assert_eq!(none, vec![]);
+        }
+
         #[test]
         fn redeclared_classes() {
             let (mut db, _state_update, header) = setup();
@@ -10,6 +10,12 @@ use super::{EventsForBlock, TransactionDataForBlock, TransactionWithReceipt};
 use crate::prelude::*;
 use crate::BlockId;
 
+/// Context of errors caused by stored events which cannot be decompressed or
+/// deserialized, as opposed to errors querying the database.
+#[derive(Debug, thiserror::Error)]
+#[error("Stored events are corrupt")]
+pub(crate) struct CorruptEvents;
+
 pub(crate) mod compression {
     use std::sync::LazyLock;
 
@@ -261,11 +267,25 @@ impl Transaction<'_> {
             .map(|(transaction, ..)| transaction.clone()))
     }
 
+    /// Counts the transactions of a block using the transaction hash index,
+    /// without reading and decompressing the transactions themselves.
     pub fn transaction_count(&self, block: BlockId) -> anyhow::Result<usize> {
         let Some(block_number) = self.block_number(block)? else {
             return Ok(0);
         };
-        Ok(self.query_transactions_by_block(block_number)?.len())
+
+        let mut stmt = self.inner().prepare_cached(
+            r"
+            SELECT COUNT(*)
+            FROM transaction_hashes
+            WHERE block_number = ?
+            ",
+        )?;
+        let count = stmt
+            .query_row(params![&block_number], |row| row.get::<_, usize>(0))
+            .context("Counting transactions of block")?;
+
+        Ok(count)
     }
 
     pub fn transaction_data_for_block(
@@ -507,11 +527,13 @@ impl Transaction<'_> {
         };
         let events: Option<dto::EventsForBlock> = match row.get_optional_blob(0)? {
             Some(events) => {
-                let events =
-                    compression::decompress_events(events).context("Decompressing events")?;
+                let events = compression::decompress_events(events)
+                    .context("Decompressing events")
+                    .context(CorruptEvents)?;
                 let events: dto::EventsForBlock =
                     bincode::serde::decode_from_slice(&events, bincode::config::standard())
-                        .context("Deserializing events")?
+                        .context("Deserializing events")
+                        .context(CorruptEvents)?
                         .0;
 
                 Some(events)
@@ -3191,6 +3213,48 @@ mod tests {
         assert_eq!(invalid, None);
     }
 
+    #[test]
+    fn transaction_with_receipt_events_are_in_emit_order() {
+        let (_, header, body) = setup();
+        let body = body[..2].to_vec();
+
+        // A call emitting an event, then calling another contract which emits an
+        // event, followed by a final event from the outer call. The order matches
+        // neither address nor key order.
+        let events = vec![
+            Event {
+                data: vec![event_data!("0x1")],
+                from_address: contract_address!("0x2"),
+                keys: vec![event_key!("0x3")],
+            },
+            Event {
+                data: vec![event_data!("0x2")],
+                from_address: contract_address!("0x1"),
+                keys: vec![event_key!("0x1")],
+            },
+            Event {
+                data: vec![event_data!("0x3")],
+                from_address: contract_address!("0x2"),
+                keys: vec![event_key!("0x2")],
+            },
+        ];
+
+        let mut db = crate::StorageBuilder::in_memory()
+            .unwrap()
+            .connection()
+            .unwrap();
+        let tx = db.transaction().unwrap();
+        tx.insert_block_header(&header).unwrap();
+        tx.insert_transaction_data(header.number, &body, Some(&[vec![], events.clone()]))
+            .unwrap();
+
+        let result = tx
+            .transaction_with_receipt(body[1].0.hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.2, events);
+    }
+
     #[test]
     fn transaction_at_block() {
         let (mut db, header, body) = setup();
@@ -18,6 +18,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use anyhow::Context;
+use bloom::AggregateBloomCache;
 pub use connection::*;
 use event::RunningEventFilter;
 pub use event::EVENT_KEY_FILTER_LIMIT;
@@ -46,6 +47,9 @@ pub enum BlockId {
     Latest,
     Number(BlockNumber),
     Hash(BlockHash),
+    /// The latest block which has been accepted on L1, as tracked by the L1-L2
+    /// pointer.
+    L1Accepted,
 }
 
 impl From<BlockHash> for BlockId {
@@ -68,6 +72,7 @@ impl TryFrom<pathfinder_common::BlockId> for BlockId {
             pathfinder_common::BlockId::Number(x) => Ok(BlockId::Number(x)),
             pathfinder_common::BlockId::Hash(x) => Ok(BlockId::Hash(x)),
             pathfinder_common::BlockId::Latest => Ok(BlockId::Latest),
+            pathfinder_common::BlockId::L1Accepted => Ok(BlockId::L1Accepted),
             pathfinder_common::BlockId::Pending => {
                 Err("Pending is invalid within the storage context")
             }
@@ -92,6 +97,7 @@ struct Inner {
     database_path: Arc<PathBuf>,
     pool: Pool<SqliteConnectionManager>,
     running_event_filter: Arc<Mutex<RunningEventFilter>>,
+    event_filter_cache: Arc<AggregateBloomCache>,
     trie_prune_mode: TriePruneMode,
 }
 
@@ -99,6 +105,7 @@ pub struct StorageManager {
     database_path: PathBuf,
     journal_mode: JournalMode,
     running_event_filter: Arc<Mutex<RunningEventFilter>>,
+    event_filter_cache: Arc<AggregateBloomCache>,
     trie_prune_mode: TriePruneMode,
 }
 
@@ -130,6 +137,7 @@ impl StorageManager {
             database_path: Arc::new(self.database_path.clone()),
             pool,
             running_event_filter: self.running_event_filter.clone(),
+            event_filter_cache: self.event_filter_cache.clone(),
             trie_prune_mode: self.trie_prune_mode,
         }))
     }
@@ -322,6 +330,9 @@ impl StorageBuilder {
             database_path: self.database_path,
             journal_mode: self.journal_mode,
             running_event_filter: Arc::new(Mutex::new(running_event_filter)),
+            event_filter_cache: Arc::new(AggregateBloomCache::with_capacity(
+                self.bloom_filter_cache_size / BLOCK_RANGE_LEN as usize,
+            )),
             trie_prune_mode,
         })
     }
@@ -393,6 +404,7 @@ impl Storage {
         Ok(Connection::new(
             conn,
             self.0.running_event_filter.clone(),
+            self.0.event_filter_cache.clone(),
             self.0.trie_prune_mode,
         ))
     }
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use pathfinder_common::{
+    BlockBodyCommitment,
     BlockCommitmentSignatureElem,
     BlockHash,
     BlockNumber,
@@ -27,6 +28,7 @@ use pathfinder_common::{
     L1DataAvailabilityMode,
     L1ToL2MessageNonce,
     L1ToL2MessagePayloadElem,
+    L1TransactionHash,
     L2ToL1MessagePayloadElem,
     ReceiptCommitment,
     SequencerAddress,
@@ -70,6 +72,14 @@ impl<Inner: ToSql> ToSql for Option<Inner> {
     }
 }
 
+impl ToSql for L1TransactionHash {
+    fn to_sql(&self) -> ToSqlOutput<'_> {
+        use rusqlite::types::ValueRef;
+
+        ToSqlOutput::Borrowed(ValueRef::Blob(self.as_bytes()))
+    }
+}
+
 impl ToSql for StarknetVersion {
     fn to_sql(&self) -> ToSqlOutput<'_> {
         use rusqlite::types::Value;
@@ -88,6 +98,7 @@ impl ToSql for L1DataAvailabilityMode {
 }
 
 to_sql_felt!(
+    BlockBodyCommitment,
     BlockHash,
     BlockCommitmentSignatureElem,
     ByteCodeOffset,
@@ -190,6 +201,31 @@ pub trait RowExt {
         Ok(num)
     }
 
+    fn get_optional_l1_block_number<Index: RowIndex>(
+        &self,
+        index: Index,
+    ) -> rusqlite::Result<Option<L1BlockNumber>> {
+        let num = self
+            .get_optional_i64(index)?
+            // Always safe since we are fetching an i64
+            .map(|x| L1BlockNumber::new_or_panic(x as u64));
+        Ok(num)
+    }
+
+    fn get_optional_l1_transaction_hash<Index: RowIndex>(
+        &self,
+        index: Index,
+    ) -> rusqlite::Result<Option<L1TransactionHash>> {
+        let Some(blob) = self.get_optional_blob(index)? else {
+            return Ok(None);
+        };
+
+        let hash: [u8; 32] = blob
+            .try_into()
+            .map_err(|e: std::array::TryFromSliceError| FromSqlError::Other(e.into()))?;
+        Ok(Some(L1TransactionHash::from(hash)))
+    }
+
     fn get_optional_casm_hash<Index: RowIndex>(
         &self,
         index: Index,
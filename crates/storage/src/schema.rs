@@ -26,6 +26,11 @@ mod revision_0063;
 mod revision_0064;
 mod revision_0065;
 mod revision_0066;
+mod revision_0067;
+mod revision_0068;
+mod revision_0069;
+mod revision_0070;
+mod revision_0071;
 
 pub(crate) use base::base_schema;
 
@@ -60,6 +65,11 @@ pub fn migrations() -> &'static [MigrationFn] {
         revision_0064::migrate,
         revision_0065::migrate,
         revision_0066::migrate,
+        revision_0067::migrate,
+        revision_0068::migrate,
+        revision_0069::migrate,
+        revision_0070::migrate,
+        revision_0071::migrate,
     ]
 }
 
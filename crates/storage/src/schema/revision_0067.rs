@@ -0,0 +1,12 @@
+use anyhow::Context;
+
+pub(crate) fn migrate(tx: &rusqlite::Transaction<'_>) -> anyhow::Result<()> {
+    tracing::info!("Adding L1 acceptance metadata columns to l1_state");
+
+    tx.execute_batch("ALTER TABLE l1_state ADD COLUMN l1_block_number INTEGER DEFAULT NULL;")
+        .context("Adding l1_block_number column to l1_state")?;
+    tx.execute_batch("ALTER TABLE l1_state ADD COLUMN l1_transaction_hash BLOB DEFAULT NULL;")
+        .context("Adding l1_transaction_hash column to l1_state")?;
+
+    Ok(())
+}
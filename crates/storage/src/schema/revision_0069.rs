@@ -0,0 +1,15 @@
+use anyhow::Context;
+
+pub(crate) fn migrate(tx: &rusqlite::Transaction<'_>) -> anyhow::Result<()> {
+    tracing::info!("Adding block_body_commitment to block_headers");
+
+    // Existing blocks have no commitment, which is read as zero since no Starknet
+    // version commits to the block body yet.
+    tx.execute(
+        "ALTER TABLE block_headers ADD COLUMN block_body_commitment BLOB",
+        [],
+    )
+    .context("Adding block_headers.block_body_commitment column")?;
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anyhow::Context;
+
+pub(crate) fn migrate(tx: &rusqlite::Transaction<'_>) -> anyhow::Result<()> {
+    tracing::info!("Adding class metadata columns to class_definitions");
+
+    // Existing definitions are not backfilled since that requires parsing every
+    // stored definition, their metadata is computed when it is queried instead.
+    for column in [
+        "sierra_program_length INTEGER",
+        "abi_length INTEGER",
+        "compiler_version TEXT",
+    ] {
+        tx.execute(
+            &format!("ALTER TABLE class_definitions ADD COLUMN {column}"),
+            [],
+        )
+        .with_context(|| format!("Adding class_definitions column {column}"))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use anyhow::Context;
+
+pub(crate) fn migrate(tx: &rusqlite::Transaction<'_>) -> anyhow::Result<()> {
+    tracing::info!("Creating contract updates class hash index");
+
+    tx.execute(
+        "CREATE INDEX contract_updates_class_hash ON contract_updates(class_hash, block_number)",
+        [],
+    )
+    .context("Creating contract_updates_class_hash index")?;
+
+    Ok(())
+}
@@ -252,3 +252,28 @@ pub fn setup_custom_test_storage(
         },
     )
 }
+
+/// Overwrites the stored events of a block with bytes which cannot be decoded,
+/// as left behind by an interrupted write.
+pub fn corrupt_block_events(storage: &crate::Storage, block: BlockNumber) {
+    use crate::params::params;
+
+    let mut connection = storage.connection().unwrap();
+    let tx = connection.transaction().unwrap();
+    tx.inner()
+        .execute(
+            "UPDATE transactions SET events = X'DEADBEEF' WHERE block_number = ?",
+            params![&block],
+        )
+        .unwrap();
+    tx.commit().unwrap();
+}
+
+/// Drops the transactions table, causing any query of transaction data,
+/// including events, to fail.
+pub fn drop_transactions_table(storage: &crate::Storage) {
+    let mut connection = storage.connection().unwrap();
+    let tx = connection.transaction().unwrap();
+    tx.inner().execute("DROP TABLE transactions", []).unwrap();
+    tx.commit().unwrap();
+}